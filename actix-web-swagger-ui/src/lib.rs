@@ -1,10 +1,15 @@
 use actix_files::file_extension_to_mime;
-use actix_utils::future::{ok, ready};
 use actix_web::http::header::{ContentType, LOCATION};
 use actix_web::web::{self, ServiceConfig};
 use actix_web::{HttpRequest, HttpResponse, Route};
+use std::borrow::Cow;
+use std::future::ready;
 
-use swagger_ui::{Assets, Config, Spec};
+use swagger_ui::{Assets, Bytes, Config, Spec};
+
+mod scope;
+
+pub use crate::scope::{swagger_ui_scope, swagger_ui_service_scope};
 
 const CONFIG_FILE_PATH: &str = "/swagger-ui-config.json";
 
@@ -25,7 +30,11 @@ pub fn swagger(spec: Spec, config: Config) -> impl FnOnce(&mut ServiceConfig) {
     for file in Assets::iter() {
         let filename = file.as_ref();
         let content_type = content_type(filename);
-        let content = Assets::get(filename).unwrap().into_owned();
+        // a view into the embedded file, which responses share instead of copying it
+        let content = match Assets::get(filename).unwrap() {
+            Cow::Borrowed(content) => Bytes::from_static(content),
+            Cow::Owned(content) => Bytes::from(content),
+        };
 
         routes.push((format!("/{}", filename), body(content_type, content)));
     }
@@ -41,14 +50,19 @@ fn config_route(config: Config, spec_name: String) -> Route {
     web::to(move |req: HttpRequest| {
         let path = req.path().replace(CONFIG_FILE_PATH, "");
         let mut config = config.clone();
-        config.url = format!("{}/{}", path, &spec_name);
+        if config.url.is_empty() {
+            config.url = format!("{}/{}", path, &spec_name);
+        }
 
         ready(HttpResponse::Ok().json(config))
     })
 }
 
 fn spec_route(spec: Spec) -> Route {
-    let content_type = content_type(&spec.name);
+    let content_type = match spec.content_type.parse() {
+        Ok(mime) => ContentType(mime),
+        Err(_) => ContentType::json(),
+    };
     let content = spec.content;
 
     web::to(move || {
@@ -75,13 +89,13 @@ fn index_route() -> Route {
     })
 }
 
-fn body(content_type: ContentType, content: Vec<u8>) -> Route {
+fn body(content_type: ContentType, content: Bytes) -> Route {
     let handler = move || {
-        ok::<_, actix_web::Error>(
+        ready(Ok::<_, actix_web::Error>(
             HttpResponse::Ok()
                 .content_type(content_type.clone())
                 .body(content.clone()),
-        )
+        ))
     };
 
     web::to(handler)
@@ -139,16 +153,16 @@ mod tests {
     fn has_location(res: &ServiceResponse, expected_location: String) -> bool {
         let location = res.headers().get(LOCATION).unwrap();
 
-        location.eq(expected_location.as_str().into())
+        location == expected_location.as_str()
     }
 
     #[actix_rt::test]
     async fn index_redirects_with_config_url_param() {
         let prefix = "/swagger-ui";
 
-        let mut app = init_app!(prefix);
+        let app = init_app!(prefix);
 
-        let res = call_service(&mut app, get(prefix)).await;
+        let res = call_service(&app, get(prefix)).await;
         assert!(res.status().is_redirection());
         assert!(has_location(
             &res,
@@ -158,13 +172,13 @@ mod tests {
             )
         ));
 
-        let res = call_service(&mut app, get(format!("{}/index.html", prefix))).await;
+        let res = call_service(&app, get(format!("{}/index.html", prefix))).await;
         assert!(res.status().is_success());
 
-        let res = call_service(&mut app, get(format!("{}/swagger-ui-config.json", prefix))).await;
+        let res = call_service(&app, get(format!("{}/swagger-ui-config.json", prefix))).await;
         assert!(res.status().is_success());
 
-        let res = call_service(&mut app, get(format!("{}/openapi.json", prefix))).await;
+        let res = call_service(&app, get(format!("{}/openapi.json", prefix))).await;
 
         let path = env!("CARGO_MANIFEST_DIR").to_string() + "/../swagger-ui/examples/openapi.json";
         println!("Loading {}", path);
@@ -0,0 +1,162 @@
+use actix_web::body::SizedStream;
+use actix_web::http::StatusCode;
+use actix_web::web::{self, Bytes};
+use actix_web::{HttpRequest, HttpResponse, Scope};
+use std::convert::Infallible;
+use std::future::ready;
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// Returns a `Scope` serving the swagger-ui page displaying the given spec under `path`, e.g.
+/// `cfg.service(swagger_ui_scope("/docs", spec, None))`.
+///
+/// Unlike [`swagger`](crate::swagger) the responses are produced by the framework-agnostic
+/// [`SwaggerUiService`], so they match the other integrations.
+pub fn swagger_ui_scope(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> Scope {
+    swagger_ui_service_scope(path, SwaggerUiService::new(spec, config))
+}
+
+/// Returns a `Scope` serving swagger-ui with an already configured service under `path`, e.g.
+/// one with [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service_scope(path: &str, service: SwaggerUiService) -> Scope {
+    web::scope(path.trim_end_matches('/')).default_service(web::to(move |req: HttpRequest| {
+        ready(handle(&service, &req))
+    }))
+}
+
+/// Hands a request over to the core service, converting between `http` versions.
+fn handle(service: &SwaggerUiService, req: &HttpRequest) -> HttpResponse {
+    let rest = req.match_info().unprocessed();
+    let mount = MountPath::from_original(req.path(), rest);
+
+    // like `swagger`, point the page to the config unless the request picks one itself
+    let query = req.query_string();
+    let uri = if rest.trim_start_matches('/').is_empty() && !query.contains("configUrl=") {
+        let config_url = format!("configUrl={}/swagger-ui-config.json", mount.0);
+        match query {
+            "" => format!("/?{config_url}"),
+            query => format!("/?{query}&{config_url}"),
+        }
+    } else if query.is_empty() {
+        rest.to_string()
+    } else {
+        format!("{rest}?{query}")
+    };
+
+    let mut builder = http::Request::builder()
+        .method(req.method().as_str())
+        .uri(uri);
+    for (name, value) in req.headers() {
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+    let Ok(mut core_req) = builder.body(()) else {
+        return HttpResponse::BadRequest().finish();
+    };
+    core_req.extensions_mut().insert(mount);
+
+    let (parts, body) = service.handle(&core_req).into_parts();
+    let status = StatusCode::from_u16(parts.status.as_u16()).expect("status is valid");
+    let mut response = HttpResponse::build(status);
+    for (name, value) in &parts.headers {
+        response.append_header((name.as_str(), value.as_bytes()));
+    }
+    let chunks = body.into_chunks().collect::<Vec<Bytes>>();
+    let length = chunks.iter().map(|chunk| chunk.len() as u64).sum();
+    let stream = futures_util::stream::iter(chunks.into_iter().map(Ok::<_, Infallible>));
+    response.body(SizedStream::new(length, stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui_scope;
+    use actix_web::http::header::{CONTENT_TYPE, LOCATION};
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use swagger_ui::Config;
+
+    #[actix_rt::test]
+    async fn scope_serves_ui_and_keeps_other_routes() {
+        let app = init_service(
+            App::new()
+                .service(swagger_ui_scope(
+                    "/docs",
+                    swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                    None,
+                ))
+                .route("/hello", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let get = |uri: &str| TestRequest::with_uri(uri).to_request();
+
+        let res = call_service(&app, get("/docs")).await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            res.headers().get(LOCATION).unwrap(),
+            "/docs/index.html?configUrl=/docs/swagger-ui-config.json"
+        );
+
+        let res = call_service(&app, get("/docs/index.html")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/html");
+
+        let res = call_service(&app, get("/docs/swagger-ui-config.json")).await;
+        let config: Config = serde_json::from_slice(&read_body(res).await).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let res = call_service(&app, get("/docs/openapi.json")).await;
+        assert_eq!(
+            read_body(res).await,
+            include_bytes!("../../swagger-ui/examples/openapi.json").as_ref()
+        );
+
+        let res = call_service(&app, get("/docs/missing.js")).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = call_service(&app, get("/hello")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn nested_scope_serves_index_spec_and_assets() {
+        let app = init_service(
+            App::new().service(web::scope("/api").service(swagger_ui_scope(
+                "/v1/docs",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            ))),
+        )
+        .await;
+        let get = |uri: &str| TestRequest::with_uri(uri).to_request();
+
+        let res = call_service(&app, get("/api/v1/docs")).await;
+        assert_eq!(
+            res.headers().get(LOCATION).unwrap(),
+            "/api/v1/docs/index.html?configUrl=/api/v1/docs/swagger-ui-config.json"
+        );
+
+        let res = call_service(&app, get("/api/v1/docs/index.html")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/html");
+
+        let res = call_service(&app, get("/api/v1/docs/swagger-ui-config.json")).await;
+        let config: Config = serde_json::from_slice(&read_body(res).await).unwrap();
+        assert_eq!(config.url, "/api/v1/docs/openapi.json");
+
+        let res = call_service(&app, get("/api/v1/docs/openapi.json")).await;
+        assert_eq!(
+            read_body(res).await,
+            include_bytes!("../../swagger-ui/examples/openapi.json").as_ref()
+        );
+
+        let res = call_service(&app, get("/api/v1/docs/swagger-ui.css")).await;
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/css");
+        assert_eq!(
+            read_body(res).await,
+            swagger_ui::Assets::get("swagger-ui.css").unwrap().as_ref()
+        );
+    }
+}
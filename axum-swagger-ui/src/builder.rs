@@ -0,0 +1,175 @@
+use super::axum;
+use super::compat::http1::{Extensions, HeaderMap};
+use super::router::{doc_ui_service_route, root_route};
+use axum::Router;
+use std::sync::Arc;
+use swagger_ui::{CachePolicy, Config, Cors, SpecOrUrl, SwaggerUiService};
+
+type Guard = Arc<dyn Fn(&HeaderMap, &Extensions) -> bool + Send + Sync>;
+
+/// Builder of a router serving swagger_ui at a path, for options the route functions don't take.
+///
+/// ```ignore
+/// let app = Router::new().merge(
+///     SwaggerUi::new("/docs")
+///         .spec(swagger_spec_file!("./openapi.json"))
+///         .config(config)
+///         .cache_policy(CachePolicy::no_store()),
+/// );
+/// ```
+///
+/// Adding more than one spec serves them with a dropdown, like [`super::swagger_ui_multi`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    path: String,
+    specs: Vec<SpecOrUrl>,
+    config: Option<Config>,
+    cors: Option<Cors>,
+    guard: Option<Guard>,
+    cache_policy: Option<CachePolicy>,
+}
+
+impl std::fmt::Debug for SwaggerUi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwaggerUi")
+            .field("path", &self.path)
+            .field("specs", &self.specs)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SwaggerUi {
+    /// Create a builder of swagger_ui served at `path`, e.g. `/docs`, or at the root of the
+    /// router it is merged into for `/`.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            specs: vec![],
+            config: None,
+            cors: None,
+            guard: None,
+            cache_policy: None,
+        }
+    }
+
+    /// Add a spec to serve.
+    pub fn spec(mut self, spec: impl Into<SpecOrUrl>) -> Self {
+        self.specs.push(spec.into());
+        self
+    }
+
+    /// Set the config of swagger_ui.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Emit CORS headers on the spec and config, see [`SwaggerUiService::with_cors`].
+    pub fn cors(mut self, cors: Cors) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Only allow requests `guard` accepts, see [`SwaggerUiService::with_guard`].
+    pub fn guard(
+        mut self,
+        guard: impl Fn(&HeaderMap, &Extensions) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.guard = Some(Arc::new(guard));
+        self
+    }
+
+    /// Set the `Cache-Control` of the responses, see [`SwaggerUiService::with_cache_policy`].
+    pub fn cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = Some(policy);
+        self
+    }
+
+    /// Returns the configured service.
+    ///
+    /// # Panics
+    ///
+    /// If no spec was added.
+    pub fn into_service(self) -> SwaggerUiService {
+        let mut service = match <[SpecOrUrl; 1]>::try_from(self.specs) {
+            Ok([spec]) => SwaggerUiService::new(spec, self.config),
+            Err(specs) => {
+                let config = self.config;
+                SwaggerUiService::multi(specs.into_iter().map(|spec| (spec, config.clone())))
+            }
+        };
+        if let Some(cors) = self.cors {
+            service = service.with_cors(cors);
+        }
+        if let Some(guard) = self.guard {
+            service = service.with_guard(move |headers, extensions| guard(headers, extensions));
+        }
+        if let Some(policy) = self.cache_policy {
+            service = service.with_cache_policy(policy);
+        }
+        service
+    }
+
+    /// Returns the router serving swagger_ui.
+    pub fn into_router<S>(self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let path = self.path.clone();
+        let service = self.into_service();
+        if path.trim_matches('/').is_empty() {
+            root_route(service)
+        } else {
+            Router::new().nest(&path, doc_ui_service_route(service))
+        }
+    }
+}
+
+impl<S> From<SwaggerUi> for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn from(value: SwaggerUi) -> Self {
+        value.into_router()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{axum, SwaggerUi};
+    use axum::body::Body;
+    use axum::http::header::{AUTHORIZATION, CACHE_CONTROL};
+    use axum::http::{Request, StatusCode};
+    use axum::Router;
+    use swagger_ui::CachePolicy;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn builds_a_guarded_router() {
+        let app: Router = Router::new().merge(
+            SwaggerUi::new("/docs")
+                .spec(swagger_ui::swagger_spec_file!(
+                    "../../swagger-ui/examples/openapi.json"
+                ))
+                .guard(|headers, _| headers.contains_key("authorization"))
+                .cache_policy(CachePolicy::no_store()),
+        );
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+        let response = app
+            .clone()
+            .oneshot(get("/docs/openapi.json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let req = Request::get("/docs/openapi.json")
+            .header(AUTHORIZATION, "Bearer abc")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+    }
+}
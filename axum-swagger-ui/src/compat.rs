@@ -0,0 +1,60 @@
+//! Conversions between axum and the core services, for the axum versions built on `http` 1 like
+//! the core services, so that only bodies are converted.
+
+use super::axum;
+pub(crate) use axum::extract::Request;
+pub(crate) use axum::http as http1;
+#[cfg(feature = "oidc")]
+pub(crate) use axum::middleware::Next;
+use axum::response::Response;
+#[cfg(feature = "oidc")]
+use std::borrow::Cow;
+
+/// A request as the core services take it.
+pub(crate) type CoreRequest = Request;
+
+/// Converts `req` for the core services.
+pub(crate) fn into_core(req: Request) -> CoreRequest {
+    req
+}
+
+/// Converts `req` for the core services reading its body, failing with `413 Payload Too Large`
+/// if it has more than `limit` bytes.
+#[cfg(any(feature = "proxy", feature = "sessions"))]
+pub(crate) async fn into_core_with_body(
+    req: Request,
+    limit: usize,
+) -> Result<http1::Request<axum::body::Bytes>, Response> {
+    use axum::response::IntoResponse;
+
+    let (parts, body) = req.into_parts();
+    match axum::body::to_bytes(body, limit).await {
+        Ok(body) => Ok(http1::Request::from_parts(parts, body)),
+        Err(_) => Err(http1::StatusCode::PAYLOAD_TOO_LARGE.into_response()),
+    }
+}
+
+/// Returns `uri` as the core services take it.
+#[cfg(feature = "oidc")]
+pub(crate) fn core_uri(uri: &http1::Uri) -> Cow<'_, http1::Uri> {
+    Cow::Borrowed(uri)
+}
+
+/// Returns `headers` as the core services take them.
+#[cfg(feature = "oidc")]
+pub(crate) fn core_headers(headers: &http1::HeaderMap) -> Cow<'_, http1::HeaderMap> {
+    Cow::Borrowed(headers)
+}
+
+/// Converts a response of the core services.
+pub(crate) fn from_core(response: http1::Response<swagger_ui::Body>) -> Response {
+    response.map(axum::body::Body::new)
+}
+
+/// Reads the body of `response`.
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) async fn body_bytes(response: Response) -> axum::body::Bytes {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body can be read")
+}
@@ -0,0 +1,109 @@
+use super::axum;
+use super::compat::{from_core, into_core, Request};
+use axum::http::uri::PathAndQuery;
+use axum::http::Uri;
+use axum::response::Response;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A `tower::Layer` that serves swagger-ui under a path prefix and passes every other request on
+/// to the wrapped service.
+///
+/// This is an alternative to [`SwaggerUiExt::swagger_ui`](super::SwaggerUiExt::swagger_ui) for
+/// services that can't have additional routes nested into them.
+#[derive(Clone)]
+pub struct SwaggerUiLayer {
+    path: Arc<str>,
+    service: SwaggerUiService,
+}
+
+impl SwaggerUiLayer {
+    /// Create a layer serving the specified spec and config with swagger-ui under `path`.
+    pub fn new(path: &str, spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self::from_service(path, SwaggerUiService::new(spec, config))
+    }
+
+    /// Create a layer serving swagger-ui with an already configured service under `path`.
+    pub fn from_service(path: &str, service: SwaggerUiService) -> Self {
+        Self {
+            path: path.trim_end_matches('/').into(),
+            service,
+        }
+    }
+}
+
+impl<S> Layer<S> for SwaggerUiLayer {
+    type Service = SwaggerUiMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SwaggerUiMiddleware {
+            inner,
+            path: self.path.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+/// The service produced by [`SwaggerUiLayer`].
+#[derive(Clone)]
+pub struct SwaggerUiMiddleware<S> {
+    inner: S,
+    path: Arc<str>,
+    service: SwaggerUiService,
+}
+
+impl<S> SwaggerUiMiddleware<S> {
+    /// Returns the part of `path` below the mount prefix, if `path` is inside of it.
+    ///
+    /// When mounted at the root, only the paths swagger-ui responds to are inside of it.
+    fn strip_prefix<'a>(&self, path: &'a str) -> Option<&'a str> {
+        if self.path.is_empty() {
+            return self.service.serves(path).then_some(path);
+        }
+        let rest = path.strip_prefix(self.path.as_ref())?;
+        (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+    }
+}
+
+impl<S> Service<Request> for SwaggerUiMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let Some(rest) = self.strip_prefix(req.uri().path()) else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let path_and_query = match req.uri().query() {
+            Some(query) => format!("/{}?{query}", rest.trim_start_matches('/')),
+            None => format!("/{}", rest.trim_start_matches('/')),
+        };
+        let mut parts = req.uri().clone().into_parts();
+        parts.path_and_query = Some(
+            PathAndQuery::try_from(path_and_query).expect("stripped path is a valid uri path"),
+        );
+        let uri = Uri::from_parts(parts).expect("stripped uri is a valid uri");
+
+        *req.uri_mut() = uri;
+        let mut req = into_core(req);
+        req.extensions_mut()
+            .insert(MountPath(self.path.to_string()));
+
+        let response = from_core(self.service.handle(&req));
+        Box::pin(async move { Ok(response) })
+    }
+}
@@ -0,0 +1,1149 @@
+use super::compat::{from_core, into_core, Request};
+use super::{axum, WILDCARD};
+use axum::extract::OriginalUri;
+use axum::response::Response;
+use axum::routing::{any, MethodRouter};
+use axum::Router;
+#[cfg(feature = "sessions")]
+use swagger_ui::AuthorizationService;
+#[cfg(feature = "mock")]
+use swagger_ui::MockService;
+#[cfg(feature = "oidc")]
+use swagger_ui::OidcGuard;
+#[cfg(feature = "proxy")]
+use swagger_ui::ProxyService;
+use swagger_ui::{
+    docs_disabled, ChangelogService, Config, DiffService, DocService, DocUi, LandingPageService,
+    MountPath, Spec, SpecOrUrl, SpecService, SwaggerUi, SwaggerUiService,
+};
+#[cfg(feature = "console")]
+use swagger_ui::{Console, ConsoleConfig};
+#[cfg(feature = "swagger-editor")]
+use swagger_ui::{EditorMode, SwaggerEditor};
+#[cfg(feature = "elements")]
+use swagger_ui::{Elements, ElementsConfig};
+#[cfg(feature = "rapidoc")]
+use swagger_ui::{Rapidoc, RapidocConfig};
+#[cfg(feature = "redoc")]
+use swagger_ui::{Redoc, RedocConfig};
+#[cfg(feature = "scalar")]
+use swagger_ui::{Scalar, ScalarConfig};
+
+/// Helper trait to allow `route.swagger_ui_route(...)`
+pub trait SwaggerUiExt {
+    fn swagger_ui(
+        self,
+        path: &str,
+        spec: impl Into<SpecOrUrl>,
+        config: impl Into<Option<Config>>,
+    ) -> Self;
+}
+
+impl<S> SwaggerUiExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn swagger_ui(
+        self,
+        path: &str,
+        spec: impl Into<SpecOrUrl>,
+        config: impl Into<Option<Config>>,
+    ) -> Self {
+        self.doc_ui::<SwaggerUi>(path, spec, config)
+    }
+}
+
+/// Helper trait to allow `route.doc_ui::<Redoc>(...)`, mounting any viewer the way
+/// [`SwaggerUiExt::swagger_ui`] mounts swagger_ui.
+pub trait DocUiExt {
+    fn doc_ui<U: DocUi>(
+        self,
+        path: &str,
+        spec: impl Into<SpecOrUrl>,
+        config: impl Into<Option<U::Config>>,
+    ) -> Self;
+}
+
+impl<S> DocUiExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn doc_ui<U: DocUi>(
+        self,
+        path: &str,
+        spec: impl Into<SpecOrUrl>,
+        config: impl Into<Option<U::Config>>,
+    ) -> Self {
+        if path.trim_matches('/').is_empty() {
+            self.merge(doc_ui_root_route::<U, S>(spec, config))
+        } else {
+            self.nest(path, doc_ui_route::<U, S>(spec, config))
+        }
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with swagger_ui
+pub fn swagger_ui_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<SwaggerUi, S>(spec, config)
+}
+
+/// creates a route serving swagger_ui with an already configured service, e.g. one with
+/// [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service_route<S>(service: SwaggerUiService) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_service_route(service)
+}
+
+/// creates a route like [`swagger_ui_route`] if `enabled`, and an empty router otherwise, e.g.
+/// `swagger_ui_if(cfg!(debug_assertions), spec, None)` to leave the docs out of release builds.
+///
+/// Like every route serving a viewer, it is empty as well if [`swagger_ui::DISABLED_VAR`] is set
+/// in the environment.
+pub fn swagger_ui_if<S>(
+    enabled: bool,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if !enabled {
+        return Router::new();
+    }
+    swagger_ui_route(spec, config)
+}
+
+/// creates a route serving every spec of `specs` in one swagger_ui nested at `path`, with a
+/// dropdown to pick them, see [`SwaggerUiService::multi`].
+///
+/// The assets are routed once for all specs, e.g.
+/// `swagger_ui_multi("/docs", vec![(v1.into(), None), (v2.into(), None)])`.
+pub fn swagger_ui_multi<S>(path: &str, specs: Vec<(SpecOrUrl, Option<Config>)>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let service = SwaggerUiService::multi(specs);
+    if path.trim_matches('/').is_empty() {
+        root_route(service)
+    } else {
+        Router::new().nest(path, doc_ui_service_route(service))
+    }
+}
+
+/// creates a route that serves the specified spec and config with swagger_ui at the root of the
+/// router it is merged into.
+///
+/// Instead of a wildcard, only the paths swagger_ui responds to are registered, so the other
+/// routes and the fallback of the surrounding router keep working.
+pub fn swagger_ui_root_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_root_route::<SwaggerUi, S>(spec, config)
+}
+
+/// creates a route that is configured to serve the specified spec and config with the viewer
+/// `U`, e.g. `doc_ui_route::<Redoc, _>(spec, None)`.
+pub fn doc_ui_route<U, S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<U::Config>>,
+) -> Router<S>
+where
+    U: DocUi,
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_service_route(DocService::<U>::new(spec, config))
+}
+
+/// creates a route serving a viewer with an already configured service, e.g. one with
+/// [`DocService::with_cors`] applied.
+pub fn doc_ui_service_route<U, S>(service: DocService<U>) -> Router<S>
+where
+    U: DocUi,
+    S: Clone + Send + Sync + 'static,
+{
+    if docs_disabled() {
+        return Router::new();
+    }
+    let handler = handler(service);
+    Router::new()
+        .route("/", handler.clone())
+        .route(WILDCARD, handler)
+}
+
+/// creates a route that serves the specified spec and config with the viewer `U` at the root of
+/// the router it is merged into, registering only the paths the viewer responds to.
+pub fn doc_ui_root_route<U, S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<U::Config>>,
+) -> Router<S>
+where
+    U: DocUi,
+    S: Clone + Send + Sync + 'static,
+{
+    root_route(DocService::<U>::new(spec, config))
+}
+
+pub(super) fn root_route<U, S>(service: DocService<U>) -> Router<S>
+where
+    U: DocUi,
+    S: Clone + Send + Sync + 'static,
+{
+    if docs_disabled() {
+        return Router::new();
+    }
+    let paths = service.paths();
+    let handler = handler(service);
+    paths.iter().fold(Router::new(), |router, path| {
+        router.route(&format!("/{path}"), handler.clone())
+    })
+}
+
+/// Helper trait to allow `route.redoc("/redoc", spec)`
+#[cfg(feature = "redoc")]
+pub trait RedocExt {
+    fn redoc(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "redoc")]
+impl<S> RedocExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn redoc(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<Redoc>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with ReDoc
+#[cfg(feature = "redoc")]
+pub fn redoc_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<RedocConfig>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<Redoc, S>(spec, config)
+}
+
+/// Helper trait to allow `route.rapidoc("/rapidoc", spec)`
+#[cfg(feature = "rapidoc")]
+pub trait RapidocExt {
+    fn rapidoc(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "rapidoc")]
+impl<S> RapidocExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn rapidoc(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<Rapidoc>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with RapiDoc
+#[cfg(feature = "rapidoc")]
+pub fn rapidoc_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<RapidocConfig>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<Rapidoc, S>(spec, config)
+}
+
+/// Helper trait to allow `route.scalar("/reference", spec)`
+#[cfg(feature = "scalar")]
+pub trait ScalarExt {
+    fn scalar(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "scalar")]
+impl<S> ScalarExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn scalar(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<Scalar>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with Scalar
+#[cfg(feature = "scalar")]
+pub fn scalar_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<ScalarConfig>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<Scalar, S>(spec, config)
+}
+
+/// Helper trait to allow `route.console("/console", spec)`
+#[cfg(feature = "console")]
+pub trait ConsoleExt {
+    fn console(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "console")]
+impl<S> ConsoleExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn console(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<Console>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with the
+/// dependency-free console
+#[cfg(feature = "console")]
+pub fn console_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<ConsoleConfig>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<Console, S>(spec, config)
+}
+
+/// Helper trait to allow `route.elements("/elements", spec)`
+#[cfg(feature = "elements")]
+pub trait ElementsExt {
+    fn elements(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "elements")]
+impl<S> ElementsExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn elements(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<Elements>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and config with Stoplight Elements
+#[cfg(feature = "elements")]
+pub fn elements_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<ElementsConfig>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<Elements, S>(spec, config)
+}
+
+/// Helper trait to allow `route.swagger_editor("/editor", spec)`
+#[cfg(feature = "swagger-editor")]
+pub trait SwaggerEditorExt {
+    fn swagger_editor(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self;
+}
+
+#[cfg(feature = "swagger-editor")]
+impl<S> SwaggerEditorExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn swagger_editor(self, path: &str, spec: impl Into<SpecOrUrl>) -> Self {
+        self.doc_ui::<SwaggerEditor>(path, spec, None)
+    }
+}
+
+/// creates a route that is configured to serve the specified spec and mode with swagger-editor in `mode`,
+/// read-only by default
+#[cfg(feature = "swagger-editor")]
+pub fn swagger_editor_route<S>(
+    spec: impl Into<SpecOrUrl>,
+    mode: impl Into<Option<EditorMode>>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    doc_ui_route::<SwaggerEditor, S>(spec, mode)
+}
+
+/// creates a route that serves only the spec, e.g. at `/.well-known/openapi.json` next to the
+/// swagger_ui mount. Pass `&service` to share the spec and CORS configuration of a mounted
+/// [`SwaggerUiService`].
+pub fn spec_route<S>(spec: impl Into<SpecService>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let spec = spec.into();
+    any(move |req: Request| {
+        let spec = spec.clone();
+        async move { from_core(spec.handle(&into_core(req))) }
+    })
+}
+
+/// creates a route that serves the landing page of `registry`, listing the mounted APIs with
+/// links to their docs, e.g. at `/` or `/docs`.
+pub fn landing_route<S>(page: impl Into<LandingPageService>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let page = page.into();
+    any(move |req: Request| {
+        let page = page.clone();
+        async move { from_core(page.handle(&into_core(req))) }
+    })
+}
+
+/// creates a route comparing versions of a spec, e.g. `/docs/diff?from=v1&to=v2`, see
+/// [`DiffService`].
+pub fn diff_route<S>(service: impl Into<DiffService>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let service = service.into();
+    any(move |req: Request| {
+        let service = service.clone();
+        async move { from_core(service.handle(&into_core(req))) }
+    })
+}
+
+/// creates a route rendering the changes between versions of a spec, e.g.
+/// `/docs/changelog?from=v1&to=v2`, see [`ChangelogService`].
+pub fn changelog_route<S>(service: impl Into<ChangelogService>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let service = service.into();
+    any(move |req: Request| {
+        let service = service.clone();
+        async move { from_core(service.handle(&into_core(req))) }
+    })
+}
+
+/// creates a route answering every operation of a spec with an example response, see
+/// [`MockService`], e.g. nested at `/mock` next to the docs.
+#[cfg(feature = "mock")]
+pub fn mock_route<S>(mock: MockService) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let handler = any(move |req: Request| {
+        let mock = mock.clone();
+        async move { from_core(mock.handle(&into_core(req))) }
+    });
+    Router::new()
+        .route("/", handler.clone())
+        .route(WILDCARD, handler)
+}
+
+/// creates a route forwarding try-it-out requests to other origins, see [`ProxyService`].
+///
+/// Route it at `proxy` next to docs served with
+/// [`with_proxy`](swagger_ui::DocService::with_proxy), e.g. at `/docs/proxy`.
+#[cfg(feature = "proxy")]
+pub fn proxy_route<S>(proxy: ProxyService) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    use super::compat::into_core_with_body;
+    use axum::extract::ConnectInfo;
+    use std::net::SocketAddr;
+    use swagger_ui::ProxyClient;
+
+    any(move |req: Request| {
+        let proxy = proxy.clone();
+        async move {
+            let connection = req.extensions().get::<ConnectInfo<SocketAddr>>();
+            let client = connection.map(|ConnectInfo(addr)| ProxyClient(addr.ip()));
+            match into_core_with_body(req, proxy.max_request_size()).await {
+                Ok(mut req) => {
+                    if let (None, Some(client)) = (req.extensions().get::<ProxyClient>(), client) {
+                        req.extensions_mut().insert(client);
+                    }
+                    from_core(proxy.handle(req).await)
+                }
+                Err(response) => response,
+            }
+        }
+    })
+}
+
+/// creates a route storing the authorization entered into swagger_ui per session, see
+/// [`AuthorizationService`].
+///
+/// Route it at `authorization` next to docs served with
+/// [`with_session_authorization`](swagger_ui::DocService::with_session_authorization), e.g. at
+/// `/docs/authorization`.
+#[cfg(feature = "sessions")]
+pub fn authorization_route<S>(service: AuthorizationService) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    use super::compat::into_core_with_body;
+
+    any(move |req: Request| {
+        let service = service.clone();
+        async move {
+            match into_core_with_body(req, service.max_request_size()).await {
+                Ok(req) => from_core(service.handle(&req).await),
+                Err(response) => response,
+            }
+        }
+    })
+}
+
+/// protects every route of `router` with an OpenID Connect login, see [`OidcGuard`].
+///
+/// Users that aren't logged in are redirected to the provider, and the handlers behind the
+/// guard find the [`OidcUser`](swagger_ui::OidcUser) in the request extensions. The callback
+/// has to be a path of `router`, e.g. `/docs/oidc/callback` for
+/// `nest("/docs", oidc_guard(swagger_ui_route(spec, None), guard))`.
+#[cfg(feature = "oidc")]
+pub fn oidc_guard<S>(router: Router<S>, guard: OidcGuard) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    use super::compat::{core_headers, core_uri, Next};
+    use axum::middleware::from_fn;
+
+    router.layer(from_fn(move |mut req: Request, next: Next| {
+        let guard = guard.clone();
+        async move {
+            let checked = {
+                let uri = req
+                    .extensions()
+                    .get::<OriginalUri>()
+                    .map_or(req.uri(), |original| &original.0);
+                let headers = core_headers(req.headers());
+                guard.check(&core_uri(uri), &headers).await
+            };
+            match checked {
+                Ok(user) => {
+                    req.extensions_mut().insert(user);
+                    next.run(req).await
+                }
+                Err(response) => from_core(response),
+            }
+        }
+    }))
+}
+
+/// serves the embedded swagger_ui asset at `path`, see [`swagger_ui::serve_asset`].
+pub fn serve_asset(path: &str) -> Response {
+    from_core(swagger_ui::serve_asset(path))
+}
+
+/// serves the content of `spec`, see [`swagger_ui::serve_spec`].
+pub fn serve_spec(spec: &Spec) -> Response {
+    from_core(swagger_ui::serve_spec(spec))
+}
+
+/// serves `config` for swagger_ui mounted at `base`, see [`swagger_ui::serve_config`].
+pub fn serve_config(config: &Config, base: &str) -> Response {
+    from_core(swagger_ui::serve_config(config, base))
+}
+
+fn handler<U, S>(service: DocService<U>) -> MethodRouter<S>
+where
+    U: DocUi,
+    S: Clone + Send + Sync + 'static,
+{
+    any(move |original: OriginalUri, req: Request| {
+        let service = service.clone();
+        async move { handle(&service, original, req) }
+    })
+}
+
+/// Hands a request nested into a router over to the framework-agnostic service.
+fn handle<U: DocUi>(service: &DocService<U>, original: OriginalUri, req: Request) -> Response {
+    let mount = MountPath::from_original(original.path(), req.uri().path());
+    let mut req = into_core(req);
+    req.extensions_mut().insert(mount);
+    from_core(service.handle(&req))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compat::body_bytes;
+    use super::super::SwaggerUiLayer;
+    use super::{
+        axum, serve_asset, serve_config, serve_spec, spec_route, swagger_ui_if, swagger_ui_multi,
+        swagger_ui_route, swagger_ui_service_route, SwaggerUiExt, WILDCARD,
+    };
+    use axum::body::Body;
+    use axum::extract::Path;
+    use axum::http::header::{ACCESS_CONTROL_ALLOW_ORIGIN, ALLOW, CONTENT_TYPE, LOCATION, ORIGIN};
+    use axum::http::{Method, Request, StatusCode};
+    use axum::Router;
+    use swagger_ui::{Config, Cors, Spec, SwaggerUiService};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        swagger_ui_route(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn does_redirect() {
+        let app = app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    }
+
+    #[tokio::test]
+    async fn does_index() {
+        let app = app();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        body_bytes(response).await;
+    }
+
+    #[tokio::test]
+    async fn mounts_nothing_when_disabled() {
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let app: Router = Router::new()
+            .nest("/docs", swagger_ui_if(false, spec, None))
+            .route("/health", axum::routing::get(|| async { "ok" }));
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let docs = app.clone().oneshot(get("/docs/index.html")).await.unwrap();
+        assert_eq!(docs.status(), StatusCode::NOT_FOUND);
+        let health = app.oneshot(get("/health")).await.unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn mounts_multiple_specs_on_one_route() {
+        let v1 = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let v2 = Spec::builder().name("v2.json").json("{}");
+        let app: Router = swagger_ui_multi("/docs", vec![(v1.into(), None), (v2.into(), None)]);
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/docs/v2.json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app
+            .oneshot(get("/docs/swagger-ui-config.json"))
+            .await
+            .unwrap();
+        let body = body_bytes(response).await;
+        let config: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config["urls"][1]["url"], "v2.json");
+    }
+
+    #[tokio::test]
+    async fn rejects_other_methods() {
+        let app = app();
+        let post = |uri: &str| {
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(post("/openapi.json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "GET, HEAD");
+
+        let response = app.oneshot(post("/missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn service_route_emits_cors_headers() {
+        let app: Router = swagger_ui_service_route(
+            SwaggerUiService::new(
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            )
+            .with_cors(Cors::any()),
+        );
+
+        let response = app
+            .oneshot(
+                Request::get("/openapi.json")
+                    .header(ORIGIN, "https://portal.example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+
+    #[tokio::test]
+    async fn spec_route_serves_spec_outside_the_mount() {
+        let service = SwaggerUiService::new(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+        let app: Router = Router::new()
+            .route("/.well-known/openapi.json", spec_route(&service))
+            .nest("/docs", swagger_ui_service_route(service));
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(get("/.well-known/openapi.json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let well_known = body_bytes(response).await;
+
+        let response = app.oneshot(get("/docs/openapi.json")).await.unwrap();
+        let mounted = body_bytes(response).await;
+        assert_eq!(well_known, mounted);
+    }
+
+    #[tokio::test]
+    async fn public_handlers_compose_custom_routes() {
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let config = Config {
+            url: "api.json".to_string(),
+            ..Default::default()
+        };
+        let app: Router = Router::new()
+            .route(
+                &format!("/ui{WILDCARD}"),
+                axum::routing::get(|Path(file): Path<String>| async move { serve_asset(&file) }),
+            )
+            .route(
+                "/ui/api.json",
+                axum::routing::get(move || async move { serve_spec(&spec) }),
+            )
+            .route(
+                "/ui/swagger-ui-config.json",
+                axum::routing::get(move || async move { serve_config(&config, "/ui") }),
+            );
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/ui/index.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone().oneshot(get("/ui/api.json")).await.unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+
+        let response = app
+            .oneshot(get("/ui/swagger-ui-config.json"))
+            .await
+            .unwrap();
+        let body = body_bytes(response).await;
+        let config: Config = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config.url, "/ui/api.json");
+    }
+
+    #[tokio::test]
+    async fn does_config() {
+        let app = app();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/swagger-ui-config.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let body = body_bytes(response).await;
+        let config: Config =
+            serde_json::from_str(std::str::from_utf8(body.as_ref()).unwrap()).unwrap();
+        assert_eq!(config.url, "/openapi.json");
+    }
+
+    #[tokio::test]
+    async fn layer_serves_prefix_and_forwards_rest() {
+        let app = Router::new()
+            .route("/hello", axum::routing::get(|| async { "hello" }))
+            .layer(SwaggerUiLayer::new(
+                "/docs",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            ));
+
+        let get = |uri: &str| {
+            Request::builder()
+                .method(Method::GET)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(get("/docs")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+
+        let response = app.clone().oneshot(get("/docs/index.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(get("/docs/swagger-ui-config.json"))
+            .await
+            .unwrap();
+        let body = body_bytes(response).await;
+        let config: Config = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let response = app.clone().oneshot(get("/hello")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/docsearch")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn root_mount_keeps_other_routes_and_fallback() {
+        let app = Router::new()
+            .route("/hello", axum::routing::get(|| async { "hello" }))
+            .swagger_ui(
+                "/",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            )
+            .fallback(|| async { StatusCode::IM_A_TEAPOT });
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/index.html");
+
+        let response = app.clone().oneshot(get("/index.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(get("/swagger-ui-config.json"))
+            .await
+            .unwrap();
+        let body = body_bytes(response).await;
+        let config: Config = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config.url, "/openapi.json");
+
+        let response = app.clone().oneshot(get("/openapi.json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone().oneshot(get("/hello")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+    }
+
+    #[tokio::test]
+    async fn root_layer_only_intercepts_served_paths() {
+        let app = Router::new()
+            .route("/hello", axum::routing::get(|| async { "hello" }))
+            .layer(SwaggerUiLayer::new(
+                "/",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            ));
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/index.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/hello")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[cfg(feature = "redoc")]
+    #[tokio::test]
+    async fn redoc_is_nested_next_to_swagger_ui() {
+        use super::RedocExt;
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let app: Router = Router::new()
+            .swagger_ui("/docs", spec.clone(), None)
+            .redoc("/redoc", spec);
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/redoc")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/redoc/index.html");
+
+        let response = app.clone().oneshot(get("/redoc/index.html")).await.unwrap();
+        let body = body_bytes(response).await;
+        let page = String::from_utf8(body.to_vec()).unwrap();
+        assert!(page.contains(r#"Redoc.init("/redoc/openapi.json""#));
+
+        let response = app
+            .clone()
+            .oneshot(get("/redoc/redoc.standalone.js"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/docs/index.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[cfg(feature = "rapidoc")]
+    #[tokio::test]
+    async fn rapidoc_is_served_at_the_root() {
+        use super::RapidocExt;
+
+        let app: Router = Router::new()
+            .route("/hello", axum::routing::get(|| async { "hello" }))
+            .rapidoc(
+                "/",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            );
+
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/index.html")).await.unwrap();
+        let body = body_bytes(response).await;
+        let page = String::from_utf8(body.to_vec()).unwrap();
+        assert!(page.contains(r#"<rapi-doc spec-url="/openapi.json">"#));
+
+        let response = app.clone().oneshot(get("/rapidoc-min.js")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/hello")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn doc_ui_mounts_viewers_by_type() {
+        use super::DocUiExt;
+        use swagger_ui::{DocUi, SwaggerUi};
+
+        async fn index<U: DocUi>(config: Option<U::Config>) -> String {
+            let app: Router = Router::new().doc_ui::<U>(
+                "/docs",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                config,
+            );
+            let response = app
+                .clone()
+                .oneshot(Request::get("/docs").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+
+            let response = app
+                .oneshot(
+                    Request::get("/docs/index.html")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+            let body = body_bytes(response).await;
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+
+        assert!(index::<SwaggerUi>(None).await.contains("swagger-ui"));
+        #[cfg(feature = "redoc")]
+        assert!(index::<swagger_ui::Redoc>(None)
+            .await
+            .contains(r#"Redoc.init("/docs/openapi.json""#));
+    }
+
+    #[tokio::test]
+    async fn landing_route_links_the_mounted_docs() {
+        use super::landing_route;
+        use swagger_ui::{SpecRegistry, UrlObject};
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let registry =
+            SpecRegistry::default().register(&spec, [UrlObject::new("Swagger UI", "/docs")]);
+        let app: Router = Router::new()
+            .route("/", landing_route(&registry))
+            .swagger_ui("/docs", spec, None);
+
+        let response = app
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let body = body_bytes(response).await;
+        let page = String::from_utf8(body.to_vec()).unwrap();
+        assert!(page.contains(r#"Swagger Petstore<span class="version">1.0.5</span>"#));
+        assert!(page.contains(r#"<a href="/docs">Swagger UI</a>"#));
+    }
+
+    #[tokio::test]
+    async fn diff_route_compares_versions() {
+        use super::diff_route;
+        use swagger_ui::DiffService;
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let app: Router = Router::new().route(
+            "/docs/diff",
+            diff_route(DiffService::new([("v1", spec.clone()), ("v2", spec)])),
+        );
+
+        let response = app
+            .oneshot(
+                Request::get("/docs/diff?from=v1&to=v2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_bytes(response).await;
+        assert_eq!(body, r#"{"breaking":false,"changes":[]}"#);
+    }
+
+    #[tokio::test]
+    async fn changelog_route_renders_the_latest_changes() {
+        use super::changelog_route;
+        use swagger_ui::DiffService;
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let diff = DiffService::new([("v1", spec.clone()), ("v2", spec)]);
+        let app: Router = Router::new().route("/docs/changelog", changelog_route(diff));
+
+        let response = app
+            .oneshot(Request::get("/docs/changelog").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let body = body_bytes(response).await;
+        assert!(String::from_utf8_lossy(&body).contains("No changes."));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn mock_route_answers_operations() {
+        use super::mock_route;
+        use swagger_ui::MockService;
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let mock = MockService::new(&spec).unwrap();
+        let app: Router = Router::new()
+            .nest("/mock", mock_route(mock))
+            .swagger_ui("/docs", spec, None);
+
+        let response = app
+            .oneshot(
+                Request::get("/mock/v2/store/inventory")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    }
+
+    #[cfg(feature = "proxy")]
+    #[tokio::test]
+    async fn proxy_route_only_forwards_allowed_hosts() {
+        use super::{proxy_route, swagger_ui_service_route};
+        use swagger_ui::{Proxy, ProxyService, SwaggerUiService};
+
+        let spec = swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json");
+        let docs = SwaggerUiService::new(spec, None).with_proxy();
+        let proxy = ProxyService::new(Proxy::hosts(["api.example.com"]));
+        let app: Router = Router::new()
+            .route("/docs/proxy", proxy_route(proxy))
+            .nest("/docs", swagger_ui_service_route(docs));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/docs/index.html")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = body_bytes(response).await;
+        assert!(String::from_utf8_lossy(&body).contains("requestInterceptor"));
+
+        let response = app
+            .oneshot(
+                Request::get("/docs/proxy?url=https%3A%2F%2Fexample.com%2F")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[cfg(feature = "sessions")]
+    #[tokio::test]
+    async fn authorization_route_stores_per_session() {
+        use super::authorization_route;
+        use swagger_ui::{AuthorizationService, MemoryAuthorizationStore};
+
+        let service =
+            AuthorizationService::new(MemoryAuthorizationStore::default(), |headers, _| {
+                Some(headers.get("cookie")?.to_str().ok()?.to_string())
+            });
+        let app: Router = Router::new().route("/docs/authorization", authorization_route(service));
+        let send = |method: Method, body: &'static str| {
+            Request::builder()
+                .method(method)
+                .uri("/docs/authorization")
+                .header("cookie", "session=1")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let put = app
+            .clone()
+            .oneshot(send(Method::PUT, r#"{"token":{"value":"x"}}"#))
+            .await
+            .unwrap();
+        assert_eq!(put.status(), StatusCode::NO_CONTENT);
+        let get = app.oneshot(send(Method::GET, "")).await.unwrap();
+        let body = body_bytes(get).await;
+        assert_eq!(body, r#"{"token":{"value":"x"}}"#);
+    }
+}
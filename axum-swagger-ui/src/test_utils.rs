@@ -0,0 +1,101 @@
+//! Helpers to smoke-test swagger-ui mounted into a router from integration tests.
+//!
+//! ```rust,ignore
+//! let app = Router::new().swagger_ui("/docs", spec, None);
+//! test_utils::assert_serves_ui(app.clone(), "/docs").await;
+//! assert_eq!(test_utils::fetch_config(app, "/docs").await.url, "/docs/openapi.json");
+//! ```
+
+use super::axum;
+use super::compat::body_bytes;
+use axum::body::Body;
+use axum::http::header::{CONTENT_TYPE, LOCATION};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use std::future::poll_fn;
+use swagger_ui::Config;
+use tower_service::Service;
+
+/// Asserts that `router` serves swagger-ui at `mount`: the mount point redirects to
+/// `index.html`, which is served along with the config and the spec it points to.
+pub async fn assert_serves_ui(router: Router, mount: &str) {
+    let base = mount.trim_end_matches('/');
+
+    let mount = if base.is_empty() { "/" } else { base };
+    let response = get(&router, mount).await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT, "{mount}");
+    assert_eq!(
+        response.headers()[LOCATION],
+        format!("{base}/index.html"),
+        "{mount} redirect"
+    );
+
+    let response = get(&router, &format!("{base}/index.html")).await;
+    assert_eq!(response.status(), StatusCode::OK, "{base}/index.html");
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+    fetch_spec(router, base).await;
+}
+
+/// Fetches the config of swagger-ui mounted at `mount`.
+pub async fn fetch_config(router: Router, mount: &str) -> Config {
+    let uri = format!("{}/swagger-ui-config.json", mount.trim_end_matches('/'));
+    let body = fetch(&router, &uri).await;
+    serde_json::from_slice(&body).expect("config is valid JSON")
+}
+
+/// Fetches the spec the config of swagger-ui mounted at `mount` points to.
+pub async fn fetch_spec(router: Router, mount: &str) -> serde_json::Value {
+    let config = fetch_config(router.clone(), mount).await;
+    let body = fetch(&router, &config.url).await;
+    serde_json::from_slice(&body).expect("spec is valid JSON")
+}
+
+/// Fetches `uri`, asserting it is served successfully.
+async fn fetch(router: &Router, uri: &str) -> Vec<u8> {
+    let response = get(router, uri).await;
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    body_bytes(response).await.to_vec()
+}
+
+async fn get(router: &Router, uri: &str) -> Response {
+    let mut router = router.clone();
+    let request = Request::get(uri).body(Body::empty()).expect("uri is valid");
+    let ready = poll_fn(|cx| Service::<Request<Body>>::poll_ready(&mut router, cx)).await;
+    match ready {
+        Ok(()) => match router.call(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        },
+        Err(never) => match never {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SwaggerUiExt;
+    use super::{assert_serves_ui, axum, fetch_config, fetch_spec};
+    use axum::Router;
+
+    #[tokio::test]
+    async fn smoke_tests_mounted_ui() {
+        for mount in ["/docs", "/"] {
+            let app = Router::new().swagger_ui(
+                mount,
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            );
+            assert_serves_ui(app.clone(), mount).await;
+
+            let config = fetch_config(app.clone(), mount).await;
+            assert_eq!(
+                config.url,
+                format!("{}/openapi.json", mount.trim_end_matches('/'))
+            );
+
+            let spec = fetch_spec(app, mount).await;
+            assert!(spec.get("paths").is_some());
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Integration for axum 0.6.
+//!
+//! axum 0.6 is built on `http` 0.2, so requests and responses are converted at the boundary to
+//! the types the core services use, see `compat`. Only the body type of axum is supported. The
+//! request extensions of swagger-ui are passed on as they are, and all of them as
+//! [`RequestExtensions`] for the closures reading others.
+
+use axum06 as axum;
+use std::sync::Arc;
+
+const WILDCARD: &str = "/*path";
+
+/// The extensions of an axum 0.6 request, which are of `http` 0.2, found in the extensions the
+/// closures of swagger-ui take, e.g. of
+/// [`SwaggerUiService::with_guard`](swagger_ui::SwaggerUiService::with_guard) or
+/// [`Acl::new`](swagger_ui::Acl::new).
+///
+/// ```ignore
+/// let docs = SwaggerUiService::new(spec, None).with_guard(|_, extensions| {
+///     let extensions = extensions.get::<RequestExtensions>();
+///     extensions.and_then(|extensions| extensions.get::<ApiKey>()).is_some()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestExtensions(Arc<axum::http::Extensions>);
+
+impl RequestExtensions {
+    /// Returns the extension of type `T` of the request, if it has one.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.0.get()
+    }
+}
+
+/// Conversions between axum and the core services, which are built on `http` 1, so requests and
+/// responses are rebuilt at the boundary.
+mod compat {
+    use super::axum;
+    use axum::body::{boxed, StreamBody};
+    use axum::response::Response;
+    pub(crate) use http as http1;
+    #[cfg(feature = "oidc")]
+    use std::borrow::Cow;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    /// The request handlers take.
+    pub(crate) type Request = axum::http::Request<axum::body::Body>;
+
+    #[cfg(feature = "oidc")]
+    pub(crate) type Next = axum::middleware::Next<axum::body::Body>;
+
+    /// A request as the core services take it, without its body.
+    pub(crate) type CoreRequest = http1::Request<()>;
+
+    /// Returns the method, uri, headers and extensions of `req`, moving the extensions out of it,
+    /// see [`RequestExtensions`](super::RequestExtensions).
+    fn head(req: &mut Request) -> http1::request::Builder {
+        let mut builder = http1::Request::builder()
+            .method(req.method().as_str())
+            .uri(req.uri().to_string());
+        for (name, value) in req.headers() {
+            builder = builder.header(name.as_str(), value.as_bytes());
+        }
+        if let Some(roles) = req.extensions().get::<swagger_ui::Roles>() {
+            builder = builder.extension(roles.clone());
+        }
+        #[cfg(feature = "oidc")]
+        if let Some(user) = req.extensions().get::<swagger_ui::OidcUser>() {
+            builder = builder.extension(user.clone());
+        }
+        #[cfg(feature = "proxy")]
+        if let Some(user) = req.extensions().get::<swagger_ui::ProxyUser>() {
+            builder = builder.extension(user.clone());
+        }
+        #[cfg(feature = "proxy")]
+        if let Some(client) = req.extensions().get::<swagger_ui::ProxyClient>() {
+            builder = builder.extension(*client);
+        }
+        let extensions = std::mem::take(req.extensions_mut());
+        builder.extension(super::RequestExtensions(Arc::new(extensions)))
+    }
+
+    /// Converts `req` for the core services.
+    pub(crate) fn into_core(mut req: Request) -> CoreRequest {
+        head(&mut req)
+            .body(())
+            .expect("request is valid in both http versions")
+    }
+
+    /// Converts `req` for the core services reading its body, failing with
+    /// `413 Payload Too Large` if it has more than `limit` bytes.
+    #[cfg(any(feature = "proxy", feature = "sessions"))]
+    pub(crate) async fn into_core_with_body(
+        mut req: Request,
+        limit: usize,
+    ) -> Result<http1::Request<axum::body::Bytes>, Response> {
+        use axum::body::HttpBody;
+        use axum::response::IntoResponse;
+
+        let builder = head(&mut req);
+        let mut body = req.into_body();
+        let mut bytes = vec![];
+        while let Some(chunk) = body.data().await {
+            match chunk {
+                Ok(chunk) if bytes.len() + chunk.len() <= limit => bytes.extend_from_slice(&chunk),
+                _ => return Err(axum::http::StatusCode::PAYLOAD_TOO_LARGE.into_response()),
+            }
+        }
+        Ok(builder
+            .body(bytes.into())
+            .expect("request is valid in both http versions"))
+    }
+
+    /// Returns `uri` as the core services take it.
+    #[cfg(feature = "oidc")]
+    pub(crate) fn core_uri(uri: &axum::http::Uri) -> Cow<'_, http1::Uri> {
+        let uri = uri.to_string().parse();
+        Cow::Owned(uri.expect("uri is valid in both http versions"))
+    }
+
+    /// Returns `headers` as the core services take them.
+    #[cfg(feature = "oidc")]
+    pub(crate) fn core_headers(headers: &axum::http::HeaderMap) -> Cow<'_, http1::HeaderMap> {
+        let mut converted = http1::HeaderMap::new();
+        for (name, value) in headers {
+            let name = http1::HeaderName::from_bytes(name.as_str().as_bytes());
+            let value = http1::HeaderValue::from_bytes(value.as_bytes());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                converted.append(name, value);
+            }
+        }
+        Cow::Owned(converted)
+    }
+
+    /// Converts a response of the core services.
+    pub(crate) fn from_core(response: http1::Response<swagger_ui::Body>) -> Response {
+        let (parts, body) = response.into_parts();
+        let mut builder = Response::builder().status(parts.status.as_u16());
+        for (name, value) in &parts.headers {
+            builder = builder.header(name.as_str(), value.as_bytes());
+        }
+        builder
+            .body(boxed(StreamBody::new(futures_util::stream::iter(
+                body.into_chunks().map(Ok::<_, Infallible>),
+            ))))
+            .expect("response is valid in both http versions")
+    }
+
+    /// Reads the body of `response`.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub(crate) async fn body_bytes(response: Response) -> axum::body::Bytes {
+        use axum::body::HttpBody;
+
+        let mut body = response.into_body();
+        let mut bytes = vec![];
+        while let Some(chunk) = body.data().await {
+            bytes.extend_from_slice(&chunk.expect("body can be read"));
+        }
+        bytes.into()
+    }
+}
+
+// The same glue is compiled once per supported axum version.
+#[allow(clippy::duplicate_mod)]
+#[path = "builder.rs"]
+mod builder;
+#[allow(clippy::duplicate_mod)]
+#[path = "layer.rs"]
+mod layer;
+#[allow(clippy::duplicate_mod)]
+#[path = "router.rs"]
+mod router;
+#[cfg(feature = "test-utils")]
+#[allow(clippy::duplicate_mod)]
+#[path = "test_utils.rs"]
+pub mod test_utils;
+
+pub use self::builder::SwaggerUi;
+pub use self::layer::{SwaggerUiLayer, SwaggerUiMiddleware};
+#[cfg(feature = "sessions")]
+pub use self::router::authorization_route;
+#[cfg(feature = "mock")]
+pub use self::router::mock_route;
+#[cfg(feature = "oidc")]
+pub use self::router::oidc_guard;
+#[cfg(feature = "proxy")]
+pub use self::router::proxy_route;
+pub use self::router::{
+    changelog_route, diff_route, doc_ui_root_route, doc_ui_route, doc_ui_service_route,
+    landing_route, serve_asset, serve_config, serve_spec, spec_route, swagger_ui_if,
+    swagger_ui_multi, swagger_ui_root_route, swagger_ui_route, swagger_ui_service_route, DocUiExt,
+    SwaggerUiExt,
+};
+#[cfg(feature = "console")]
+pub use self::router::{console_route, ConsoleExt};
+#[cfg(feature = "elements")]
+pub use self::router::{elements_route, ElementsExt};
+#[cfg(feature = "rapidoc")]
+pub use self::router::{rapidoc_route, RapidocExt};
+#[cfg(feature = "redoc")]
+pub use self::router::{redoc_route, RedocExt};
+#[cfg(feature = "scalar")]
+pub use self::router::{scalar_route, ScalarExt};
+#[cfg(feature = "swagger-editor")]
+pub use self::router::{swagger_editor_route, SwaggerEditorExt};
+
+#[cfg(test)]
+mod tests {
+    use super::compat::body_bytes;
+    use super::{axum, swagger_ui_service_route, RequestExtensions};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use swagger_ui::SwaggerUiService;
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct ApiKey;
+
+    #[tokio::test]
+    async fn passes_the_request_extensions_to_the_guard() {
+        let service = SwaggerUiService::new(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        )
+        .with_guard(|_, extensions| {
+            let extensions = extensions.get::<RequestExtensions>();
+            extensions
+                .and_then(|extensions| extensions.get::<ApiKey>())
+                .is_some()
+        });
+        let app = swagger_ui_service_route::<()>(service);
+        let request = |key: Option<ApiKey>| {
+            let mut req = Request::get("/openapi.json").body(Body::empty()).unwrap();
+            if let Some(key) = key {
+                req.extensions_mut().insert(key);
+            }
+            req
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.oneshot(request(Some(ApiKey))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        body_bytes(response).await;
+    }
+}
@@ -0,0 +1,52 @@
+//! Integration for axum 0.7.
+
+use axum07 as axum;
+
+const WILDCARD: &str = "/*path";
+
+// The same glue is compiled once per supported axum version.
+#[allow(clippy::duplicate_mod)]
+#[path = "builder.rs"]
+mod builder;
+#[allow(clippy::duplicate_mod)]
+#[path = "compat.rs"]
+mod compat;
+#[allow(clippy::duplicate_mod)]
+#[path = "layer.rs"]
+mod layer;
+#[allow(clippy::duplicate_mod)]
+#[path = "router.rs"]
+mod router;
+#[cfg(feature = "test-utils")]
+#[allow(clippy::duplicate_mod)]
+#[path = "test_utils.rs"]
+pub mod test_utils;
+
+pub use self::builder::SwaggerUi;
+pub use self::layer::{SwaggerUiLayer, SwaggerUiMiddleware};
+#[cfg(feature = "sessions")]
+pub use self::router::authorization_route;
+#[cfg(feature = "mock")]
+pub use self::router::mock_route;
+#[cfg(feature = "oidc")]
+pub use self::router::oidc_guard;
+#[cfg(feature = "proxy")]
+pub use self::router::proxy_route;
+pub use self::router::{
+    changelog_route, diff_route, doc_ui_root_route, doc_ui_route, doc_ui_service_route,
+    landing_route, serve_asset, serve_config, serve_spec, spec_route, swagger_ui_if,
+    swagger_ui_multi, swagger_ui_root_route, swagger_ui_route, swagger_ui_service_route, DocUiExt,
+    SwaggerUiExt,
+};
+#[cfg(feature = "console")]
+pub use self::router::{console_route, ConsoleExt};
+#[cfg(feature = "elements")]
+pub use self::router::{elements_route, ElementsExt};
+#[cfg(feature = "rapidoc")]
+pub use self::router::{rapidoc_route, RapidocExt};
+#[cfg(feature = "redoc")]
+pub use self::router::{redoc_route, RedocExt};
+#[cfg(feature = "scalar")]
+pub use self::router::{scalar_route, ScalarExt};
+#[cfg(feature = "swagger-editor")]
+pub use self::router::{swagger_editor_route, SwaggerEditorExt};
@@ -0,0 +1,207 @@
+//! Swagger-ui for AWS Lambda functions using lambda_http.
+//!
+//! ```rust,ignore
+//! lambda_http::run(lambda_swagger_ui::swagger_ui("/docs", spec, None)).await
+//! ```
+//!
+//! Behind API Gateway, lambda_http keeps the stage in the request path unless
+//! `AWS_LAMBDA_HTTP_IGNORE_STAGE_IN_PATH` is set, e.g. for custom domains. `path` is matched
+//! below the stage either way, and the stage is kept in the links whenever it was part of the
+//! request path.
+
+use lambda_http::http::{Response, StatusCode};
+use lambda_http::request::RequestContext;
+use lambda_http::{Body, Request, RequestExt, Service};
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::task::{Context, Poll};
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// creates a lambda service that is configured to serve the specified spec and config with
+/// swagger_ui under `path`.
+pub fn swagger_ui(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> SwaggerUi {
+    SwaggerUi::from_service(path, SwaggerUiService::new(spec, config))
+}
+
+/// A `Service` serving swagger_ui from a lambda function, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    path: String,
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a service serving swagger_ui with an already configured service under `path`, e.g.
+    /// one with [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(path: &str, service: SwaggerUiService) -> Self {
+        let path = match path.trim_matches('/') {
+            "" => String::new(),
+            path => format!("/{path}"),
+        };
+        Self { path, service }
+    }
+
+    /// Returns whether swagger_ui responds to the request, so functions that also serve an api
+    /// can dispatch on it.
+    pub fn serves(&self, req: &Request) -> bool {
+        self.split(req)
+            .is_some_and(|(_, rest)| self.service.serves(rest))
+    }
+
+    /// Handle a request, responding with `404 Not Found` outside of `path`.
+    pub fn handle(&self, req: &Request) -> Response<Body> {
+        let Some((mount, rest)) = self.split(req) else {
+            return not_found();
+        };
+        let uri = match req.uri().query() {
+            Some(query) => format!("/{}?{query}", rest.trim_start_matches('/')),
+            None => format!("/{}", rest.trim_start_matches('/')),
+        };
+        let mut builder = lambda_http::http::Request::builder()
+            .method(req.method().clone())
+            .uri(uri);
+        if let Some(headers) = builder.headers_mut() {
+            *headers = req.headers().clone();
+        }
+        let Ok(mut core_req) = builder.body(()) else {
+            return not_found();
+        };
+        core_req.extensions_mut().insert(MountPath(mount));
+
+        let (parts, body) = self.service.handle(&core_req).into_parts();
+        // text goes through api gateway as is, anything else has to be base64 encoded
+        let body = match String::from_utf8(body.into_bytes().to_vec()) {
+            Ok(text) if text.is_empty() => Body::Empty,
+            Ok(text) => Body::Text(text),
+            Err(err) => Body::Binary(err.into_bytes()),
+        };
+        Response::from_parts(parts, body)
+    }
+
+    /// Splits the request path into the public mount point and the path below it.
+    fn split<'a>(&self, req: &'a Request) -> Option<(String, &'a str)> {
+        let path = req.uri().path();
+        let stage = stage_prefix(req, path);
+        let rest = path[stage.len()..].strip_prefix(self.path.as_str())?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        Some((format!("{stage}{}", self.path), rest))
+    }
+}
+
+/// Returns the `/{stage}` prefix lambda_http put in front of the path, if any.
+fn stage_prefix<'a>(req: &Request, path: &'a str) -> &'a str {
+    let stage = match req.request_context_ref() {
+        Some(RequestContext::ApiGatewayV1(context)) => context.stage.as_deref(),
+        Some(RequestContext::ApiGatewayV2(context)) => context.stage.as_deref(),
+        _ => None,
+    };
+    let Some(stage) = stage.filter(|stage| *stage != "$default") else {
+        return "";
+    };
+    match path
+        .strip_prefix('/')
+        .and_then(|path| path.strip_prefix(stage))
+    {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => &path[..stage.len() + 1],
+        _ => "",
+    }
+}
+
+fn not_found() -> Response<Body> {
+    let mut response = Response::new(Body::Empty);
+    *response.status_mut() = StatusCode::NOT_FOUND;
+    response
+}
+
+impl Service<Request> for SwaggerUi {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use lambda_http::aws_lambda_events::apigw::ApiGatewayProxyRequestContext;
+    use lambda_http::http::header::{CONTENT_TYPE, LOCATION};
+    use lambda_http::http::StatusCode;
+    use lambda_http::request::RequestContext;
+    use lambda_http::{Body, Request};
+    use swagger_ui::Config;
+
+    fn get(uri: &str, stage: &str) -> Request {
+        let context = ApiGatewayProxyRequestContext {
+            stage: Some(stage.to_string()),
+            ..Default::default()
+        };
+        lambda_http::http::Request::builder()
+            .uri(uri)
+            .extension(RequestContext::ApiGatewayV1(context))
+            .body(Body::Empty)
+            .unwrap()
+    }
+
+    fn config(body: &Body) -> Config {
+        serde_json::from_slice(body.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn serves_ui_below_the_stage() {
+        let docs = swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+
+        let response = docs.handle(&get("/Prod/docs", "Prod"));
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/Prod/docs/index.html");
+
+        let response = docs.handle(&get("/Prod/docs/index.html", "Prod"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+        let response = docs.handle(&get("/Prod/docs/swagger-ui-config.json", "Prod"));
+        assert_eq!(config(response.body()).url, "/Prod/docs/openapi.json");
+
+        let response = docs.handle(&get("/Prod/docs/favicon-32x32.png", "Prod"));
+        assert!(matches!(response.body(), Body::Binary(_)));
+
+        assert!(docs.serves(&get("/Prod/docs/openapi.json", "Prod")));
+        assert!(!docs.serves(&get("/Prod/hello", "Prod")));
+        assert_eq!(
+            docs.handle(&get("/Prod/hello", "Prod")).status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn keeps_the_stage_out_of_the_links_unless_it_is_in_the_path() {
+        let docs = swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+
+        // e.g. with `AWS_LAMBDA_HTTP_IGNORE_STAGE_IN_PATH` behind a custom domain
+        let response = docs.handle(&get("/docs/swagger-ui-config.json", "Prod"));
+        assert_eq!(config(response.body()).url, "/docs/openapi.json");
+
+        let response = docs.handle(&get("/docs/swagger-ui-config.json", "$default"));
+        assert_eq!(config(response.body()).url, "/docs/openapi.json");
+    }
+}
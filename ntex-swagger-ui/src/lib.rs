@@ -0,0 +1,166 @@
+//! Swagger-ui for ntex.
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .service(ntex_swagger_ui::swagger_ui_scope("/docs", spec, None))
+//!     .route("/hello", web::get().to(hello))
+//! ```
+
+use ntex::http::header::HeaderValue;
+use ntex::util::Bytes;
+use ntex::web::{self, HttpRequest, HttpResponse, Scope};
+use std::future::ready;
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// Returns a `Scope` serving the swagger-ui page displaying the given spec under `path`, e.g.
+/// `App::new().service(swagger_ui_scope("/docs", spec, None))`.
+pub fn swagger_ui_scope(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> Scope<web::DefaultError> {
+    swagger_ui_service_scope(path, SwaggerUiService::new(spec, config))
+}
+
+/// Returns a `Scope` serving swagger-ui with an already configured service under `path`, e.g.
+/// one with [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service_scope(path: &str, service: SwaggerUiService) -> Scope<web::DefaultError> {
+    web::scope(path.trim_end_matches('/')).default_service(web::to(move |req: HttpRequest| {
+        ready(handle(&service, &req))
+    }))
+}
+
+/// Hands a request over to the core service, which shares the `http` types with ntex.
+fn handle(service: &SwaggerUiService, req: &HttpRequest) -> HttpResponse {
+    // what the scope and those it is nested in left, unlike `Path::unprocessed` this doesn't
+    // panic for requests to the scope itself
+    let rest = req.match_info().path();
+    let mount = MountPath::from_original(req.path(), rest);
+
+    // like the actix-web scope, point the page to the config unless the request picks one itself
+    let query = req.query_string();
+    let uri = if rest.trim_start_matches('/').is_empty() && !query.contains("configUrl=") {
+        let config_url = format!("configUrl={}/swagger-ui-config.json", mount.0);
+        match query {
+            "" => format!("/?{config_url}"),
+            query => format!("/?{query}&{config_url}"),
+        }
+    } else if query.is_empty() {
+        rest.to_string()
+    } else {
+        format!("{rest}?{query}")
+    };
+
+    let mut builder = http::Request::builder()
+        .method(req.method().clone())
+        .uri(uri);
+    for (name, value) in req.headers() {
+        builder = builder.header(name, value.as_bytes());
+    }
+    let Ok(mut core_req) = builder.body(()) else {
+        return HttpResponse::BadRequest().finish();
+    };
+    core_req.extensions_mut().insert(mount);
+
+    let (parts, body) = service.handle(&core_req).into_parts();
+    let mut response = HttpResponse::build(parts.status);
+    for (name, value) in &parts.headers {
+        if let Ok(value) = HeaderValue::from_bytes(value.as_bytes()) {
+            response.header(name, value);
+        }
+    }
+    // ntex has its own `Bytes`, so the body has to be copied once
+    response.body(Bytes::copy_from_slice(&body.into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui_scope;
+    use ntex::http::header::{CONTENT_TYPE, LOCATION};
+    use ntex::http::StatusCode;
+    use ntex::web::test::{call_service, init_service, read_body, TestRequest};
+    use ntex::web::{self, App, HttpResponse};
+    use swagger_ui::Config;
+
+    #[ntex::test]
+    async fn scope_serves_ui_and_keeps_other_routes() {
+        let app = init_service(
+            App::new()
+                .service(swagger_ui_scope(
+                    "/docs",
+                    swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                    None,
+                ))
+                .route("/hello", web::get().to(|| async { HttpResponse::Ok() })),
+        )
+        .await;
+        let get = |uri: &str| TestRequest::with_uri(uri).to_request();
+
+        let res = call_service(&app, get("/docs")).await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            res.headers().get(LOCATION).unwrap(),
+            "/docs/index.html?configUrl=/docs/swagger-ui-config.json"
+        );
+
+        let res = call_service(&app, get("/docs/index.html")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/html");
+
+        let res = call_service(&app, get("/docs/swagger-ui-config.json")).await;
+        let config: Config = serde_json::from_slice(&read_body(res).await).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let res = call_service(&app, get("/docs/openapi.json")).await;
+        assert_eq!(
+            read_body(res).await,
+            include_bytes!("../../swagger-ui/examples/openapi.json").as_ref()
+        );
+
+        let res = call_service(&app, get("/docs/missing.js")).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = call_service(&app, get("/hello")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn nested_scope_serves_index_spec_and_assets() {
+        let app = init_service(
+            App::new().service(web::scope("/api").service(swagger_ui_scope(
+                "/v1/docs",
+                swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                None,
+            ))),
+        )
+        .await;
+        let get = |uri: &str| TestRequest::with_uri(uri).to_request();
+
+        let res = call_service(&app, get("/api/v1/docs")).await;
+        assert_eq!(
+            res.headers().get(LOCATION).unwrap(),
+            "/api/v1/docs/index.html?configUrl=/api/v1/docs/swagger-ui-config.json"
+        );
+
+        let res = call_service(&app, get("/api/v1/docs/index.html")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/html");
+
+        let res = call_service(&app, get("/api/v1/docs/swagger-ui-config.json")).await;
+        let config: Config = serde_json::from_slice(&read_body(res).await).unwrap();
+        assert_eq!(config.url, "/api/v1/docs/openapi.json");
+
+        let res = call_service(&app, get("/api/v1/docs/openapi.json")).await;
+        assert_eq!(
+            read_body(res).await,
+            include_bytes!("../../swagger-ui/examples/openapi.json").as_ref()
+        );
+
+        let res = call_service(&app, get("/api/v1/docs/swagger-ui.css")).await;
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/css");
+        assert_eq!(
+            read_body(res).await,
+            swagger_ui::Assets::get("swagger-ui.css").unwrap().as_ref()
+        );
+    }
+}
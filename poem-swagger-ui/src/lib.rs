@@ -0,0 +1,119 @@
+//! Swagger-ui for poem.
+//!
+//! ```rust,ignore
+//! let app = Route::new()
+//!     .nest("/docs", poem_swagger_ui::swagger_ui(spec, None))
+//!     .at("/hello", hello);
+//! ```
+
+use futures_util::stream;
+use poem::http::header::CONTENT_LENGTH;
+use poem::http::Request as CoreRequest;
+use poem::{Body, Endpoint, Request, Response, Result};
+use std::io;
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// creates an endpoint that is configured to serve the specified spec and config with
+/// swagger_ui, meant to be nested with `Route::nest`.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> SwaggerUi {
+    SwaggerUi::new(spec, config)
+}
+
+/// An `Endpoint` serving swagger_ui, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create an endpoint serving the specified spec and config with swagger_ui.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self::from_service(SwaggerUiService::new(spec, config))
+    }
+
+    /// Create an endpoint serving swagger_ui with an already configured service, e.g. one with
+    /// [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(service: SwaggerUiService) -> Self {
+        Self { service }
+    }
+}
+
+impl Endpoint for SwaggerUi {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        Ok(handle(&self.service, &req))
+    }
+}
+
+/// Hands a request nested into a route over to the framework-agnostic service.
+fn handle(service: &SwaggerUiService, req: &Request) -> Response {
+    let mut core_req = CoreRequest::new(());
+    *core_req.method_mut() = req.method().clone();
+    *core_req.uri_mut() = req.uri().clone();
+    *core_req.headers_mut() = req.headers().clone();
+    let mount = MountPath::from_original(req.original_uri().path(), req.uri().path());
+    core_req.extensions_mut().insert(mount);
+
+    let (mut parts, body) = service.handle(&core_req).into_parts();
+    let chunks = body.into_chunks().collect::<Vec<_>>();
+    let length = chunks.iter().map(|chunk| chunk.len()).sum::<usize>();
+    parts.headers.entry(CONTENT_LENGTH).or_insert(length.into());
+
+    let mut response = Response::builder()
+        .status(parts.status)
+        .body(Body::from_bytes_stream(stream::iter(
+            chunks.into_iter().map(Ok::<_, io::Error>),
+        )));
+    *response.headers_mut() = parts.headers;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use poem::http::StatusCode;
+    use poem::test::TestClient;
+    use poem::{handler, Route};
+    use swagger_ui::Config;
+
+    #[handler]
+    fn hello() -> &'static str {
+        "hello"
+    }
+
+    #[tokio::test]
+    async fn nested_endpoint_serves_ui() {
+        let app = Route::new()
+            .nest(
+                "/docs",
+                swagger_ui(
+                    swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                    None,
+                ),
+            )
+            .at("/hello", hello);
+        let client = TestClient::new(app);
+
+        // the test client doesn't record the original uri, which the mount path is derived from
+        let response = client.get("/docs").send().await;
+        response.assert_status(StatusCode::PERMANENT_REDIRECT);
+
+        let response = client.get("/docs/index.html").send().await;
+        response.assert_status_is_ok();
+        response.assert_content_type("text/html");
+
+        let response = client.get("/docs/swagger-ui-config.json").send().await;
+        let body = response.0.into_body().into_bytes().await.unwrap();
+        let config: Config = serde_json::from_slice(&body).unwrap();
+        assert!(config.url.ends_with("/openapi.json"));
+
+        let response = client.get("/docs/openapi.json").send().await;
+        response
+            .assert_bytes(include_bytes!("../../swagger-ui/examples/openapi.json").as_ref())
+            .await;
+
+        let response = client.get("/hello").send().await;
+        response.assert_text("hello").await;
+    }
+}
@@ -0,0 +1,55 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::Rocket;
+use swagger_ui::{Config, Spec};
+
+/// A fairing that mounts swagger-ui under a path when it is attached, see [`mount_swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUiFairing {
+    path: String,
+    spec: Spec,
+    config: Config,
+}
+
+impl Fairing for SwaggerUiFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Swagger UI",
+            kind: Kind::Attach,
+        }
+    }
+
+    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let routes = crate::routes(self.spec.clone(), self.config.clone());
+        Ok(rocket.mount(&self.path, routes))
+    }
+}
+
+/// Returns a fairing mounting swagger-ui displaying the given `Spec` under `path`:
+///
+/// ```rust,ignore
+/// rocket::ignite().attach(mount_swagger_ui("/docs", spec, None))
+/// ```
+///
+/// To mount the routes without a fairing, use [`routes`](crate::routes) instead.
+pub fn mount_swagger_ui(
+    path: &str,
+    spec: Spec,
+    config: impl Into<Option<Config>>,
+) -> SwaggerUiFairing {
+    SwaggerUiFairing {
+        path: path.to_string(),
+        spec,
+        config: config.into().unwrap_or_default(),
+    }
+}
+
+/// Helper trait to allow `rocket.mount_swagger_ui(...)`
+pub trait SwaggerUiExt {
+    fn mount_swagger_ui(self, path: &str, spec: Spec, config: impl Into<Option<Config>>) -> Self;
+}
+
+impl SwaggerUiExt for Rocket {
+    fn mount_swagger_ui(self, path: &str, spec: Spec, config: impl Into<Option<Config>>) -> Self {
+        self.mount(path, crate::routes(spec, config.into().unwrap_or_default()))
+    }
+}
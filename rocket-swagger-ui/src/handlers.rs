@@ -1,7 +1,19 @@
 use rocket::handler::{Handler, Outcome};
 use rocket::http::{ContentType, Method};
-use rocket::response::{Content, Redirect, Responder};
+use rocket::response::{self, Content, Redirect, Responder, Response};
 use rocket::{Data, Request, Route};
+use std::io::Cursor;
+use swagger_ui::Bytes;
+
+/// A responder for shared `Bytes`, cloning it per request only bumps a reference count.
+#[derive(Clone)]
+pub struct SharedBytes(pub Bytes);
+
+impl<'r> Responder<'r> for SharedBytes {
+    fn respond_to(self, _: &Request) -> response::Result<'r> {
+        Response::build().sized_body(Cursor::new(self.0)).ok()
+    }
+}
 
 /// A content handler is a wrapper type around `rocket::response::Content`, which can be turned into
 /// a `rocket::Route` that serves the content with correct content-type.
@@ -31,6 +43,16 @@ impl ContentHandler<Vec<u8>> {
     }
 }
 
+impl ContentHandler<SharedBytes> {
+    /// Create a `ContentHandler<SharedBytes>`, which serves its content with the specified
+    /// `content_type` without copying it per request.
+    pub fn shared(content_type: ContentType, content: Bytes) -> Self {
+        ContentHandler {
+            content: Content(content_type, SharedBytes(content)),
+        }
+    }
+}
+
 impl<R: Responder<'static> + Clone + Send + Sync + 'static> ContentHandler<R> {
     /// Create a `rocket::Route` from the current `ContentHandler`.
     pub fn into_route(self, path: impl AsRef<str>) -> Route {
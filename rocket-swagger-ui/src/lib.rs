@@ -1,5 +1,8 @@
+mod fairing;
 mod handlers;
 
+pub use crate::fairing::{mount_swagger_ui, SwaggerUiExt, SwaggerUiFairing};
+
 use crate::handlers::{ContentHandler, RedirectHandler};
 use rocket::http::ContentType;
 use rocket::Route;
@@ -21,7 +24,8 @@ fn mime_type(filename: &str) -> ContentType {
 }
 
 pub fn routes(spec: Spec, mut config: Config) -> Vec<Route> {
-    let spec_handler = ContentHandler::bytes(mime_type(spec.name.as_ref()), spec.content.into());
+    let content_type = ContentType::parse_flexible(&spec.content_type).unwrap_or(ContentType::JSON);
+    let spec_handler = ContentHandler::shared(content_type, spec.content);
 
     let spec_name: &str = Path::new(spec.name.as_ref())
         .file_name()
@@ -29,7 +33,9 @@ pub fn routes(spec: Spec, mut config: Config) -> Vec<Route> {
         .to_str()
         .unwrap_or("openapi.json".as_ref());
 
-    config.url = String::from(spec_name);
+    if config.url.is_empty() {
+        config.url = String::from(spec_name);
+    }
 
     let config_handler = ContentHandler::json(&config);
 
@@ -75,6 +81,37 @@ mod tests {
         )
     }
 
+    #[test]
+    fn fairing_mounts_swagger_ui() {
+        let rocket = rocket::ignite().attach(super::mount_swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        ));
+        let client = Client::new(rocket).expect("valid rocket instance");
+
+        let response = client.get("/docs/index.html").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/docs/openapi.json").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn extension_mounts_swagger_ui() {
+        use super::SwaggerUiExt;
+
+        let rocket = rocket::ignite().mount_swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+        let client = Client::new(rocket).expect("valid rocket instance");
+
+        let response = client.get("/docs/swagger-ui-config.json").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
     #[test]
     fn swagger_ui() {
         let client = Client::new(ignite()).expect("valid rocket instance");
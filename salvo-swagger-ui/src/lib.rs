@@ -0,0 +1,129 @@
+//! Swagger-ui for salvo.
+//!
+//! ```rust,ignore
+//! let router = Router::new()
+//!     .push(Router::with_path("docs").push(salvo_swagger_ui::swagger_ui(spec, None)))
+//!     .push(Router::with_path("hello").get(hello));
+//! ```
+
+use salvo::http::ResBody;
+use salvo::{async_trait, Depot, FlowCtrl, Handler, Request, Response, Router};
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// creates a router that is configured to serve the specified spec and config with swagger_ui,
+/// meant to be pushed onto the router of the path it is mounted at.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Router {
+    swagger_ui_service(SwaggerUiService::new(spec, config))
+}
+
+/// creates a router serving swagger_ui with an already configured service, e.g. one with
+/// [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service(service: SwaggerUiService) -> Router {
+    Router::with_path("{**rest}").goal(SwaggerUi { service })
+}
+
+/// A `Handler` serving swagger_ui, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving the specified spec and config with swagger_ui.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self {
+            service: SwaggerUiService::new(spec, config),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for SwaggerUi {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        // salvo doesn't strip the prefix, the path below the mount point is the `rest` parameter
+        let rest = format!("/{}", req.param::<String>("rest").unwrap_or_default());
+        let mount = MountPath::from_original(req.uri().path(), &rest);
+        let uri = match req.uri().query() {
+            Some(query) => format!("{rest}?{query}"),
+            None => rest,
+        };
+
+        let mut builder = http::Request::builder()
+            .method(req.method().clone())
+            .uri(uri);
+        if let Some(headers) = builder.headers_mut() {
+            *headers = req.headers().clone();
+        }
+        let Ok(mut core_req) = builder.body(()) else {
+            res.status_code(salvo::http::StatusCode::BAD_REQUEST);
+            return;
+        };
+        core_req.extensions_mut().insert(mount);
+
+        let (parts, body) = self.service.handle(&core_req).into_parts();
+        res.status_code(parts.status);
+        *res.headers_mut() = parts.headers;
+        res.replace_body(ResBody::Chunks(body.into_chunks().collect()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use salvo::http::header::{CONTENT_TYPE, LOCATION};
+    use salvo::http::StatusCode;
+    use salvo::test::{ResponseExt, TestClient};
+    use salvo::{handler, Router, Service};
+    use swagger_ui::Config;
+
+    #[handler]
+    async fn hello() -> &'static str {
+        "hello"
+    }
+
+    #[tokio::test]
+    async fn router_serves_ui_and_keeps_other_routes() {
+        let service = Service::new(
+            Router::new()
+                .push(Router::with_path("docs").push(swagger_ui(
+                    swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                    None,
+                )))
+                .push(Router::with_path("hello").get(hello)),
+        );
+        let url = |path: &str| format!("http://127.0.0.1:5800{path}");
+
+        let res = TestClient::get(url("/docs")).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::PERMANENT_REDIRECT));
+        assert_eq!(res.headers()[LOCATION], "/docs/index.html");
+
+        let res = TestClient::get(url("/docs/index.html"))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/html");
+
+        let mut res = TestClient::get(url("/docs/swagger-ui-config.json"))
+            .send(&service)
+            .await;
+        let config: Config = serde_json::from_str(&res.take_string().await.unwrap()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let mut res = TestClient::get(url("/docs/openapi.json"))
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            include_str!("../../swagger-ui/examples/openapi.json")
+        );
+
+        let mut res = TestClient::get(url("/hello")).send(&service).await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+    }
+}
@@ -0,0 +1,114 @@
+//! Swagger-ui for Spin and other wasi-http components.
+//!
+//! ```rust,ignore
+//! #[http_component]
+//! fn handle(req: Request) -> impl IntoResponse {
+//!     spin_swagger_ui::swagger_ui(spec, None).handle(&req)
+//! }
+//! ```
+//!
+//! With a route like `route = "/docs/..."`, Spin passes the path below the route in the
+//! `spin-path-info` header, which is used to find the mount point. Without it, e.g. on other
+//! wasi-http hosts, the ui is served at the root.
+
+use spin_sdk::http::Request;
+use swagger_ui::{Bytes, Config, MountPath, SpecOrUrl, SwaggerUiService};
+
+/// The header Spin puts the path below the matched route in.
+const PATH_INFO: &str = "spin-path-info";
+
+/// creates a component handler that is configured to serve the specified spec and config with
+/// swagger_ui.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> SwaggerUi {
+    SwaggerUi::from_service(SwaggerUiService::new(spec, config))
+}
+
+/// Serves swagger_ui from a component, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving swagger_ui with an already configured service, e.g. one with
+    /// [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(service: SwaggerUiService) -> Self {
+        Self { service }
+    }
+
+    /// Handle a request, the response implements Spin's `IntoResponse`.
+    pub fn handle(&self, req: &Request) -> http::Response<Bytes> {
+        let path = req.path();
+        let rest = req
+            .header(PATH_INFO)
+            .and_then(|value| value.as_str())
+            .unwrap_or(path);
+        let mount = MountPath::from_original(path, rest);
+        let uri = match req.query() {
+            "" => format!("/{}", rest.trim_start_matches('/')),
+            query => format!("/{}?{query}", rest.trim_start_matches('/')),
+        };
+
+        let mut builder = http::Request::builder()
+            .method(http::Method::from(req.method().clone()))
+            .uri(uri);
+        for (name, value) in req.headers() {
+            builder = builder.header(name, value.as_bytes());
+        }
+        let Ok(mut core_req) = builder.body(()) else {
+            let mut response = http::Response::new(Bytes::new());
+            *response.status_mut() = http::StatusCode::BAD_REQUEST;
+            return response;
+        };
+        core_req.extensions_mut().insert(mount);
+
+        // components send the body in one piece anyway
+        self.service.handle(&core_req).map(|body| body.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{swagger_ui, PATH_INFO};
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::StatusCode;
+    use spin_sdk::http::Request;
+    use swagger_ui::Config;
+
+    fn get(path: &str, path_info: Option<&str>) -> Request {
+        let mut req = Request::get(path).build();
+        if let Some(path_info) = path_info {
+            req.set_header(PATH_INFO, path_info);
+        }
+        req
+    }
+
+    #[test]
+    fn serves_ui_below_the_route() {
+        let docs = swagger_ui(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+
+        let response = docs.handle(&get("/docs", Some("")));
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+
+        let response = docs.handle(&get("/docs/index.html", Some("/index.html")));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+        let response = docs.handle(&get(
+            "/docs/swagger-ui-config.json",
+            Some("/swagger-ui-config.json"),
+        ));
+        let config: Config = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let response = docs.handle(&get("/openapi.json", None));
+        assert_eq!(
+            response.body().as_ref(),
+            include_bytes!("../../swagger-ui/examples/openapi.json")
+        );
+    }
+}
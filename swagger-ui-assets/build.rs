@@ -0,0 +1,154 @@
+use anyhow::Context;
+use async_recursion::async_recursion;
+use base64::Engine;
+use futures::StreamExt;
+use reqwest::{Client, IntoUrl};
+use serde::Deserialize;
+use sha2::{Digest, Sha384};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EntryType {
+    File,
+    Dir,
+}
+
+#[derive(Deserialize)]
+struct FolderEntry {
+    name: String,
+    url: String,
+    r#type: EntryType,
+    download_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    /// Single value
+    One(T),
+    /// Array of values
+    Vec(Vec<T>),
+}
+impl<T> From<OneOrMany<T>> for Vec<T> {
+    fn from(from: OneOrMany<T>) -> Self {
+        match from {
+            OneOrMany::One(val) => vec![val],
+            OneOrMany::Vec(vec) => vec,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let out_dir = manifest_dir.join(".dist");
+    download_folder(
+        "https://api.github.com/repos/swagger-api/swagger-ui/contents/dist",
+        out_dir,
+    )
+    .await?;
+    write_asset_index(&manifest_dir.join(".dist"))?;
+    Ok(())
+}
+
+/// Writes `asset_index.rs` into `OUT_DIR`, with a `match` from the path of every file in `dist`
+/// to its content, content type and Subresource Integrity hash.
+fn write_asset_index(dist: &Path) -> anyhow::Result<()> {
+    let mut paths = vec![];
+    let mut pending = vec![dist.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            match path.is_dir() {
+                true => pending.push(path),
+                false => paths.push(path),
+            }
+        }
+    }
+    paths.sort();
+
+    let mut arms = String::new();
+    let mut names = vec![];
+    for file in &paths {
+        let path = file
+            .strip_prefix(dist)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        names.push(path.clone());
+        let content = std::fs::read(file)?;
+        let integrity = format!(
+            "sha384-{}",
+            base64::engine::general_purpose::STANDARD.encode(Sha384::digest(&content))
+        );
+        let content_type = match path.rsplit_once('.') {
+            // source maps are JSON documents
+            Some((_, "map")) => "application/json".to_string(),
+            _ => mime_guess::from_path(&path)
+                .first_or_octet_stream()
+                .to_string(),
+        };
+        writeln!(
+            arms,
+            "        {path:?} => Some(&Asset {{ content: include_bytes!({file:?}), \
+             content_type: {content_type:?}, integrity: {integrity:?} }}),",
+        )?;
+    }
+
+    let index = format!(
+        "/// The paths of all assets, sorted.\n\
+         pub const PATHS: [&str; {}] = {names:?};\n\n\
+         /// Returns the asset at `path`, e.g. `swagger-ui-bundle.js`.\n\
+         pub fn get(path: &str) -> Option<&'static Asset> {{\n    \
+         match path {{\n{arms}        _ => None,\n    }}\n}}\n",
+        names.len()
+    );
+    let out = PathBuf::from(std::env::var("OUT_DIR")?).join("asset_index.rs");
+    std::fs::write(out, index)?;
+    Ok(())
+}
+
+#[async_recursion]
+async fn download_folder(url: &str, to: impl Into<PathBuf> + Send + 'static) -> anyhow::Result<()> {
+    let entries: Vec<_> = reqwest()?
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("failed to query folder data for {url}"))?
+        .json::<OneOrMany<FolderEntry>>()
+        .await
+        .with_context(|| format!("failed to parse json for {url}"))?
+        .into();
+    let path = &to.into();
+    futures::future::try_join_all(entries.into_iter().map(|entry| async move {
+        match entry.r#type {
+            EntryType::File => {
+                download_file(entry.download_url.unwrap(), path.clone().join(entry.name)).await
+            }
+            EntryType::Dir => download_folder(&entry.url, path.clone().join(entry.name)).await,
+        }
+    }))
+    .await?;
+    Ok(())
+}
+
+async fn download_file(
+    url: impl IntoUrl + Send,
+    to: impl Into<PathBuf> + Send,
+) -> anyhow::Result<()> {
+    let path = to.into();
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut byte_stream = reqwest()?.get(url).send().await?.bytes_stream();
+    let mut tmp_file = tokio::fs::File::create(path).await?;
+    while let Some(item) = byte_stream.next().await {
+        tokio::io::copy(&mut item?.as_ref(), &mut tmp_file).await?;
+    }
+    Ok(())
+}
+
+fn reqwest() -> anyhow::Result<Client> {
+    Ok(Client::builder().user_agent("reqwest").build()?)
+}
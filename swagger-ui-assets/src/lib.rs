@@ -0,0 +1,105 @@
+//! The files of swagger-ui-dist, embedded into the binary and indexed at build time.
+//!
+//! Kept apart from the `swagger-ui` crate, so changing the code serving them doesn't embed
+//! megabytes again on every incremental build. Looking up an asset is a single `match` on its
+//! path, with the content type and hash computed by the build script instead of on every
+//! request.
+
+use rust_embed::RustEmbed;
+
+/// Assets from swagger-ui-dist
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.dist"]
+pub struct Assets;
+
+/// An embedded asset and what it is served with, see [`get`].
+#[derive(Debug)]
+pub struct Asset {
+    /// The content of the file.
+    pub content: &'static [u8],
+    /// The content type it is served with, e.g. `text/css`.
+    pub content_type: &'static str,
+    /// Its Subresource Integrity hash, e.g. `sha384-oqVuAf...`.
+    pub integrity: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/asset_index.rs"));
+
+/// Assets whose content doesn't match the hash recorded when they were embedded, see
+/// [`verify`].
+#[cfg(feature = "verify")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The paths of the assets, e.g. `swagger-ui-bundle.js`.
+    pub paths: Vec<String>,
+}
+
+#[cfg(feature = "verify")]
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the embedded swagger-ui assets {} don't match the hashes recorded at build time",
+            self.paths.join(", ")
+        )
+    }
+}
+
+#[cfg(feature = "verify")]
+impl std::error::Error for Mismatch {}
+
+/// Hashes every asset again and compares it to the hash the build script recorded, failing
+/// with the assets that differ or weren't there at build time.
+///
+/// This catches a dist dir changed while building, and in debug builds, where [`Assets`] reads
+/// the files from disk, one changed afterwards.
+#[cfg(feature = "verify")]
+pub fn verify() -> Result<(), Mismatch> {
+    use base64::Engine;
+    use sha2::{Digest, Sha384};
+
+    let hash = |content: &[u8]| {
+        let digest = Sha384::digest(content);
+        format!(
+            "sha384-{}",
+            base64::engine::general_purpose::STANDARD.encode(digest)
+        )
+    };
+    let paths: Vec<String> = Assets::iter()
+        .filter(|path| {
+            let Some(asset) = get(path) else {
+                return true;
+            };
+            let embedded = Assets::get(path).unwrap_or_default();
+            hash(asset.content) != asset.integrity || hash(&embedded) != asset.integrity
+        })
+        .map(|path| path.into_owned())
+        .collect();
+    match paths.is_empty() {
+        true => Ok(()),
+        false => Err(Mismatch { paths }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, Assets, PATHS};
+
+    #[test]
+    fn indexes_every_embedded_asset() {
+        assert_eq!(PATHS.len(), Assets::iter().count());
+        for path in Assets::iter() {
+            let asset = get(&path).unwrap();
+            assert_eq!(asset.content, Assets::get(&path).unwrap().as_ref());
+            assert!(asset.integrity.starts_with("sha384-"));
+        }
+        assert_eq!(get("index.html").unwrap().content_type, "text/html");
+        assert!(get("missing.js").is_none());
+    }
+
+    #[cfg(feature = "verify")]
+    #[test]
+    fn verifies_the_embedded_assets() {
+        assert_eq!(super::verify(), Ok(()));
+    }
+}
@@ -0,0 +1,272 @@
+//! Parsing of the command line, kept free of dependencies like the rest of the crate.
+
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: swagger-ui <command> [options]
+
+Commands:
+  serve <spec>          Serve the spec at http://127.0.0.1:<port>
+  validate <spec>       Check that the spec is valid, failing on errors
+  lint <spec>           Check the spec for style problems, failing on warnings
+  bundle <entry>        Inline the `$ref`s into other files, writing one document
+  diff <old> <new>      Show the operations and schemas that changed
+
+Options of serve:
+  --port <port>         Port to listen on [default: 3000]
+  --config <file>       JSON config of the ui, e.g. `{\"docExpansion\": \"full\"}`
+  --ui <swagger|redoc>  The ui to show the spec with [default: swagger]
+
+Options of bundle:
+  -o, --output <file>   File to write the bundled spec to [default: stdout]
+
+Options of diff:
+  --fail-on-breaking    Fail if any change is breaking
+
+JSON specs are bundled before they are served or checked.";
+
+/// The viewer `serve` shows the spec with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ui {
+    #[default]
+    Swagger,
+    Redoc,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ServeArgs {
+    pub spec: PathBuf,
+    pub port: u16,
+    pub config: Option<PathBuf>,
+    pub ui: Ui,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BundleArgs {
+    pub entry: PathBuf,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DiffArgs {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    pub fail_on_breaking: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Serve(ServeArgs),
+    Validate(PathBuf),
+    Lint(PathBuf),
+    Bundle(BundleArgs),
+    Diff(DiffArgs),
+    Help,
+}
+
+/// Parses the arguments following the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None | Some("help" | "-h" | "--help") => Ok(Command::Help),
+        Some("serve") => parse_serve(args).map(Command::Serve),
+        Some("validate") => parse_spec("validate", args).map(Command::Validate),
+        Some("lint") => parse_spec("lint", args).map(Command::Lint),
+        Some("bundle") => parse_bundle(args).map(Command::Bundle),
+        Some("diff") => parse_diff(args).map(Command::Diff),
+        Some(command) => Err(format!("unknown command `{command}`")),
+    }
+}
+
+/// Parses the arguments of commands only taking the path of a spec.
+fn parse_spec(command: &str, mut args: impl Iterator<Item = String>) -> Result<PathBuf, String> {
+    let spec = args
+        .next()
+        .ok_or_else(|| format!("`{command}` expects the path of a spec"))?;
+    if spec.starts_with('-') {
+        return Err(format!("unknown option `{spec}`"));
+    }
+    match args.next() {
+        Some(arg) => Err(format!("unexpected argument `{arg}`")),
+        None => Ok(PathBuf::from(spec)),
+    }
+}
+
+fn parse_bundle(mut args: impl Iterator<Item = String>) -> Result<BundleArgs, String> {
+    let mut entry = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("`{arg}` expects a value"))?;
+                output = Some(PathBuf::from(value));
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            _ if entry.is_some() => return Err(format!("unexpected argument `{arg}`")),
+            _ => entry = Some(PathBuf::from(arg)),
+        }
+    }
+    Ok(BundleArgs {
+        entry: entry.ok_or("`bundle` expects the path of the entry spec")?,
+        output,
+    })
+}
+
+fn parse_diff(args: impl Iterator<Item = String>) -> Result<DiffArgs, String> {
+    let mut specs = vec![];
+    let mut fail_on_breaking = false;
+    for arg in args {
+        match arg.as_str() {
+            "--fail-on-breaking" => fail_on_breaking = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            _ if specs.len() == 2 => return Err(format!("unexpected argument `{arg}`")),
+            _ => specs.push(PathBuf::from(arg)),
+        }
+    }
+    let [old, new]: [PathBuf; 2] = specs
+        .try_into()
+        .map_err(|_| "`diff` expects the paths of the old and the new spec")?;
+    Ok(DiffArgs {
+        old,
+        new,
+        fail_on_breaking,
+    })
+}
+
+fn parse_serve(mut args: impl Iterator<Item = String>) -> Result<ServeArgs, String> {
+    let mut spec = None;
+    let mut port = 3000;
+    let mut config = None;
+    let mut ui = Ui::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("`{flag}` expects a value"))
+        };
+        match flag.as_str() {
+            "--port" => {
+                let value = value()?;
+                port = value
+                    .parse()
+                    .map_err(|_| format!("invalid port `{value}`"))?;
+            }
+            "--config" => config = Some(PathBuf::from(value()?)),
+            "--ui" => {
+                ui = match value()?.as_str() {
+                    "swagger" => Ui::Swagger,
+                    "redoc" => Ui::Redoc,
+                    other => {
+                        return Err(format!("unknown ui `{other}`, expected swagger or redoc"))
+                    }
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            _ if spec.is_some() => return Err(format!("unexpected argument `{flag}`")),
+            _ => spec = Some(PathBuf::from(flag)),
+        }
+    }
+    Ok(ServeArgs {
+        spec: spec.ok_or("`serve` expects the path of a spec")?,
+        port,
+        config,
+        ui,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, BundleArgs, Command, DiffArgs, ServeArgs, Ui};
+    use std::path::PathBuf;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_serve() {
+        assert_eq!(
+            parse(args("serve openapi.json")),
+            Ok(Command::Serve(ServeArgs {
+                spec: PathBuf::from("openapi.json"),
+                port: 3000,
+                config: None,
+                ui: Ui::Swagger,
+            }))
+        );
+        assert_eq!(
+            parse(args(
+                "serve --port=8080 --ui redoc openapi.json --config ui.json"
+            )),
+            Ok(Command::Serve(ServeArgs {
+                spec: PathBuf::from("openapi.json"),
+                port: 8080,
+                config: Some(PathBuf::from("ui.json")),
+                ui: Ui::Redoc,
+            }))
+        );
+        assert_eq!(parse(args("")), Ok(Command::Help));
+
+        assert!(parse(args("serve")).is_err());
+        assert!(parse(args("serve openapi.json --port")).is_err());
+        assert!(parse(args("serve openapi.json --port 99999")).is_err());
+        assert!(parse(args("serve openapi.json --ui rapidoc")).is_err());
+        assert!(parse(args("publish openapi.json")).is_err());
+    }
+
+    #[test]
+    fn parses_checks() {
+        assert_eq!(
+            parse(args("validate openapi.json")),
+            Ok(Command::Validate(PathBuf::from("openapi.json")))
+        );
+        assert_eq!(
+            parse(args("lint openapi.json")),
+            Ok(Command::Lint(PathBuf::from("openapi.json")))
+        );
+        assert!(parse(args("lint")).is_err());
+        assert!(parse(args("lint a.json b.json")).is_err());
+    }
+
+    #[test]
+    fn parses_bundle() {
+        assert_eq!(
+            parse(args("bundle openapi.json -o bundled.json")),
+            Ok(Command::Bundle(BundleArgs {
+                entry: PathBuf::from("openapi.json"),
+                output: Some(PathBuf::from("bundled.json")),
+            }))
+        );
+        assert_eq!(
+            parse(args("bundle openapi.json")),
+            Ok(Command::Bundle(BundleArgs {
+                entry: PathBuf::from("openapi.json"),
+                output: None,
+            }))
+        );
+        assert!(parse(args("bundle -o bundled.json")).is_err());
+    }
+
+    #[test]
+    fn parses_diff() {
+        assert_eq!(
+            parse(args("diff v1.json --fail-on-breaking v2.json")),
+            Ok(Command::Diff(DiffArgs {
+                old: PathBuf::from("v1.json"),
+                new: PathBuf::from("v2.json"),
+                fail_on_breaking: true,
+            }))
+        );
+        assert!(parse(args("diff v1.json")).is_err());
+        assert!(parse(args("diff v1.json v2.json v3.json")).is_err());
+    }
+}
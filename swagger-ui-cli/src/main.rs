@@ -0,0 +1,170 @@
+//! Preview an OpenAPI spec without writing any rust:
+//!
+//! ```sh
+//! cargo install swagger-ui-cli
+//! swagger-ui serve openapi.json --port 3000 --ui redoc
+//! ```
+//!
+//! `bundle` inlines the `$ref`s into other files into one document. `validate` and `lint` check a
+//! spec, exiting with a non-zero code on problems, e.g. from a pre-commit hook, as does `diff` on
+//! breaking changes with `--fail-on-breaking`.
+
+mod args;
+
+use crate::args::{BundleArgs, Command, DiffArgs, ServeArgs, Ui, USAGE};
+use serde::de::DeserializeOwned;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::{env, fs};
+use swagger_ui::{Diagnostic, RedocService, Severity, Spec, SwaggerUiService};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let command = match args::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let result = match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::Serve(args) => serve(args).await,
+        Command::Validate(path) => check(&path, swagger_ui::validate, Severity::Error),
+        Command::Lint(path) => check(&path, swagger_ui::lint, Severity::Warning),
+        Command::Bundle(args) => bundle(args),
+        Command::Diff(args) => diff(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn serve(args: ServeArgs) -> Result<(), String> {
+    let spec = read_spec(&args.spec)?;
+    let addr = ("127.0.0.1", args.port);
+    let result = match args.ui {
+        Ui::Swagger => {
+            let service = SwaggerUiService::new(spec, read_config(args.config.as_deref())?);
+            println!(
+                "Serving {} at http://127.0.0.1:{}",
+                args.spec.display(),
+                args.port
+            );
+            swagger_ui::serve_service(addr, service).await
+        }
+        Ui::Redoc => {
+            let service = RedocService::new(spec, read_config(args.config.as_deref())?);
+            println!(
+                "Serving {} at http://127.0.0.1:{}",
+                args.spec.display(),
+                args.port
+            );
+            swagger_ui::serve_service(addr, service).await
+        }
+    };
+    result.map_err(|err| err.to_string())
+}
+
+/// Prints the diagnostics `checks` finds in the spec at `path`, failing if any of them is at
+/// least as serious as `fail_on`.
+fn check(
+    path: &Path,
+    checks: fn(&Spec) -> Vec<Diagnostic>,
+    fail_on: Severity,
+) -> Result<(), String> {
+    let diagnostics = checks(&read_spec(path)?);
+    for diagnostic in &diagnostics {
+        println!("{}: {diagnostic}", path.display());
+    }
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity >= fail_on)
+    {
+        return Err(format!(
+            "{}: {errors} error(s), {warnings} warning(s)",
+            path.display()
+        ));
+    }
+    println!(
+        "{}: {errors} error(s), {warnings} warning(s)",
+        path.display()
+    );
+    Ok(())
+}
+
+fn bundle(args: BundleArgs) -> Result<(), String> {
+    let spec = swagger_ui::bundle(&args.entry).map_err(|err| err.to_string())?;
+    match &args.output {
+        Some(path) => {
+            fs::write(path, &spec.content).map_err(|err| format!("{}: {err}", path.display()))
+        }
+        None => io::stdout()
+            .write_all(&spec.content)
+            .map_err(|err| err.to_string()),
+    }
+}
+
+fn diff(args: DiffArgs) -> Result<(), String> {
+    let old = read_spec(&args.old)?;
+    let new = read_spec(&args.new)?;
+    let diff = swagger_ui::diff(&old, &new).map_err(|err| err.to_string())?;
+    for change in &diff.changes {
+        println!("{change}");
+    }
+    let breaking = diff.breaking_changes().count();
+    let summary = format!(
+        "{} change(s), {breaking} of them breaking",
+        diff.changes.len()
+    );
+    if args.fail_on_breaking && diff.breaking {
+        return Err(summary);
+    }
+    println!("{summary}");
+    Ok(())
+}
+
+/// Reads the spec at `path`, served under its file name.
+///
+/// JSON specs are bundled with the same resolver as the `bundle` command, so what is served and
+/// checked is exactly what gets shipped.
+fn read_spec(path: &Path) -> Result<Spec, String> {
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        return swagger_ui::bundle(path).map_err(|err| err.to_string());
+    }
+    let content = fs::read(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let builder = match path.file_name() {
+        Some(name) => Spec::builder().name(name.to_string_lossy().into_owned()),
+        None => Spec::builder(),
+    };
+    Ok(builder.content(content))
+}
+
+/// Reads the JSON config of the ui at `path`, if any.
+fn read_config<T: DeserializeOwned>(path: Option<&Path>) -> Result<Option<T>, String> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let content = fs::read(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
@@ -0,0 +1,89 @@
+//! Compile-time compression of specs, behind `swagger_ui::swagger_spec_file!("openapi.json", gzip)`.
+//!
+//! Use the macros of `swagger-ui` with its `compression` feature instead of this crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::quote;
+use std::io::Write;
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+/// The arguments, e.g. `"../openapi.json", gzip`.
+struct Input {
+    path: LitStr,
+    encoding: Ident,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let encoding = input.parse()?;
+        Ok(Self { path, encoding })
+    }
+}
+
+/// Reads the file at the path relative to the current file, like `include_bytes!`, compresses it
+/// with `gzip` or `brotli` and expands to `(content_coding, compressed, raw_len, digest)`.
+///
+/// The digest is the 64-bit FNV-1a hash of the raw content, the same `swagger-ui` sends in
+/// `X-Spec-Digest`. Only the compressed bytes end up in the binary.
+#[doc(hidden)]
+#[proc_macro]
+pub fn include_precompressed(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &Input) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |message: String| syn::Error::new(input.path.span(), message);
+    let file = match input.path.span().unwrap().local_file() {
+        Some(current) => current.with_file_name("").join(input.path.value()),
+        None => PathBuf::from(input.path.value()),
+    };
+    let file = file
+        .canonicalize()
+        .map_err(|err| error(format!("couldn't read {}: {err}", file.display())))?;
+    let raw = std::fs::read(&file)
+        .map_err(|err| error(format!("couldn't read {}: {err}", file.display())))?;
+
+    let (coding, compressed) = match input.encoding.to_string().as_str() {
+        "gzip" => ("gzip", gzip(&raw)),
+        "brotli" => ("br", brotli(&raw)),
+        other => {
+            let message = format!("unknown compression `{other}`, expected `gzip` or `brotli`");
+            return Err(syn::Error::new(input.encoding.span(), message));
+        }
+    };
+    let compressed = Literal::byte_string(&compressed);
+    let raw_len = raw.len();
+    let digest = raw.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let file = file.to_string_lossy();
+    Ok(quote! {{
+        // rebuilds when the file changes, without its raw content ending up in the binary
+        const _: &[u8] = include_bytes!(#file);
+        (#coding, #compressed as &'static [u8], #raw_len, #digest)
+    }})
+}
+
+fn gzip(raw: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::best());
+    encoder.write_all(raw).expect("writing to a Vec can't fail");
+    encoder.finish().expect("writing to a Vec can't fail")
+}
+
+fn brotli(raw: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    {
+        let mut encoder = brotli::CompressorWriter::new(&mut out, 4096, 11, 22);
+        encoder.write_all(raw).expect("writing to a Vec can't fail");
+    }
+    out
+}
@@ -0,0 +1,81 @@
+//! Benchmarks of the serving path, run with `cargo bench -p swagger-ui`.
+//!
+//! Compare against a baseline with `cargo bench -p swagger-ui -- --save-baseline before` on the
+//! old tree and `--baseline before` on the new one.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use http::Request;
+use std::collections::BTreeSet;
+use std::hint::black_box;
+use swagger_ui::{
+    filter_spec, json_schemas, minify_spec, serve_asset, serve_config, sort_spec,
+    swagger_spec_file, Config, MountPath, SwaggerUiService,
+};
+
+fn get(uri: &str) -> Request<()> {
+    let mut req = Request::get(uri).body(()).unwrap();
+    req.extensions_mut().insert(MountPath("/docs".to_string()));
+    req
+}
+
+fn assets(c: &mut Criterion) {
+    let service = SwaggerUiService::new(swagger_spec_file!("../examples/openapi.json"), None);
+    let mut group = c.benchmark_group("assets");
+    group.bench_function("serve_asset", |b| {
+        b.iter(|| serve_asset(black_box("swagger-ui-bundle.js")))
+    });
+    group.bench_function("index", |b| {
+        let req = get("/index.html");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.bench_function("bundle", |b| {
+        let req = get("/swagger-ui-bundle.js");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.finish();
+}
+
+fn config(c: &mut Criterion) {
+    let service = SwaggerUiService::new(swagger_spec_file!("../examples/openapi.json"), None);
+    let mut group = c.benchmark_group("config");
+    group.bench_function("serve_config", |b| {
+        let config = Config::default();
+        b.iter(|| serve_config(black_box(&config), "/docs"))
+    });
+    group.bench_function("cached", |b| {
+        let req = get("/swagger-ui-config.json");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.bench_function("forwarded_query", |b| {
+        let req = get("/swagger-ui-config.json?filter=pet");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.finish();
+}
+
+fn spec(c: &mut Criterion) {
+    let spec = swagger_spec_file!("../examples/openapi.json");
+    let service = SwaggerUiService::new(spec.clone(), None);
+    let mut group = c.benchmark_group("spec");
+    group.bench_function("serve", |b| {
+        let req = get("/openapi.json");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.bench_function("pretty", |b| {
+        let req = get("/openapi.json?pretty=1");
+        b.iter(|| service.handle(black_box(&req)))
+    });
+    group.bench_function("filter_spec", |b| {
+        let allowed = BTreeSet::from(["pet".to_string()]);
+        b.iter(|| filter_spec(black_box(&spec), &allowed))
+    });
+    group.bench_function("sort_spec", |b| b.iter(|| sort_spec(black_box(&spec))));
+    group.bench_function("minify_spec", |b| b.iter(|| minify_spec(black_box(&spec))));
+    group.bench_function("json_schemas", |b| {
+        b.iter(|| json_schemas(black_box(&spec)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, assets, config, spec);
+criterion_main!(benches);
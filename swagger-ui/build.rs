@@ -1,90 +1,166 @@
 use anyhow::Context;
-use async_recursion::async_recursion;
+use base64::Engine;
 use futures::StreamExt;
 use reqwest::{Client, IntoUrl};
-use serde::Deserialize;
-use std::path::PathBuf;
+use sha2::{Digest, Sha384};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
 
-#[derive(Deserialize)]
-#[serde(rename_all = "snake_case")]
-enum EntryType {
-    File,
-    Dir,
+/// Bundle of another viewer, downloaded only when its feature is enabled.
+struct Viewer {
+    /// The feature, as in `CARGO_FEATURE_{feature}`.
+    feature: &'static str,
+    /// The folder below `.viewers` the files are put into.
+    folder: &'static str,
+    /// The files of the bundle.
+    files: &'static [File],
 }
 
-#[derive(Deserialize)]
-struct FolderEntry {
-    name: String,
-    url: String,
-    r#type: EntryType,
-    download_url: Option<String>,
+/// A file of a viewer bundle.
+struct File {
+    url: &'static str,
+    /// The name it is saved under.
+    name: &'static str,
+    /// The sha384 hash as in a subresource integrity attribute, checked before the file is saved.
+    /// Files without one aren't saved, the build fails with the hash of the download to pin here.
+    integrity: Option<&'static str>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-#[serde(untagged)]
-pub enum OneOrMany<T> {
-    /// Single value
-    One(T),
-    /// Array of values
-    Vec(Vec<T>),
-}
-impl<T> From<OneOrMany<T>> for Vec<T> {
-    fn from(from: OneOrMany<T>) -> Self {
-        match from {
-            OneOrMany::One(val) => vec![val],
-            OneOrMany::Vec(vec) => vec,
-        }
-    }
-}
+const VIEWERS: &[Viewer] = &[
+    Viewer {
+        feature: "REDOC",
+        folder: "redoc",
+        files: &[File {
+            url: "https://cdn.jsdelivr.net/npm/redoc@2.1.5/bundles/redoc.standalone.js",
+            name: "redoc.standalone.js",
+            integrity: None,
+        }],
+    },
+    Viewer {
+        feature: "RAPIDOC",
+        folder: "rapidoc",
+        files: &[File {
+            url: "https://cdn.jsdelivr.net/npm/rapidoc@9.3.8/dist/rapidoc-min.js",
+            name: "rapidoc-min.js",
+            integrity: None,
+        }],
+    },
+    Viewer {
+        feature: "SCALAR",
+        folder: "scalar",
+        files: &[File {
+            url: "https://cdn.jsdelivr.net/npm/@scalar/api-reference@1.31.3/dist/browser/standalone.js",
+            name: "standalone.js",
+            integrity: Some("sha384-/ixUolHvytveRr0mAhs/jZ/7OZ5la1ixJMW9rTpJiDgu++FFJf3n5Ywziq2zu31x"),
+        }],
+    },
+    Viewer {
+        feature: "ELEMENTS",
+        folder: "elements",
+        files: &[
+            File {
+                url: "https://cdn.jsdelivr.net/npm/@stoplight/elements@8.4.2/web-components.min.js",
+                name: "web-components.min.js",
+                integrity: None,
+            },
+            File {
+                url: "https://cdn.jsdelivr.net/npm/@stoplight/elements@8.4.2/styles.min.css",
+                name: "styles.min.css",
+                integrity: None,
+            },
+        ],
+    },
+    Viewer {
+        feature: "SWAGGER_EDITOR",
+        folder: "swagger-editor",
+        files: &[
+            File {
+                url: "https://cdn.jsdelivr.net/npm/swagger-editor-dist@4.13.1/swagger-editor-bundle.js",
+                name: "swagger-editor-bundle.js",
+                integrity: None,
+            },
+            File {
+                url: "https://cdn.jsdelivr.net/npm/swagger-editor-dist@4.13.1/swagger-editor-standalone-preset.js",
+                name: "swagger-editor-standalone-preset.js",
+                integrity: None,
+            },
+            File {
+                url: "https://cdn.jsdelivr.net/npm/swagger-editor-dist@4.13.1/swagger-editor.css",
+                name: "swagger-editor.css",
+                integrity: None,
+            },
+        ],
+    },
+];
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let out_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?).join(".dist");
-    download_folder(
-        "https://api.github.com/repos/swagger-api/swagger-ui/contents/dist",
-        out_dir,
-    )
-    .await?;
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    download_viewers(manifest_dir.join(".viewers")).await?;
     Ok(())
 }
 
-#[async_recursion]
-async fn download_folder(url: &str, to: impl Into<PathBuf> + Send + 'static) -> anyhow::Result<()> {
-    let entries: Vec<_> = reqwest()?
-        .get(url)
-        .send()
-        .await
-        .with_context(|| format!("failed to query folder data for {url}"))?
-        .json::<OneOrMany<FolderEntry>>()
-        .await
-        .with_context(|| format!("failed to parse json for {url}"))?
-        .into();
-    let ref path = to.into();
-    futures::future::try_join_all(entries.into_iter().map(|entry| async move {
-        match entry.r#type {
-            EntryType::File => {
-                download_file(entry.download_url.unwrap(), path.clone().join(entry.name)).await
+async fn download_viewers(to: PathBuf) -> anyhow::Result<()> {
+    let enabled = VIEWERS
+        .iter()
+        .filter(|viewer| std::env::var_os(format!("CARGO_FEATURE_{}", viewer.feature)).is_some());
+    for viewer in enabled {
+        for file in viewer.files {
+            let path = to.join(viewer.folder).join(file.name);
+            // the bundles are pinned, so there's nothing to update once they are downloaded
+            if !path.exists() {
+                download_file(file.url, &path, file.integrity)
+                    .await
+                    .with_context(|| format!("failed to download {}", file.url))?;
             }
-            EntryType::Dir => download_folder(&entry.url, path.clone().join(entry.name)).await,
         }
-    }))
-    .await?;
+    }
     Ok(())
 }
 
+/// Downloads `url` to `to` through a temporary file, so that an interrupted or rejected download
+/// is not taken for the file by the next build.
 async fn download_file(
     url: impl IntoUrl + Send,
-    to: impl Into<PathBuf> + Send,
+    to: &Path,
+    integrity: Option<&str>,
 ) -> anyhow::Result<()> {
-    let path = to.into();
-    if let Some(parent) = path.parent() {
+    if let Some(parent) = to.parent() {
         tokio::fs::create_dir_all(parent).await?;
     }
-    let mut byte_stream = reqwest()?.get(url).send().await?.bytes_stream();
-    let mut tmp_file = tokio::fs::File::create(path).await?;
+    let mut byte_stream = reqwest()?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes_stream();
+    let tmp_path = to.with_extension("part");
+    let mut tmp_file = tokio::fs::File::create(&tmp_path).await?;
+    let mut hasher = Sha384::new();
     while let Some(item) = byte_stream.next().await {
-        tokio::io::copy(&mut item?.as_ref(), &mut tmp_file).await?;
+        let item = item?;
+        hasher.update(&item);
+        tmp_file.write_all(&item).await?;
+    }
+    tmp_file.flush().await?;
+    drop(tmp_file);
+
+    let hash = format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+    );
+    match integrity {
+        Some(expected) if expected == hash => {}
+        Some(expected) => {
+            tokio::fs::remove_file(&tmp_path).await?;
+            anyhow::bail!("expected {expected}, downloaded {hash}");
+        }
+        None => {
+            tokio::fs::remove_file(&tmp_path).await?;
+            anyhow::bail!("{} is not pinned, pin its hash {hash}", to.display());
+        }
     }
+    tokio::fs::rename(&tmp_path, to).await?;
     Ok(())
 }
 
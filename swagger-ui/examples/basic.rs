@@ -1,5 +1,6 @@
 use swagger_ui::{
-    swagger_spec_file, Assets, Config, DefaultModelRendering, DocExpansion, Filter, Spec,
+    swagger_spec_file, try_swagger_spec_file, Assets, Config, DefaultModelRendering, DocExpansion,
+    Filter, Spec,
 };
 
 fn main() {
@@ -14,10 +15,14 @@ fn main() {
     // Load openapi spec (compile-time)
     let _spec: Spec = swagger_spec_file!("./openapi.json");
 
+    // Or at runtime, with the path in the error if it can't be read
+    let _spec: std::io::Result<Spec> = try_swagger_spec_file!("./openapi.json");
+
     // swagger-ui configuration struct
     let _config: Config = Config {
         url: "".to_string(),
         urls: vec![],
+        urls_primary_name: None,
         deep_linking: false,
         display_operation_id: false,
         default_models_expand_depth: 0,
@@ -29,5 +34,8 @@ fn main() {
         max_displayed_tags: 0,
         show_extensions: false,
         show_common_extensions: false,
+        servers: vec![],
+        keep_spec_servers: false,
+        default_server: None,
     };
 }
@@ -0,0 +1,25 @@
+use hyper::server::conn::http1;
+use hyper_util::rt::TokioIo;
+use swagger_ui::{swagger_spec_file, SwaggerUiService};
+use tokio::net::TcpListener;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let service = SwaggerUiService::new(swagger_spec_file!("./openapi.json"), None);
+
+    let listener = TcpListener::bind("127.0.0.1:3000").await?;
+    println!("serving swagger-ui at http://127.0.0.1:3000/");
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = service.clone();
+        tokio::spawn(async move {
+            // `SwaggerUiService` implements hyper's `Service` with the `hyper` feature
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                eprintln!("failed to serve connection: {err}");
+            }
+        });
+    }
+}
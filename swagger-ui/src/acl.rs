@@ -0,0 +1,54 @@
+//! Deciding which APIs a requester is entitled to see.
+
+use http::{Extensions, HeaderMap};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// Request extension with the roles of the requester, inserted by the authentication in front of
+/// the docs, e.g. from the claims of a token, see [`Acl::roles`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Roles(pub BTreeSet<String>);
+
+impl Roles {
+    /// Create the roles `roles`.
+    pub fn new<R: Into<String>>(roles: impl IntoIterator<Item = R>) -> Self {
+        Self(roles.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Returns whether a request may see an API, see [`Acl::new`].
+type Allows = Arc<dyn Fn(&HeaderMap, &Extensions) -> bool + Send + Sync>;
+
+/// Who may see an API, e.g. of a [`SpecRegistry`](crate::SpecRegistry) or of the specs of
+/// [`DocService::multi`](crate::DocService::multi).
+#[derive(Clone)]
+pub struct Acl(Allows);
+
+impl fmt::Debug for Acl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Acl").finish_non_exhaustive()
+    }
+}
+
+impl Acl {
+    /// Allow the requests `allows` returns `true` for, from their headers and extensions.
+    pub fn new(allows: impl Fn(&HeaderMap, &Extensions) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(allows))
+    }
+
+    /// Allow the requests with one of `roles` in their [`Roles`] extension.
+    pub fn roles<R: Into<String>>(roles: impl IntoIterator<Item = R>) -> Self {
+        let roles: BTreeSet<String> = roles.into_iter().map(Into::into).collect();
+        Self::new(move |_, extensions| {
+            extensions
+                .get::<Roles>()
+                .is_some_and(|granted| !granted.0.is_disjoint(&roles))
+        })
+    }
+
+    /// Returns whether the request with `headers` and `extensions` may see the API.
+    pub fn allows(&self, headers: &HeaderMap, extensions: &Extensions) -> bool {
+        (self.0)(headers, extensions)
+    }
+}
@@ -0,0 +1,216 @@
+//! Merging the specs of several services into one hub document.
+
+use crate::markdown::METHODS;
+use crate::Spec;
+use serde_json::{json, Map, Value};
+
+/// Returns one spec titled `title` with the operations of every spec of `specs`, under the path
+/// prefix given with it, or `None` if one of them isn't an OpenAPI 3 JSON document.
+///
+/// The paths of a spec with prefix `payments` are served as `/payments/charges`, as a gateway in
+/// front of the services routes them, and its tags are renamed to e.g. `payments: charges`, or
+/// `payments` for untagged operations. Its components, security schemes included, are renamed
+/// to e.g. `payments.Charge`, and the references to them rewritten, so that components of the
+/// same name don't collide. Global security requirements are copied into the operations they
+/// apply to. The servers of the specs are left out, set those of the gateway with
+/// [`Config::servers`](crate::Config::servers).
+pub fn merge_specs<'a>(
+    title: &str,
+    specs: impl IntoIterator<Item = (&'a str, &'a Spec)>,
+) -> Option<Spec> {
+    let mut openapi = String::from("3.0.3");
+    let mut paths = Map::new();
+    let mut webhooks = Map::new();
+    let mut tags = vec![];
+    let mut components: Map<String, Value> = Map::new();
+
+    for (prefix, spec) in specs {
+        let prefix = prefix.trim_matches('/');
+        let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+        let version = document["openapi"].as_str()?;
+        if version > openapi.as_str() {
+            openapi = version.to_string();
+        }
+        let security = document["security"].take();
+        rename(&mut document, prefix);
+
+        for (section, merged) in [("paths", &mut paths), ("webhooks", &mut webhooks)] {
+            let Some(items) = document[section].as_object_mut() else {
+                continue;
+            };
+            for (name, mut item) in std::mem::take(items) {
+                for method in METHODS {
+                    if let Some(operation) = item.get_mut(method).and_then(Value::as_object_mut) {
+                        prefix_tags(operation, prefix);
+                        if !security.is_null() && !operation.contains_key("security") {
+                            let mut security = security.clone();
+                            rename_requirements(&mut security, prefix);
+                            operation.insert("security".into(), security);
+                        }
+                    }
+                }
+                if let Some(item) = item.as_object_mut() {
+                    item.remove("servers");
+                }
+                let name = match section {
+                    "paths" => format!("/{prefix}{name}"),
+                    _ => format!("{prefix}.{name}"),
+                };
+                merged.insert(name, item);
+            }
+        }
+        for tag in document["tags"].as_array_mut().into_iter().flatten() {
+            if let Some(name) = tag["name"].as_str() {
+                tag["name"] = json!(format!("{prefix}: {name}"));
+                tags.push(tag.take());
+            }
+        }
+        let kinds = document["components"].as_object_mut().into_iter().flatten();
+        for (kind, named) in kinds {
+            let Some(named) = named.as_object_mut() else {
+                continue;
+            };
+            let merged = components.entry(kind.clone()).or_insert_with(|| json!({}));
+            for (name, component) in std::mem::take(named) {
+                merged[format!("{prefix}.{name}")] = component;
+            }
+        }
+    }
+
+    let mut hub =
+        json!({"openapi": openapi, "info": {"title": title, "version": "1.0.0"}, "paths": paths});
+    if !webhooks.is_empty() {
+        hub["webhooks"] = Value::Object(webhooks);
+    }
+    if !tags.is_empty() {
+        hub["tags"] = Value::Array(tags);
+    }
+    if !components.is_empty() {
+        hub["components"] = Value::Object(components);
+    }
+    let content = serde_json::to_vec(&hub).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name("openapi.json").json(content))
+}
+
+/// Rewrites the references to components below `value`, and the security schemes its
+/// requirements name, to the components renamed with `prefix`.
+fn rename(value: &mut Value, prefix: &str) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match (key.as_str(), value) {
+                    ("$ref", Value::String(reference)) => rename_reference(reference, prefix),
+                    // discriminators map values to references as well
+                    ("mapping", Value::Object(mapping)) => {
+                        for reference in mapping.values_mut() {
+                            if let Value::String(reference) = reference {
+                                rename_reference(reference, prefix);
+                            }
+                        }
+                    }
+                    ("security", requirements @ Value::Array(_)) => {
+                        rename_requirements(requirements, prefix)
+                    }
+                    // values, not schemas, while the `examples` of media types are references
+                    ("example" | "enum" | "const" | "default", _) => {}
+                    ("examples", Value::Array(_)) => {}
+                    (_, value) => rename(value, prefix),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                rename(value, prefix);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rename_reference(reference: &mut String, prefix: &str) {
+    let Some(rest) = reference.strip_prefix("#/components/") else {
+        return;
+    };
+    if let Some((kind, name)) = rest.split_once('/') {
+        *reference = format!("#/components/{kind}/{prefix}.{name}");
+    }
+}
+
+/// Renames the security schemes the security requirements of `requirements` name.
+fn rename_requirements(requirements: &mut Value, prefix: &str) {
+    for requirement in requirements.as_array_mut().into_iter().flatten() {
+        if let Some(schemes) = requirement.as_object_mut() {
+            *schemes = std::mem::take(schemes)
+                .into_iter()
+                .map(|(scheme, scopes)| (format!("{prefix}.{scheme}"), scopes))
+                .collect();
+        }
+    }
+}
+
+fn prefix_tags(operation: &mut Map<String, Value>, prefix: &str) {
+    let tags: Vec<Value> = match operation.get("tags").and_then(Value::as_array) {
+        Some(tags) if !tags.is_empty() => tags
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|tag| json!(format!("{prefix}: {tag}")))
+            .collect(),
+        _ => vec![json!(prefix)],
+    };
+    operation.insert("tags".into(), Value::Array(tags));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_specs;
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn merges_specs_under_their_prefixes() {
+        let spec = |document: Value| Spec::builder().json(serde_json::to_vec(&document).unwrap());
+        let payments = spec(json!({
+            "openapi": "3.0.3",
+            "servers": [{"url": "https://payments.internal"}],
+            "security": [{"oauth": ["charges"]}],
+            "tags": [{"name": "charges"}],
+            "paths": {"/charges": {"post": {"tags": ["charges"], "responses": {"201": {
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Error"}}}
+            }}}}},
+            "components": {"schemas": {"Error": {}}, "securitySchemes": {"oauth": {"type": "oauth2"}}}
+        }));
+        let users = spec(json!({
+            "openapi": "3.1.0",
+            "paths": {"/users": {"get": {"security": []}}},
+            "components": {"schemas": {"Error": {"example": {"$ref": "#/components/schemas/Kept"}}}}
+        }));
+        let hub = merge_specs("Platform", [("payments", &payments), ("/users/", &users)]).unwrap();
+        let hub: Value = serde_json::from_slice(&hub.content).unwrap();
+
+        assert_eq!(hub["openapi"], "3.1.0");
+        assert_eq!(hub["info"]["title"], "Platform");
+        let charge = &hub["paths"]["/payments/charges"]["post"];
+        assert_eq!(charge["tags"], json!(["payments: charges"]));
+        assert_eq!(charge["security"], json!([{"payments.oauth": ["charges"]}]));
+        assert_eq!(
+            charge["responses"]["201"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/payments.Error"
+        );
+        assert_eq!(
+            hub["paths"]["/users/users"]["get"]["tags"],
+            json!(["users"])
+        );
+        assert_eq!(hub["tags"], json!([{"name": "payments: charges"}]));
+        let schemas = &hub["components"]["schemas"];
+        assert!(schemas["payments.Error"].is_object());
+        assert_eq!(
+            schemas["users.Error"]["example"]["$ref"],
+            "#/components/schemas/Kept"
+        );
+        assert!(hub["components"]["securitySchemes"]["payments.oauth"].is_object());
+        assert!(hub.get("servers").is_none());
+
+        let swagger = Spec::builder().json(r#"{"swagger": "2.0"}"#);
+        assert!(merge_specs("Platform", [("legacy", &swagger)]).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+//! Adding an analytics snippet to the docs, for visitors who don't object to tracking.
+
+use crate::Body;
+use http::header::VARY;
+use http::{HeaderMap, HeaderName, HeaderValue, Response};
+
+/// Header of browsers asking not to be tracked.
+const DNT: HeaderName = HeaderName::from_static("dnt");
+
+/// An analytics snippet added to `index.html`, e.g. of Plausible, Matomo or Google Analytics,
+/// see [`DocService::with_analytics`](crate::DocService::with_analytics).
+///
+/// Visitors sending `DNT: 1` get the page without it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analytics {
+    snippet: String,
+    before_consent: bool,
+}
+
+impl Analytics {
+    /// Create analytics adding the HTML `snippet`, which is trusted and inserted as it is.
+    pub fn new(snippet: impl Into<String>) -> Self {
+        Self {
+            snippet: snippet.into(),
+            before_consent: false,
+        }
+    }
+
+    /// Create analytics counting the views of the site `domain` with Plausible.
+    pub fn plausible(domain: &str) -> Self {
+        Self::new(format!(
+            r#"<script defer data-domain="{}" src="https://plausible.io/js/script.js"></script>"#,
+            crate::html::attribute(domain)
+        ))
+    }
+
+    /// Add the snippet to the page showing the terms set with
+    /// [`DocService::with_terms`](crate::DocService::with_terms) as well, instead of only once
+    /// they are accepted.
+    pub fn before_consent(mut self) -> Self {
+        self.before_consent = true;
+        self
+    }
+
+    /// Returns whether the snippet is added to the page showing the terms.
+    pub(crate) fn tracks_before_consent(&self) -> bool {
+        self.before_consent
+    }
+
+    /// Adds the snippet to the page `response`, unless `headers` ask not to be tracked.
+    pub(crate) fn apply(&self, headers: &HeaderMap, response: &mut Response<Body>) {
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_static("DNT"));
+        if headers.get(DNT).is_some_and(|dnt| dnt == "1") {
+            return;
+        }
+        let page = std::mem::replace(response.body_mut(), Body::empty()).into_bytes();
+        *response.body_mut() = crate::html::inject_script(&page, &self.snippet).into();
+    }
+}
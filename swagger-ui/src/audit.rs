@@ -0,0 +1,30 @@
+//! Audit events of who viewed which docs.
+
+use http::{Extensions, HeaderMap, Method, StatusCode};
+use std::sync::Arc;
+
+/// One request for a document of a [`DocService`](crate::DocService), passed to the hook of
+/// [`with_access_audit`](crate::DocService::with_access_audit).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessEvent {
+    pub method: Method,
+    /// The public path of the document, including the mount path, e.g. `/docs/index.html`.
+    pub path: String,
+    pub status: StatusCode,
+    /// Who made the request, as extracted by the identity function, or `None` if it extracted
+    /// nothing.
+    pub identity: Option<String>,
+}
+
+/// Extracts the identity of a request from its headers and extensions, e.g. the subject of a
+/// JWT put into the extensions by an authentication middleware.
+pub(crate) type Identify = Arc<dyn Fn(&HeaderMap, &Extensions) -> Option<String> + Send + Sync>;
+
+/// Receives every [`AccessEvent`].
+pub(crate) type Hook = Arc<dyn Fn(&AccessEvent) + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct Audit {
+    pub(crate) identify: Identify,
+    pub(crate) hook: Hook,
+}
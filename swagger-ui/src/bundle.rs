@@ -0,0 +1,194 @@
+use crate::Spec;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Reads the JSON spec at `entry` and inlines every `$ref` into other files, so the spec can be
+/// served as one document.
+///
+/// References are resolved relative to the file they are in. Local references of the entry, like
+/// `#/components/schemas/Pet`, are kept, while those of the referenced files are inlined too, as
+/// they point into documents that aren't served. Urls like `https://example.com/pet.json` are
+/// left for the viewer to fetch. The spec is named after the file name of `entry`, and specs
+/// without references into other files are returned as they are.
+///
+/// Fails if a file can't be read or isn't JSON, if a reference doesn't resolve or if references
+/// form a cycle.
+pub fn bundle(entry: impl AsRef<Path>) -> io::Result<Spec> {
+    let entry = entry.as_ref();
+    let mut bundler = Bundler {
+        entry: fs::canonicalize(entry).map_err(|err| context(entry, err))?,
+        documents: HashMap::new(),
+        inlined: false,
+    };
+    let mut document = bundler.load(entry)?.clone();
+    let file = bundler.entry.clone();
+    bundler.inline(&mut document, &file, &mut vec![])?;
+    let content = match bundler.inlined {
+        true => serde_json::to_vec(&document).expect("Could not serialize spec as JSON."),
+        // keeps the order of the keys, which viewers show the paths in
+        false => fs::read(entry).map_err(|err| context(entry, err))?,
+    };
+
+    let builder = match entry.file_name() {
+        Some(name) => Spec::builder().name(name.to_string_lossy().into_owned()),
+        None => Spec::builder(),
+    };
+    Ok(builder.json(content))
+}
+
+struct Bundler {
+    entry: PathBuf,
+    /// Every file read so far, by its canonical path.
+    documents: HashMap<PathBuf, Value>,
+    /// Whether any reference was inlined.
+    inlined: bool,
+}
+
+impl Bundler {
+    fn load(&mut self, path: &Path) -> io::Result<&Value> {
+        let path = fs::canonicalize(path).map_err(|err| context(path, err))?;
+        if !self.documents.contains_key(&path) {
+            let content = fs::read(&path).map_err(|err| context(&path, err))?;
+            let document = serde_json::from_slice(&content).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {err}", path.display()),
+                )
+            })?;
+            self.documents.insert(path.clone(), document);
+        }
+        Ok(&self.documents[&path])
+    }
+
+    /// Replaces the references below `value`, which is part of `file`.
+    ///
+    /// `stack` holds the references being inlined, to detect cycles.
+    fn inline(
+        &mut self,
+        value: &mut Value,
+        file: &Path,
+        stack: &mut Vec<String>,
+    ) -> io::Result<()> {
+        match value {
+            Value::Object(object) => {
+                let reference = object.get("$ref").and_then(Value::as_str);
+                if let Some((target, pointer)) = reference.and_then(|r| self.target(r, file)) {
+                    let target = fs::canonicalize(&target).map_err(|err| context(&target, err))?;
+                    let key = format!("{}#{pointer}", target.display());
+                    if stack.contains(&key) {
+                        return Err(invalid(format!(
+                            "{}: `$ref` cycle through {key}",
+                            file.display()
+                        )));
+                    }
+                    let mut resolved =
+                        self.load(&target)?
+                            .pointer(&pointer)
+                            .cloned()
+                            .ok_or_else(|| {
+                                invalid(format!(
+                                    "{}: `$ref` {key} does not resolve",
+                                    file.display()
+                                ))
+                            })?;
+                    stack.push(key);
+                    self.inline(&mut resolved, &target, stack)?;
+                    stack.pop();
+                    *value = resolved;
+                    self.inlined = true;
+                    return Ok(());
+                }
+                for value in object.values_mut() {
+                    self.inline(value, file, stack)?;
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.inline(value, file, stack)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the file and JSON pointer `reference` in `file` points to, or `None` if it is left
+    /// as is.
+    fn target(&self, reference: &str, file: &Path) -> Option<(PathBuf, String)> {
+        if reference.contains("://") {
+            return None;
+        }
+        let (path, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        if path.is_empty() {
+            // local references of the entry still resolve once it is bundled
+            return (file != self.entry).then(|| (file.to_path_buf(), pointer.to_string()));
+        }
+        let target = file.parent().unwrap_or(Path::new("")).join(path);
+        Some((target, pointer.to_string()))
+    }
+}
+
+fn context(path: &Path, err: io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {err}", path.display()))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bundle;
+    use serde_json::{json, Value};
+    use std::fs;
+
+    #[test]
+    fn inlines_references_into_other_files() {
+        let dir = std::env::temp_dir().join(format!("swagger-ui-bundle-{}", std::process::id()));
+        fs::create_dir_all(dir.join("schemas")).unwrap();
+        let write = |name: &str, value: Value| {
+            fs::write(dir.join(name), serde_json::to_vec(&value).unwrap()).unwrap()
+        };
+        write(
+            "openapi.json",
+            json!({
+                "openapi": "3.0.3",
+                "paths": {"/pets": {"$ref": "paths.json#/pets"}},
+                "components": {"schemas": {"Id": {"type": "integer"}}}
+            }),
+        );
+        write(
+            "paths.json",
+            json!({"pets": {"get": {"responses": {"200": {"$ref": "schemas/pet.json"}}}}}),
+        );
+        write(
+            "schemas/pet.json",
+            json!({
+                "type": "object",
+                "properties": {"id": {"$ref": "../openapi.json#/components/schemas/Id"}, "tag": {"$ref": "#/$defs/Tag"}},
+                "$defs": {"Tag": {"type": "string"}}
+            }),
+        );
+
+        let spec = bundle(dir.join("openapi.json")).unwrap();
+        assert_eq!(spec.name, "openapi.json");
+        let document: Value = serde_json::from_slice(&spec.content).unwrap();
+        let pet = &document["paths"]["/pets"]["get"]["responses"]["200"];
+        assert_eq!(pet["properties"]["id"], json!({"type": "integer"}));
+        assert_eq!(pet["properties"]["tag"], json!({"type": "string"}));
+
+        let standalone = br#"{"paths": {"/b": {}, "/a": {"$ref": "https://example.com/a.json"}}}"#;
+        fs::write(dir.join("standalone.json"), standalone).unwrap();
+        let spec = bundle(dir.join("standalone.json")).unwrap();
+        assert_eq!(spec.content.as_ref(), standalone);
+
+        write("paths.json", json!({"pets": {"$ref": "#/pets"}}));
+        let err = bundle(dir.join("openapi.json")).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+//! `Cache-Control` of the responses of a viewer.
+
+use http::HeaderValue;
+use std::time::Duration;
+
+/// `Cache-Control` of the responses of a [`DocService`](crate::DocService), see
+/// [`DocService::with_cache_policy`](crate::DocService::with_cache_policy).
+///
+/// The embedded assets only change with the version of this crate, while `index.html`, the
+/// config and the spec may change with every deployment, so both get their own header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub(crate) assets: HeaderValue,
+    pub(crate) documents: HeaderValue,
+}
+
+impl CachePolicy {
+    /// Caches nothing, e.g. for docs behind a login.
+    pub fn no_store() -> Self {
+        Self::new(
+            HeaderValue::from_static("no-store"),
+            HeaderValue::from_static("no-store"),
+        )
+    }
+
+    /// Caches the assets for `max_age` and revalidates the documents on every use.
+    pub fn max_age(max_age: Duration) -> Self {
+        let assets = format!("public, max-age={}", max_age.as_secs());
+        Self::new(
+            HeaderValue::from_str(&assets).expect("Could not create Cache-Control header."),
+            HeaderValue::from_static("no-cache"),
+        )
+    }
+
+    /// Sends `assets` with the embedded assets and `documents` with `index.html`, the config,
+    /// the spec and the documents generated from it.
+    pub fn new(assets: HeaderValue, documents: HeaderValue) -> Self {
+        Self { assets, documents }
+    }
+}
@@ -0,0 +1,192 @@
+//! Human-readable changelogs between versions of a spec, rendered from their [`SpecDiff`].
+
+use crate::diff::{ADDED_OPERATION, DEPRECATED_OPERATION};
+use crate::handlers::{content, head, method_not_allowed, status};
+use crate::{html, mime, Body, Change, ChangeKind, DiffService, Spec, SpecDiff};
+use http::{Method, Request, Response, StatusCode};
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+const CHANGELOG_PAGE: &str = include_str!("changelog.html");
+
+/// Headings of the groups of a changelog, see [`group`].
+const GROUPS: [&str; 5] = [
+    "Breaking changes",
+    "New endpoints",
+    "Deprecations",
+    "Endpoint changes",
+    "Schema changes",
+];
+
+/// Returns the index of the group in [`GROUPS`] `change` is listed in.
+fn group(change: &Change) -> usize {
+    if change.breaking {
+        0
+    } else if change.kind == ChangeKind::Added && change.message == ADDED_OPERATION {
+        1
+    } else if change.message == DEPRECATED_OPERATION {
+        2
+    } else if change.location.starts_with("schema ") {
+        4
+    } else {
+        3
+    }
+}
+
+/// Renders `diff`, between the versions `from` and `to`, into an HTML page.
+///
+/// The changes are grouped into breaking changes, new endpoints, deprecations, other changes of
+/// endpoints and changes of schemas, leaving out empty groups.
+pub fn changelog(from: &str, to: &str, diff: &SpecDiff) -> String {
+    let mut sections = String::new();
+    for (index, title) in GROUPS.iter().enumerate() {
+        let items: String = diff
+            .changes
+            .iter()
+            .filter(|change| group(change) == index)
+            .map(|change| {
+                format!(
+                    "<li><code>{}</code>{}</li>\n",
+                    html::attribute(&change.location),
+                    html::attribute(&change.message)
+                )
+            })
+            .collect();
+        if !items.is_empty() {
+            let class = if index == 0 {
+                r#" class="breaking""#
+            } else {
+                ""
+            };
+            sections.push_str(&format!(
+                "<section{class}>\n<h2>{title}</h2>\n<ul>\n{items}</ul>\n</section>\n"
+            ));
+        }
+    }
+    if sections.is_empty() {
+        sections.push_str("<p>No changes.</p>\n");
+    }
+
+    let title = format!("Changes from {from} to {to}");
+    CHANGELOG_PAGE
+        .replace("{{title}}", &html::attribute(&title))
+        .replace("{{groups}}", &sections)
+}
+
+/// A `tower::Service` answering e.g. `?from=v1&to=v2` with the [`changelog`] between the versions
+/// of a spec named `v1` and `v2`.
+///
+/// Without `to`, the changelog is up to the last version, and without `from`, from the version
+/// before `to`, so requests without parameters show what the latest version changed. Unknown
+/// versions are answered with `404 Not Found` and versions that aren't JSON with
+/// `422 Unprocessable Entity`.
+#[derive(Debug, Clone)]
+pub struct ChangelogService {
+    diff: DiffService,
+}
+
+impl ChangelogService {
+    /// Create a service with the changelogs between the given versions of a spec, by name and
+    /// from oldest to newest.
+    pub fn new<N: Into<String>>(versions: impl IntoIterator<Item = (N, Spec)>) -> Self {
+        DiffService::new(versions).into()
+    }
+
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        match *req.method() {
+            Method::GET => self.get(req.uri().query()),
+            Method::HEAD => head(self.get(req.uri().query())),
+            _ => method_not_allowed(),
+        }
+    }
+
+    fn get(&self, query: Option<&str>) -> Response<Body> {
+        let (mut from, mut to) = (None, None);
+        for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+            match key.as_ref() {
+                "from" => from = Some(value.into_owned()),
+                "to" => to = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let versions: Vec<&str> = self.diff.versions().collect();
+        let Some(to) = to.or_else(|| versions.last().map(|last| last.to_string())) else {
+            return status(StatusCode::NOT_FOUND);
+        };
+        let from = from.or_else(|| {
+            let index = versions.iter().position(|version| *version == to)?;
+            Some(versions.get(index.checked_sub(1)?)?.to_string())
+        });
+        let Some(from) = from else {
+            return status(StatusCode::NOT_FOUND);
+        };
+
+        match self.diff.compare(&from, &to) {
+            Ok(diff) => content(mime::HTML, changelog(&from, &to, &diff).into_bytes().into()),
+            Err(code) => status(code),
+        }
+    }
+}
+
+impl From<DiffService> for ChangelogService {
+    fn from(diff: DiffService) -> Self {
+        Self { diff }
+    }
+}
+
+impl<B> Service<Request<B>> for ChangelogService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChangelogService;
+    use crate::Spec;
+    use http::{Request, StatusCode};
+    use serde_json::{json, Value};
+
+    fn spec(value: Value) -> Spec {
+        Spec::builder().json(serde_json::to_vec(&value).unwrap())
+    }
+
+    #[test]
+    fn groups_the_changes_of_the_latest_version() {
+        let v1 = spec(json!({"paths": {"/pets": {"get": {}, "delete": {}}}}));
+        let v2 = spec(json!({
+            "paths": {"/pets": {"get": {"deprecated": true}, "post": {}}},
+            "components": {"schemas": {"Pet": {}}}
+        }));
+        let service = ChangelogService::new([("v1", v1), ("v2", v2)]);
+
+        let response = service.handle(&Request::get("/docs/changelog").body(()).unwrap());
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains("<title>Changes from v1 to v2</title>"));
+        let position = |text: &str| page.find(text).unwrap();
+        assert!(position("<h2>Breaking changes</h2>") < position("DELETE /pets"));
+        assert!(position("<h2>New endpoints</h2>") < position("POST /pets"));
+        assert!(position("<h2>Deprecations</h2>") < position("<code>GET /pets</code>"));
+        assert!(position("<h2>Schema changes</h2>") < position("schema Pet"));
+        assert!(!page.contains("Endpoint changes"));
+
+        let same = service.handle(&Request::get("/?from=v2&to=v2").body(()).unwrap());
+        let page = String::from_utf8(same.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains("No changes."));
+
+        let first = service.handle(&Request::get("/?to=v1").body(()).unwrap());
+        assert_eq!(first.status(), StatusCode::NOT_FOUND);
+    }
+}
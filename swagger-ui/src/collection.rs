@@ -0,0 +1,320 @@
+//! The requests of a spec as templates, for exporting them into API clients.
+
+use crate::example::{self, resolve};
+use crate::markdown::sections;
+use crate::Spec;
+use serde_json::Value;
+
+/// The operations of a spec, ready to be rendered into the format of an API client.
+pub(crate) struct Collection {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    /// Url of the first server, with its variables as `{name}`.
+    pub(crate) base_url: String,
+    /// Variables of the first server with their defaults.
+    pub(crate) variables: Vec<Parameter>,
+    /// Authentication of every request that doesn't specify its own.
+    pub(crate) auth: Option<Auth>,
+    /// The requests by tag, in the order of the spec.
+    pub(crate) folders: Vec<Folder>,
+}
+
+pub(crate) struct Folder {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) requests: Vec<RequestTemplate>,
+}
+
+pub(crate) struct RequestTemplate {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    /// Upper case, e.g. `GET`.
+    pub(crate) method: String,
+    /// Path below the base url, with its parameters as `{name}`.
+    pub(crate) path: String,
+    pub(crate) path_parameters: Vec<Parameter>,
+    pub(crate) query: Vec<Parameter>,
+    pub(crate) headers: Vec<Parameter>,
+    pub(crate) body: Option<RequestBody>,
+    /// Authentication of this request, `None` if it uses the one of the collection.
+    pub(crate) auth: Option<Auth>,
+}
+
+/// A named value, e.g. a query parameter with its example.
+pub(crate) struct Parameter {
+    pub(crate) name: String,
+    pub(crate) value: String,
+    pub(crate) description: Option<String>,
+}
+
+pub(crate) struct RequestBody {
+    pub(crate) content_type: String,
+    pub(crate) text: String,
+}
+
+/// How a request authenticates, mapped from the security schemes of the spec.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) enum Auth {
+    /// Unauthenticated.
+    None,
+    Basic,
+    Bearer,
+    ApiKey {
+        name: String,
+        in_query: bool,
+    },
+    OAuth2,
+}
+
+impl Auth {
+    /// Returns the variables the credentials of this authentication are left to.
+    pub(crate) fn credentials(&self) -> &'static [&'static str] {
+        match self {
+            Auth::None => &[],
+            Auth::Basic => &["username", "password"],
+            Auth::Bearer => &["bearerToken"],
+            Auth::ApiKey { .. } => &["apiKey"],
+            Auth::OAuth2 => &["accessToken"],
+        }
+    }
+}
+
+impl Collection {
+    /// Collects the requests of `spec`, or returns `None` if it isn't JSON.
+    pub(crate) fn new(spec: &Spec) -> Option<Self> {
+        let document: Value = serde_json::from_slice(&spec.content).ok()?;
+        let info = &document["info"];
+
+        let (base_url, variables) = match document["servers"][0].as_object() {
+            Some(server) => {
+                let variables = server
+                    .get("variables")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, variable)| Parameter {
+                        name: name.clone(),
+                        value: text(&variable["default"]),
+                        description: description(variable),
+                    })
+                    .collect();
+                (text(&server["url"]), variables)
+            }
+            // Swagger 2.0
+            None => {
+                let base_path = document["basePath"].as_str().unwrap_or_default();
+                let base_url = match document["host"].as_str() {
+                    Some(host) => {
+                        let scheme = document["schemes"][0].as_str().unwrap_or("https");
+                        format!("{scheme}://{host}{base_path}")
+                    }
+                    None => base_path.to_string(),
+                };
+                (base_url, vec![])
+            }
+        };
+
+        let folders = sections(&document)
+            .into_iter()
+            .map(|(tag, operations)| Folder {
+                description: document["tags"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find(|entry| entry["name"] == tag.as_str())
+                    .and_then(description),
+                name: tag,
+                requests: operations
+                    .into_iter()
+                    .map(|(path, method, operation)| request(&document, path, method, operation))
+                    .collect(),
+            })
+            .collect();
+
+        Some(Self {
+            name: info["title"]
+                .as_str()
+                .unwrap_or(spec.name.as_ref())
+                .to_string(),
+            description: description(info),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            variables,
+            auth: auth(&document, &document["security"]),
+            folders,
+        })
+    }
+
+    /// Returns the requests of every folder.
+    pub(crate) fn requests(&self) -> impl Iterator<Item = &RequestTemplate> {
+        self.folders.iter().flat_map(|folder| &folder.requests)
+    }
+}
+
+fn request(document: &Value, path: &str, method: &str, operation: &Value) -> RequestTemplate {
+    let item = &document["paths"][path];
+    let parameters: Vec<&Value> = [&item["parameters"], &operation["parameters"]]
+        .into_iter()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|parameter| resolve(document, parameter))
+        .collect();
+    let of = |location: &str| -> Vec<Parameter> {
+        parameters
+            .iter()
+            .filter(|parameter| parameter["in"] == location)
+            .filter_map(|parameter| {
+                Some(Parameter {
+                    name: parameter["name"].as_str()?.to_string(),
+                    value: parameter_value(document, parameter),
+                    description: description(parameter),
+                })
+            })
+            .collect()
+    };
+
+    let mut headers = of("header");
+    // described by the spec in other ways, and set by the clients themselves
+    headers.retain(|header| {
+        !["accept", "content-type", "authorization"].contains(&header.name.to_lowercase().as_str())
+    });
+    let body = request_body(document, operation, &parameters);
+    if let Some(body) = &body {
+        headers.push(Parameter {
+            name: "Content-Type".to_string(),
+            value: body.content_type.clone(),
+            description: None,
+        });
+    }
+
+    let name = operation["summary"]
+        .as_str()
+        .or_else(|| operation["operationId"].as_str())
+        .filter(|name| !name.trim().is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {path}", method.to_uppercase()));
+    RequestTemplate {
+        name,
+        description: description(operation),
+        method: method.to_uppercase(),
+        path: path.to_string(),
+        path_parameters: of("path"),
+        query: of("query"),
+        headers,
+        body,
+        auth: operation
+            .get("security")
+            .and_then(|security| auth(document, security)),
+    }
+}
+
+fn request_body(document: &Value, operation: &Value, parameters: &[&Value]) -> Option<RequestBody> {
+    let (content_type, value) = match parameters.iter().find(|p| p["in"] == "body") {
+        // Swagger 2.0
+        Some(parameter) => {
+            let content_type = operation["consumes"][0]
+                .as_str()
+                .or_else(|| document["consumes"][0].as_str())
+                .unwrap_or("application/json");
+            (
+                content_type,
+                example::generate(document, &parameter["schema"]),
+            )
+        }
+        None => {
+            let body = resolve(document, &operation["requestBody"]);
+            let (content_type, content) = body["content"].as_object()?.iter().next()?;
+            let value = content.get("example").cloned().or_else(|| {
+                let (_, example) = content["examples"].as_object()?.iter().next()?;
+                resolve(document, example).get("value").cloned()
+            });
+            let value = value.unwrap_or_else(|| example::generate(document, &content["schema"]));
+            (content_type.as_str(), value)
+        }
+    };
+    let text = match value {
+        Value::String(text) if !content_type.contains("json") => text,
+        value => serde_json::to_string_pretty(&value).expect("Could not serialize JSON."),
+    };
+    Some(RequestBody {
+        content_type: content_type.to_string(),
+        text,
+    })
+}
+
+/// Returns the documented example of a parameter, or an empty string if there is none.
+fn parameter_value(document: &Value, parameter: &Value) -> String {
+    // Swagger 2.0 parameters carry the schema fields themselves
+    let schema = resolve(document, parameter.get("schema").unwrap_or(parameter));
+    let example = parameter
+        .get("example")
+        .or_else(|| schema.get("example"))
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema["enum"].get(0));
+    example.map(text).unwrap_or_default()
+}
+
+/// Maps the first requirement of `security` to the scheme it names.
+fn auth(document: &Value, security: &Value) -> Option<Auth> {
+    let requirement = security.as_array()?.first();
+    let Some(name) = requirement
+        .and_then(Value::as_object)
+        .and_then(|requirement| requirement.keys().next())
+    else {
+        // no requirement, or the empty one making authentication optional
+        return Some(Auth::None);
+    };
+    let scheme = document["components"]["securitySchemes"]
+        .get(name)
+        .or_else(|| document["securityDefinitions"].get(name))?;
+    let scheme = resolve(document, scheme);
+    match scheme["type"].as_str()? {
+        "basic" => Some(Auth::Basic),
+        "http" => match scheme["scheme"].as_str()?.to_lowercase().as_str() {
+            "basic" => Some(Auth::Basic),
+            "bearer" => Some(Auth::Bearer),
+            _ => None,
+        },
+        "apiKey" => match scheme["in"].as_str()? {
+            location @ ("header" | "query") => Some(Auth::ApiKey {
+                name: scheme["name"].as_str()?.to_string(),
+                in_query: location == "query",
+            }),
+            _ => None,
+        },
+        "oauth2" | "openIdConnect" => Some(Auth::OAuth2),
+        _ => None,
+    }
+}
+
+/// Replaces every `{name}` in `template` with `replace(name)`.
+pub(crate) fn substitute(template: &str, replace: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some((before, after)) = rest.split_once('{') {
+        let Some((name, after)) = after.split_once('}') else {
+            break;
+        };
+        out.push_str(before);
+        out.push_str(&replace(name));
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Returns `value` as text, without the quotes of strings.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn description(value: &Value) -> Option<String> {
+    value["description"]
+        .as_str()
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .map(str::to_string)
+}
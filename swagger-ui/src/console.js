@@ -0,0 +1,194 @@
+(function () {
+    "use strict";
+    var root = document.getElementById("console");
+    var tryIt = root.dataset.tryIt === "true";
+    var methods = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+    var spec;
+
+    function el(tag, props, children) {
+        var node = document.createElement(tag);
+        Object.assign(node, props || {});
+        (children || []).forEach(function (child) {
+            node.append(child);
+        });
+        return node;
+    }
+
+    // follows local `$ref`s like `#/components/schemas/Pet`
+    function resolve(value) {
+        var seen = 0;
+        while (value && value.$ref && seen++ < 16) {
+            value = value.$ref.slice(2).split("/").reduce(function (node, key) {
+                return node && node[key.replace(/~1/g, "/").replace(/~0/g, "~")];
+            }, spec);
+        }
+        return value || {};
+    }
+
+    function typeOf(schema) {
+        var name = schema.$ref ? schema.$ref.split("/").pop() : "";
+        schema = resolve(schema);
+        if (schema.type === "array") {
+            return typeOf(schema.items || {}) + "[]";
+        }
+        return name || schema.type || (schema.enum ? "enum" : "object");
+    }
+
+    function schemaTable(schema) {
+        schema = resolve(schema);
+        if (schema.type === "array") {
+            schema = resolve(schema.items || {});
+        }
+        var required = schema.required || [];
+        var rows = Object.keys(schema.properties || {}).map(function (name) {
+            var property = schema.properties[name];
+            return el("tr", {}, [
+                el("td", {}, [el("code", {textContent: name + (required.indexOf(name) < 0 ? "" : " *")})]),
+                el("td", {textContent: typeOf(property)}),
+                el("td", {textContent: resolve(property).description || ""}),
+            ]);
+        });
+        return rows.length ? el("table", {}, rows) : el("p", {}, [el("code", {textContent: typeOf(schema)})]);
+    }
+
+    function bodySchema(container) {
+        if (!container) {
+            return null;
+        }
+        if (container.schema) {
+            return container.schema;
+        }
+        var content = container.content || {};
+        var type = Object.keys(content)[0];
+        return type ? content[type].schema || {} : null;
+    }
+
+    function serverUrl() {
+        if (spec.servers && spec.servers.length) {
+            return spec.servers[0].url.replace(/\/$/, "");
+        }
+        return spec.basePath ? spec.basePath.replace(/\/$/, "") : "";
+    }
+
+    function form(path, method, parameters, hasBody) {
+        var inputs = parameters.filter(function (parameter) {
+            return parameter.in !== "body";
+        }).map(function (parameter) {
+            return el("label", {}, [parameter.name + " (" + parameter.in + ") ", el("input", {name: parameter.name}), el("br")]);
+        });
+        var body = hasBody ? el("textarea", {placeholder: "request body"}) : null;
+        var output = el("pre", {hidden: true});
+        var send = el("button", {type: "submit", textContent: "Send"});
+        var node = el("form", {}, inputs.concat(body ? [body] : [], [send, output]));
+        node.onsubmit = function (event) {
+            event.preventDefault();
+            var url = path, query = new URLSearchParams(), headers = {};
+            parameters.forEach(function (parameter) {
+                var input = node.elements[parameter.name];
+                var value = input ? input.value : "";
+                if (!value) {
+                    return;
+                }
+                if (parameter.in === "path") {
+                    url = url.replace("{" + parameter.name + "}", encodeURIComponent(value));
+                } else if (parameter.in === "query") {
+                    query.append(parameter.name, value);
+                } else if (parameter.in === "header") {
+                    headers[parameter.name] = value;
+                }
+            });
+            if (body && body.value) {
+                headers["Content-Type"] = "application/json";
+            }
+            var search = query.toString();
+            output.hidden = false;
+            output.textContent = "...";
+            fetch(serverUrl() + url + (search ? "?" + search : ""), {
+                method: method.toUpperCase(),
+                headers: headers,
+                body: body && body.value ? body.value : undefined,
+            }).then(function (response) {
+                return response.text().then(function (text) {
+                    output.textContent = response.status + " " + response.statusText + "\n\n" + text;
+                });
+            }, function (error) {
+                output.textContent = String(error);
+            });
+        };
+        return node;
+    }
+
+    function operation(path, method, item) {
+        var op = item[method];
+        var parameters = (item.parameters || []).concat(op.parameters || []).map(resolve);
+        var requestBody = bodySchema(resolve(op.requestBody)) || bodySchema(parameters.filter(function (parameter) {
+            return parameter.in === "body";
+        })[0]);
+        var children = [];
+        if (op.description) {
+            children.push(el("p", {textContent: op.description}));
+        }
+        if (parameters.length) {
+            children.push(el("h4", {textContent: "Parameters"}), el("table", {}, parameters.map(function (parameter) {
+                return el("tr", {}, [
+                    el("td", {}, [el("code", {textContent: parameter.name + (parameter.required ? " *" : "")})]),
+                    el("td", {textContent: parameter.in}),
+                    el("td", {textContent: parameter.schema ? typeOf(parameter.schema) : parameter.type || ""}),
+                    el("td", {textContent: parameter.description || ""}),
+                ]);
+            })));
+        }
+        if (requestBody) {
+            children.push(el("h4", {textContent: "Request body"}), schemaTable(requestBody));
+        }
+        var responses = op.responses || {};
+        children.push(el("h4", {textContent: "Responses"}), el("table", {}, Object.keys(responses).map(function (status) {
+            var response = resolve(responses[status]);
+            var schema = bodySchema(response);
+            return el("tr", {}, [
+                el("td", {}, [el("code", {textContent: status})]),
+                el("td", {textContent: response.description || ""}),
+                el("td", {textContent: schema ? typeOf(schema) : ""}),
+            ]);
+        })));
+        if (tryIt) {
+            children.push(el("h4", {textContent: "Try it"}), form(path, method, parameters, !!requestBody));
+        }
+        return el("details", {}, [
+            el("summary", {}, [el("b", {className: method, textContent: method}), el("code", {textContent: path}), " " + (op.summary || "")]),
+            el("div", {}, children),
+        ]);
+    }
+
+    function render() {
+        var info = spec.info || {};
+        root.append(el("h1", {textContent: (info.title || "API") + (info.version ? " " + info.version : "")}));
+        if (info.description) {
+            root.append(el("p", {textContent: info.description}));
+        }
+        Object.keys(spec.paths || {}).forEach(function (path) {
+            var item = resolve(spec.paths[path]);
+            methods.filter(function (method) {
+                return item[method];
+            }).forEach(function (method) {
+                root.append(operation(path, method, item));
+            });
+        });
+        var schemas = (spec.components || {}).schemas || spec.definitions || {};
+        if (Object.keys(schemas).length) {
+            root.append(el("h2", {textContent: "Schemas"}));
+            Object.keys(schemas).forEach(function (name) {
+                root.append(el("details", {}, [el("summary", {}, [el("code", {textContent: name})]), el("div", {}, [schemaTable(schemas[name])])]));
+            });
+        }
+    }
+
+    fetch(root.dataset.specUrl).then(function (response) {
+        return response.json();
+    }).then(function (document) {
+        spec = document;
+        render();
+    }, function (error) {
+        root.textContent = "Could not load the spec: " + error;
+    });
+})();
@@ -0,0 +1,106 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the console script, with the spec url and configuration filled in.
+const INDEX: &str = include_str!("console.html");
+
+/// The console itself, a few KB of plain javascript and CSS without any dependencies.
+const ASSETS: [(&str, &[u8]); 2] = [
+    ("console.js", include_bytes!("console.js")),
+    ("console.css", include_bytes!("console.css")),
+];
+
+/// Configuration of the console.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleConfig {
+    /// Title of the page, `API` if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Leave out the forms sending requests to the api, e.g. when it is not reachable from the
+    /// browser.
+    #[serde(default)]
+    pub hide_try_it: bool,
+}
+
+/// A minimal console listing the operations and schemas of the spec, with a form to try each
+/// operation. Unlike the other viewers it comes without any downloaded bundle, for deployments
+/// where the size of the binary matters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Console;
+
+impl DocUi for Console {
+    type Config = ConsoleConfig;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        ASSETS
+            .iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, content)| Cow::Borrowed(*content))
+    }
+
+    fn asset_paths() -> Vec<String> {
+        ASSETS.iter().map(|(name, _)| name.to_string()).collect()
+    }
+
+    fn index(spec_url: &str, config: &ConsoleConfig) -> Option<Bytes> {
+        let title = config.title.as_deref().unwrap_or("API");
+        let page = INDEX
+            .replace("{{title}}", &html::attribute(title))
+            .replace("{{spec_url}}", &html::attribute(spec_url))
+            .replace(
+                "{{try_it}}",
+                if config.hide_try_it { "false" } else { "true" },
+            );
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with the console.
+///
+/// It is mounted like [`SwaggerUiService`](crate::SwaggerUiService), see
+/// [`MountPath`](crate::MountPath).
+pub type ConsoleService = DocService<Console>;
+
+#[cfg(test)]
+mod tests {
+    use super::{ConsoleConfig, ConsoleService};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn serves_console_below_the_mount() {
+        let service = ConsoleService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            ConsoleConfig {
+                title: Some("Pets & Co".to_string()),
+                hide_try_it: true,
+            },
+        );
+        let get = |uri: &str| {
+            let mut req = Request::get(uri).body(()).unwrap();
+            req.extensions_mut()
+                .insert(MountPath("/console".to_string()));
+            service.handle(&req)
+        };
+
+        let response = get("/");
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/console/index.html");
+
+        let response = get("/index.html");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains("<title>Pets &amp; Co</title>"));
+        assert!(page.contains(r#"data-spec-url="/console/openapi.json" data-try-it="false""#));
+
+        let response = get("/console.js");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.into_body().into_bytes().len() < 16 * 1024);
+        assert_eq!(get("/console.css").status(), StatusCode::OK);
+        assert_eq!(get("/openapi.json").status(), StatusCode::OK);
+    }
+}
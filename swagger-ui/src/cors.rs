@@ -0,0 +1,94 @@
+use crate::Body;
+use http::header::{
+    ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue, Method, Response, StatusCode};
+use std::time::Duration;
+
+/// Cross-origin resource sharing (CORS) headers emitted on the spec and config responses, so
+/// tooling hosted on other origins can fetch them.
+#[derive(Debug, Clone)]
+pub struct Cors {
+    /// Origins allowed to fetch the spec and config. An entry of `*` allows every origin.
+    pub allowed_origins: Vec<String>,
+    /// Methods announced in responses to preflight requests.
+    /// Default: `GET`, `HEAD`.
+    pub allowed_methods: Vec<Method>,
+    /// How long browsers may cache the response to a preflight request.
+    /// Default: `None` (the browser default).
+    pub max_age: Option<Duration>,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![],
+            allowed_methods: vec![Method::GET, Method::HEAD],
+            max_age: None,
+        }
+    }
+}
+
+impl Cors {
+    /// Allow every origin.
+    pub fn any() -> Self {
+        Self::origins(["*"])
+    }
+
+    /// Allow the specified origins, e.g. `https://portal.example.com`.
+    pub fn origins(origins: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_origins: origins.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the `Access-Control-Allow-Origin` value for a request with the given headers, if
+    /// its origin is allowed.
+    fn allow_origin(&self, request: &HeaderMap) -> Option<HeaderValue> {
+        if self.allowed_origins.iter().any(|origin| origin == "*") {
+            return Some(HeaderValue::from_static("*"));
+        }
+        let origin = request.get(ORIGIN)?;
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed.as_bytes() == origin.as_bytes())
+            .then(|| origin.clone())
+    }
+
+    /// Adds the CORS headers for a request with the given headers to `response`.
+    pub(crate) fn apply(&self, request: &HeaderMap, response: &mut HeaderMap) {
+        if let Some(origin) = self.allow_origin(request) {
+            if origin != "*" {
+                response.append(VARY, HeaderValue::from_static("Origin"));
+            }
+            response.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        }
+    }
+
+    /// Answers a preflight request with the given headers.
+    pub(crate) fn preflight(&self, request: &HeaderMap) -> Response<Body> {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        self.apply(request, response.headers_mut());
+
+        let headers = response.headers_mut();
+        let methods = self
+            .allowed_methods
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Ok(methods) = HeaderValue::from_str(&methods) {
+            headers.insert(ACCESS_CONTROL_ALLOW_METHODS, methods);
+        }
+        if let Some(requested) = request.get(ACCESS_CONTROL_REQUEST_HEADERS) {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+        }
+        if let Some(max_age) = self.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+        }
+        response
+    }
+}
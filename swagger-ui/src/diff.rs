@@ -0,0 +1,604 @@
+//! Comparison of two versions of a spec, classifying the changes as breaking or not.
+
+use crate::handlers::{content, head, method_not_allowed, status};
+use crate::markdown::METHODS;
+use crate::{mime, Body, Spec};
+use bytes::Bytes;
+use http::{Method, Request, Response, StatusCode};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+use std::fmt;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Message of the [`Change`] of an operation that was added.
+pub(crate) const ADDED_OPERATION: &str = "added operation";
+
+/// Message of the [`Change`] of an operation that was deprecated.
+pub(crate) const DEPRECATED_OPERATION: &str = "deprecated operation";
+
+/// What happened to the part of the spec a [`Change`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A difference between two versions of a spec, see [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// What happened.
+    pub kind: ChangeKind,
+    /// Where, e.g. `GET /pets/{id}` or `schema Pet`.
+    pub location: String,
+    /// What changed, e.g. `added required property name`.
+    pub message: String,
+    /// Whether existing clients may break, e.g. because an operation or a response field was
+    /// removed, an enum was narrowed or a request now needs more.
+    pub breaking: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.breaking { "breaking" } else { "ok" };
+        write!(f, "{marker}: {}: {}", self.location, self.message)
+    }
+}
+
+/// The differences between two versions of a spec, see [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecDiff {
+    /// Whether any of the changes is breaking.
+    pub breaking: bool,
+    /// The changes, operations first and schemas last.
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    /// Returns the breaking changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    fn push(&mut self, kind: ChangeKind, location: &str, message: String, breaking: bool) {
+        self.breaking |= breaking;
+        self.changes.push(Change {
+            kind,
+            location: location.to_string(),
+            message,
+            breaking,
+        });
+    }
+}
+
+/// Compares the operations and schemas of `old` and `new`, which have to be JSON.
+///
+/// Removed operations, parameters, responses, schemas and properties are breaking, as are
+/// narrowed enums, changed types and anything newly required. Additions are not.
+pub fn diff(old: &Spec, new: &Spec) -> serde_json::Result<SpecDiff> {
+    let old: Value = serde_json::from_slice(&old.content)?;
+    let new: Value = serde_json::from_slice(&new.content)?;
+    let mut diff = SpecDiff::default();
+
+    let empty = Map::new();
+    let old_paths = old["paths"].as_object().unwrap_or(&empty);
+    let new_paths = new["paths"].as_object().unwrap_or(&empty);
+    for (path, old_item) in old_paths {
+        for method in METHODS {
+            let location = format!("{} {path}", method.to_uppercase());
+            let Some(old_op) = old_item.get(method) else {
+                continue;
+            };
+            match new_paths.get(path).and_then(|item| item.get(method)) {
+                Some(new_op) => {
+                    let new_item = &new_paths[path];
+                    let comparison = Comparison {
+                        old: &old,
+                        new: &new,
+                        location: &location,
+                    };
+                    comparison.operation(&mut diff, (old_item, old_op), (new_item, new_op));
+                }
+                None => diff.push(
+                    ChangeKind::Removed,
+                    &location,
+                    "removed operation".into(),
+                    true,
+                ),
+            }
+        }
+    }
+    for (path, new_item) in new_paths {
+        for method in METHODS {
+            let old_op = old_paths.get(path).and_then(|item| item.get(method));
+            if new_item.get(method).is_some() && old_op.is_none() {
+                let location = format!("{} {path}", method.to_uppercase());
+                diff.push(ChangeKind::Added, &location, ADDED_OPERATION.into(), false);
+            }
+        }
+    }
+
+    let old_schemas = schemas(&old).unwrap_or(&empty);
+    let new_schemas = schemas(&new).unwrap_or(&empty);
+    for (name, old_schema) in old_schemas {
+        let location = format!("schema {name}");
+        match new_schemas.get(name) {
+            Some(new_schema) => {
+                let comparison = Comparison {
+                    old: &old,
+                    new: &new,
+                    location: &location,
+                };
+                comparison.schema(&mut diff, "", old_schema, new_schema);
+            }
+            None => diff.push(
+                ChangeKind::Removed,
+                &location,
+                "removed schema".into(),
+                true,
+            ),
+        }
+    }
+    for name in new_schemas
+        .keys()
+        .filter(|name| !old_schemas.contains_key(*name))
+    {
+        let location = format!("schema {name}");
+        diff.push(ChangeKind::Added, &location, "added schema".into(), false);
+    }
+    Ok(diff)
+}
+
+/// A `tower::Service` comparing versions of a spec, answering e.g. `?from=v1&to=v2` with the
+/// [`SpecDiff`] between the versions named `v1` and `v2` as JSON.
+///
+/// Requests without both parameters are answered with `400 Bad Request`, unknown versions with
+/// `404 Not Found` and versions that aren't JSON with `422 Unprocessable Entity`.
+#[derive(Debug, Clone)]
+pub struct DiffService {
+    versions: Arc<Vec<(String, Spec)>>,
+}
+
+impl DiffService {
+    /// Create a service comparing the given versions of a spec, by name.
+    pub fn new<N: Into<String>>(versions: impl IntoIterator<Item = (N, Spec)>) -> Self {
+        let versions = versions
+            .into_iter()
+            .map(|(name, spec)| (name.into(), spec))
+            .collect();
+        Self {
+            versions: Arc::new(versions),
+        }
+    }
+
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        match *req.method() {
+            Method::GET => self.get(req.uri().query()),
+            Method::HEAD => head(self.get(req.uri().query())),
+            _ => method_not_allowed(),
+        }
+    }
+
+    fn get(&self, query: Option<&str>) -> Response<Body> {
+        let (mut from, mut to) = (None, None);
+        for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+            match key.as_ref() {
+                "from" => from = Some(value),
+                "to" => to = Some(value),
+                _ => {}
+            }
+        }
+        let (Some(from), Some(to)) = (from, to) else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+        match self.compare(&from, &to) {
+            Ok(diff) => {
+                let json = serde_json::to_vec(&diff).expect("Could not serialize diff as JSON.");
+                content(mime::JSON, Bytes::from(json).into())
+            }
+            Err(code) => status(code),
+        }
+    }
+
+    /// Returns the names of the versions, in the order they were given.
+    pub(crate) fn versions(&self) -> impl Iterator<Item = &str> {
+        self.versions.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Compares the versions named `from` and `to`, failing with the status to answer with.
+    pub(crate) fn compare(&self, from: &str, to: &str) -> Result<SpecDiff, StatusCode> {
+        let version = |name: &str| {
+            self.versions
+                .iter()
+                .find(|(version, _)| version == name)
+                .map(|(_, spec)| spec)
+        };
+        let (Some(old), Some(new)) = (version(from), version(to)) else {
+            return Err(StatusCode::NOT_FOUND);
+        };
+        diff(old, new).map_err(|_| StatusCode::UNPROCESSABLE_ENTITY)
+    }
+}
+
+impl<B> Service<Request<B>> for DiffService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+/// Returns the OpenAPI 3 `components.schemas` or the Swagger 2.0 `definitions`.
+fn schemas(document: &Value) -> Option<&Map<String, Value>> {
+    document["components"]["schemas"]
+        .as_object()
+        .or_else(|| document["definitions"].as_object())
+}
+
+/// Both documents and where in them the compared values are.
+struct Comparison<'a> {
+    old: &'a Value,
+    new: &'a Value,
+    location: &'a str,
+}
+
+impl Comparison<'_> {
+    fn operation(&self, diff: &mut SpecDiff, old: (&Value, &Value), new: (&Value, &Value)) {
+        if new.1["deprecated"] == true && old.1["deprecated"] != true {
+            let message = DEPRECATED_OPERATION.to_string();
+            diff.push(ChangeKind::Changed, self.location, message, false);
+        }
+        let old_parameters = parameters(self.old, old.0, old.1);
+        let new_parameters = parameters(self.new, new.0, new.1);
+        for (key, old_parameter) in &old_parameters {
+            let name = format!("parameter {} ({})", key.0, key.1);
+            let Some((_, new_parameter)) = new_parameters.iter().find(|(other, _)| other == key)
+            else {
+                // clients still sending it are not rejected for it
+                let message = format!("removed {name}");
+                diff.push(ChangeKind::Removed, self.location, message, false);
+                continue;
+            };
+            if new_parameter["required"] == true && old_parameter["required"] != true {
+                let message = format!("made {name} required");
+                diff.push(ChangeKind::Changed, self.location, message, true);
+            }
+            let old_schema = old_parameter.get("schema").unwrap_or(old_parameter);
+            let new_schema = new_parameter.get("schema").unwrap_or(new_parameter);
+            self.schema(diff, &name, old_schema, new_schema);
+        }
+        for (key, new_parameter) in &new_parameters {
+            if old_parameters.iter().all(|(other, _)| other != key) {
+                let required = new_parameter["required"] == true;
+                let message = match required {
+                    true => format!("added required parameter {} ({})", key.0, key.1),
+                    false => format!("added parameter {} ({})", key.0, key.1),
+                };
+                diff.push(ChangeKind::Added, self.location, message, required);
+            }
+        }
+
+        let old_body = request_body(self.old, old.1);
+        let new_body = request_body(self.new, new.1);
+        match (old_body, new_body) {
+            (Some(old_body), Some(new_body)) => {
+                self.schema(diff, "request body", old_body, new_body)
+            }
+            (None, Some(_)) => {
+                let required = body_required(self.new, new.1);
+                let message = match required {
+                    true => "added required request body".to_string(),
+                    false => "added request body".to_string(),
+                };
+                diff.push(ChangeKind::Added, self.location, message, required);
+            }
+            (Some(_), None) => {
+                let message = "removed request body".to_string();
+                diff.push(ChangeKind::Removed, self.location, message, false);
+            }
+            (None, None) => {}
+        }
+
+        let empty = Map::new();
+        let old_responses = old.1["responses"].as_object().unwrap_or(&empty);
+        let new_responses = new.1["responses"].as_object().unwrap_or(&empty);
+        for status in old_responses.keys() {
+            if !new_responses.contains_key(status) {
+                let message = format!("removed response {status}");
+                diff.push(ChangeKind::Removed, self.location, message, true);
+            }
+        }
+        for status in new_responses.keys() {
+            if !old_responses.contains_key(status) {
+                let message = format!("added response {status}");
+                diff.push(ChangeKind::Added, self.location, message, false);
+            }
+        }
+    }
+
+    /// Compares two versions of a schema, `name` being where in the location it is.
+    fn schema(&self, diff: &mut SpecDiff, name: &str, old: &Value, new: &Value) {
+        // named schemas are compared on their own
+        if old.get("$ref").is_some() || new.get("$ref").is_some() {
+            if old["$ref"] != new["$ref"] {
+                let message = format!("changed type of {}", describe(name));
+                diff.push(ChangeKind::Changed, self.location, message, true);
+            }
+            return;
+        }
+
+        if old["type"] != new["type"] || old["format"] != new["format"] {
+            let message = format!("changed type of {}", describe(name));
+            diff.push(ChangeKind::Changed, self.location, message, true);
+        }
+
+        if let (Some(old_values), Some(new_values)) =
+            (old["enum"].as_array(), new["enum"].as_array())
+        {
+            let removed: Vec<String> = old_values
+                .iter()
+                .filter(|value| !new_values.contains(value))
+                .map(Value::to_string)
+                .collect();
+            if !removed.is_empty() {
+                let message = format!(
+                    "removed {} from enum of {}",
+                    removed.join(", "),
+                    describe(name)
+                );
+                diff.push(ChangeKind::Changed, self.location, message, true);
+            }
+            let added: Vec<String> = new_values
+                .iter()
+                .filter(|value| !old_values.contains(value))
+                .map(Value::to_string)
+                .collect();
+            if !added.is_empty() {
+                let message = format!("added {} to enum of {}", added.join(", "), describe(name));
+                diff.push(ChangeKind::Changed, self.location, message, false);
+            }
+        }
+
+        let required = |schema: &Value, property: &str| {
+            schema["required"]
+                .as_array()
+                .is_some_and(|required| required.iter().any(|entry| entry == property))
+        };
+        let empty = Map::new();
+        let old_properties = old["properties"].as_object().unwrap_or(&empty);
+        let new_properties = new["properties"].as_object().unwrap_or(&empty);
+        for (property, old_property) in old_properties {
+            let path = match name {
+                "" => property.clone(),
+                name => format!("{name}.{property}"),
+            };
+            match new_properties.get(property) {
+                Some(new_property) => {
+                    if required(new, property) && !required(old, property) {
+                        let message = format!("made property {path} required");
+                        diff.push(ChangeKind::Changed, self.location, message, true);
+                    }
+                    self.schema(
+                        diff,
+                        &format!("property {path}"),
+                        old_property,
+                        new_property,
+                    );
+                }
+                None => {
+                    let message = format!("removed property {path}");
+                    diff.push(ChangeKind::Removed, self.location, message, true);
+                }
+            }
+        }
+        for property in new_properties.keys() {
+            if !old_properties.contains_key(property) {
+                let path = match name {
+                    "" => property.clone(),
+                    name => format!("{name}.{property}"),
+                };
+                let required = required(new, property);
+                let message = match required {
+                    true => format!("added required property {path}"),
+                    false => format!("added property {path}"),
+                };
+                diff.push(ChangeKind::Added, self.location, message, required);
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            let items = match name {
+                "" => "items".to_string(),
+                name => format!("{name} items"),
+            };
+            self.schema(diff, &items, old_items, new_items);
+        }
+    }
+}
+
+fn describe(name: &str) -> &str {
+    match name {
+        "" => "the schema",
+        name => name,
+    }
+}
+
+/// Returns the parameters of an operation by name and location, with those of its path item.
+fn parameters<'a>(
+    document: &'a Value,
+    item: &'a Value,
+    operation: &'a Value,
+) -> Vec<((String, String), &'a Value)> {
+    let mut parameters: Vec<((String, String), &Value)> = vec![];
+    let all = [&item["parameters"], &operation["parameters"]]
+        .into_iter()
+        .filter_map(Value::as_array)
+        .flatten();
+    for parameter in all {
+        let parameter = resolve(document, parameter);
+        if parameter["in"] == "body" {
+            continue;
+        }
+        let key = (
+            parameter["name"].as_str().unwrap_or_default().to_string(),
+            parameter["in"].as_str().unwrap_or_default().to_string(),
+        );
+        // operations override the parameters of their path item
+        parameters.retain(|(other, _)| *other != key);
+        parameters.push((key, parameter));
+    }
+    parameters
+}
+
+/// Returns the schema of the request body of an operation, from its OpenAPI 3 `requestBody` or
+/// its Swagger 2.0 `body` parameter.
+fn request_body<'a>(document: &'a Value, operation: &'a Value) -> Option<&'a Value> {
+    if let Some(body) = operation.get("requestBody") {
+        let body = resolve(document, body);
+        return body["content"].as_object()?.values().next()?.get("schema");
+    }
+    operation["parameters"]
+        .as_array()?
+        .iter()
+        .map(|parameter| resolve(document, parameter))
+        .find(|parameter| parameter["in"] == "body")?
+        .get("schema")
+}
+
+/// Returns whether the request body of an operation is required.
+fn body_required(document: &Value, operation: &Value) -> bool {
+    if let Some(body) = operation.get("requestBody") {
+        return resolve(document, body)["required"] == true;
+    }
+    operation["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|parameter| resolve(document, parameter))
+        .any(|parameter| parameter["in"] == "body" && parameter["required"] == true)
+}
+
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix('#'))
+    {
+        Some(pointer) => document.pointer(pointer).unwrap_or(&Value::Null),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, DiffService};
+    use crate::Spec;
+    use http::header::CONTENT_TYPE;
+    use http::{Request, StatusCode};
+    use serde_json::{json, Value};
+
+    fn spec(value: Value) -> Spec {
+        Spec::builder().json(serde_json::to_vec(&value).unwrap())
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let old = spec(json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{"name": "status", "in": "query", "schema": {"type": "string", "enum": ["available", "sold"]}}],
+                        "responses": {"200": {}, "404": {}}
+                    },
+                    "delete": {"responses": {"204": {}}}
+                }
+            },
+            "components": {"schemas": {
+                "Pet": {"properties": {"name": {"type": "string"}, "age": {"type": "integer"}}},
+                "Legacy": {}
+            }}
+        }));
+        let new = spec(json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "deprecated": true,
+                        "parameters": [
+                            {"name": "status", "in": "query", "schema": {"type": "string", "enum": ["available", "pending"]}},
+                            {"name": "limit", "in": "query", "schema": {"type": "integer"}}
+                        ],
+                        "responses": {"200": {}}
+                    },
+                    "post": {"responses": {"201": {}}}
+                }
+            },
+            "components": {"schemas": {
+                "Pet": {"required": ["owner"], "properties": {"name": {"type": "string"}, "owner": {"type": "string"}}}
+            }}
+        }));
+
+        let diff = diff(&old, &new).unwrap();
+        assert!(diff.breaking);
+        let changes: Vec<String> = diff.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            [
+                "ok: GET /pets: deprecated operation",
+                r#"breaking: GET /pets: removed "sold" from enum of parameter status (query)"#,
+                r#"ok: GET /pets: added "pending" to enum of parameter status (query)"#,
+                "ok: GET /pets: added parameter limit (query)",
+                "breaking: GET /pets: removed response 404",
+                "breaking: DELETE /pets: removed operation",
+                "ok: POST /pets: added operation",
+                "breaking: schema Legacy: removed schema",
+                "breaking: schema Pet: removed property age",
+                "breaking: schema Pet: added required property owner",
+            ]
+        );
+        assert_eq!(diff.breaking_changes().count(), 6);
+
+        let same = super::diff(&old, &old).unwrap();
+        assert!(!same.breaking);
+        assert!(same.changes.is_empty());
+    }
+
+    #[test]
+    fn serves_diffs_between_versions() {
+        let v1 = spec(json!({"paths": {"/pets": {"get": {}, "delete": {}}}}));
+        let v2 = spec(json!({"paths": {"/pets": {"get": {}}}}));
+        let service = DiffService::new([("v1", v1), ("v2", v2)]);
+        let get = |uri: &str| service.handle(&Request::get(uri).body(()).unwrap());
+
+        let response = get("/diff?from=v1&to=v2");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let body: Value = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "breaking": true,
+                "changes": [{
+                    "kind": "removed",
+                    "location": "DELETE /pets",
+                    "message": "removed operation",
+                    "breaking": true
+                }]
+            })
+        );
+
+        assert_eq!(get("/diff?from=v1").status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get("/diff?from=v1&to=v3").status(), StatusCode::NOT_FOUND);
+    }
+}
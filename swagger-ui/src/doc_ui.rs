@@ -0,0 +1,97 @@
+use crate::{Config, Spec};
+use bytes::Bytes;
+use std::borrow::Cow;
+
+/// A documentation viewer served by [`DocService`](crate::DocService), e.g. [`SwaggerUi`] or one
+/// of the viewers behind the `redoc`, `rapidoc`, `scalar`, `elements`, `swagger-editor` and
+/// `console` features.
+///
+/// Adapters mount every viewer the same way, so switching viewers only takes changing the type
+/// parameter, e.g. from `DocService<SwaggerUi>` to `DocService<Redoc>`.
+pub trait DocUi: Send + Sync + 'static {
+    /// Options of the viewer.
+    type Config: Clone + Default + Send + Sync + 'static;
+
+    /// Path of a document with the serialized config next to `index.html`, for viewers that load
+    /// their config separately instead of from the page.
+    const CONFIG_PATH: Option<&'static str> = None;
+
+    /// Returns the embedded asset at `path`, relative to the mount point.
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>>;
+
+    /// Returns the paths of all embedded assets.
+    fn asset_paths() -> Vec<String>;
+
+    /// Returns the Subresource Integrity hash of the asset at `path` if it was computed at build
+    /// time, otherwise it is computed from the content when needed.
+    fn asset_integrity(path: &str) -> Option<&'static str> {
+        let _ = path;
+        None
+    }
+
+    /// Renders `index.html` showing the spec at `spec_url`, or `None` if the page is one of the
+    /// assets.
+    fn index(spec_url: &str, config: &Self::Config) -> Option<Bytes>;
+
+    /// Serializes `config` for showing the spec at `spec_url` into the document at
+    /// [`CONFIG_PATH`](DocUi::CONFIG_PATH).
+    fn serialize_config(spec_url: &str, config: Self::Config) -> Bytes {
+        let _ = (spec_url, config);
+        Bytes::new()
+    }
+
+    /// Returns `spec` as it is served with `config`, or `None` if it is served unchanged.
+    fn prepare_spec(spec: &Spec, config: &Self::Config) -> Option<Spec> {
+        let _ = (spec, config);
+        None
+    }
+}
+
+/// swagger-ui, served from the embedded [`Assets`](crate::Assets) with its config at `swagger-ui-config.json`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwaggerUi;
+
+impl DocUi for SwaggerUi {
+    type Config = Config;
+
+    const CONFIG_PATH: Option<&'static str> = Some("swagger-ui-config.json");
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        swagger_ui_assets::get(path).map(|entry| Cow::Borrowed(entry.content))
+    }
+
+    fn asset_paths() -> Vec<String> {
+        swagger_ui_assets::PATHS
+            .iter()
+            .map(|path| path.to_string())
+            .collect()
+    }
+
+    fn asset_integrity(path: &str) -> Option<&'static str> {
+        swagger_ui_assets::get(path).map(|entry| entry.integrity)
+    }
+
+    fn index(_spec_url: &str, _config: &Config) -> Option<Bytes> {
+        None
+    }
+
+    fn serialize_config(spec_url: &str, mut config: Config) -> Bytes {
+        // an explicit url wins, e.g. one of a spec hosted by a gateway
+        if config.url.is_empty() {
+            config.url = spec_url.to_string();
+        }
+        Bytes::from(serde_json::to_vec(&config).expect("Could not serialize config as JSON."))
+    }
+
+    fn prepare_spec(spec: &Spec, config: &Config) -> Option<Spec> {
+        if config.servers.is_empty() && config.default_server.is_none() {
+            return None;
+        }
+        crate::servers::set_servers(
+            spec,
+            &config.servers,
+            config.keep_spec_servers || config.servers.is_empty(),
+            config.default_server.as_deref(),
+        )
+    }
+}
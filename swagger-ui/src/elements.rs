@@ -0,0 +1,152 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the Elements web components, with the spec url and attributes filled in.
+const INDEX: &str = include_str!("elements.html");
+
+/// The Stoplight Elements web components and their styles
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.viewers/elements"]
+pub struct ElementsAssets;
+
+/// Used to pick how Elements keeps track of the selected page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ElementsRouter {
+    /// In the path, which needs the server to serve `index.html` for every page.
+    History,
+    /// In the fragment of the url.
+    Hash,
+    /// In memory, so the url never changes.
+    Memory,
+    /// In the path, for pages rendered ahead of time.
+    Static,
+}
+
+/// Used to control the layout of Elements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ElementsLayout {
+    /// Three columns with a sidebar listing the operations.
+    Sidebar,
+    /// Everything below each other, for embedding into other pages.
+    Stacked,
+}
+
+/// Attributes of the `<elements-api>` element, see
+/// <https://github.com/stoplightio/elements/blob/main/docs/getting-started/elements/elements-options.md>.
+/// Attributes that are `None` keep the Elements defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementsConfig {
+    /// How the selected page is tracked, [`ElementsRouter::Hash`] by default as the service
+    /// only serves `index.html` itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub router: Option<ElementsRouter>,
+    /// The layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<ElementsLayout>,
+    /// Hide the panel to try out the operations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_try_it: Option<bool>,
+    /// Hide the schemas in the sidebar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_schemas: Option<bool>,
+    /// Hide the buttons to export the spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_export: Option<bool>,
+    /// Url of a logo to show in the sidebar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    /// Url of a CORS proxy to send the requests of the try it panel through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub try_it_cors_proxy: Option<String>,
+}
+
+impl Default for ElementsConfig {
+    fn default() -> Self {
+        Self {
+            router: Some(ElementsRouter::Hash),
+            layout: None,
+            hide_try_it: None,
+            hide_schemas: None,
+            hide_export: None,
+            logo: None,
+            try_it_cors_proxy: None,
+        }
+    }
+}
+
+/// Stoplight Elements, served from the embedded [`ElementsAssets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elements;
+
+impl DocUi for Elements {
+    type Config = ElementsConfig;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        ElementsAssets::get(path)
+    }
+
+    fn asset_paths() -> Vec<String> {
+        ElementsAssets::iter().map(Cow::into_owned).collect()
+    }
+
+    fn index(spec_url: &str, config: &ElementsConfig) -> Option<Bytes> {
+        let page = INDEX
+            .replace("{{spec_url}}", &html::attribute(spec_url))
+            .replace("{{attributes}}", &html::attributes(config));
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with Stoplight Elements.
+///
+/// It is mounted like [`SwaggerUiService`](crate::SwaggerUiService), see
+/// [`MountPath`](crate::MountPath).
+pub type ElementsService = DocService<Elements>;
+
+#[cfg(test)]
+mod tests {
+    use super::{ElementsConfig, ElementsLayout, ElementsService};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn serves_elements_below_the_mount() {
+        let service = ElementsService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            ElementsConfig {
+                layout: Some(ElementsLayout::Stacked),
+                hide_try_it: Some(true),
+                ..Default::default()
+            },
+        );
+        let get = |uri: &str| {
+            let mut req = Request::get(uri).body(()).unwrap();
+            req.extensions_mut()
+                .insert(MountPath("/elements".to_string()));
+            service.handle(&req)
+        };
+
+        let response = get("/");
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/elements/index.html");
+
+        let response = get("/index.html");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains(concat!(
+            r#"<elements-api apiDescriptionUrl="/elements/openapi.json" hideTryIt="true""#,
+            r#" layout="stacked" router="hash">"#
+        )));
+
+        assert_eq!(get("/web-components.min.js").status(), StatusCode::OK);
+        assert_eq!(get("/styles.min.css").headers()[CONTENT_TYPE], "text/css");
+        assert_eq!(get("/openapi.json").status(), StatusCode::OK);
+    }
+}
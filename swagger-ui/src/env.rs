@@ -0,0 +1,35 @@
+//! Switching the docs off per environment.
+
+/// The environment variable disabling the docs when set to anything but `0` or `false`, e.g. in
+/// production.
+pub const DISABLED_VAR: &str = "SWAGGER_UI_DISABLED";
+
+/// Returns whether [`DISABLED_VAR`] disables the docs in this environment.
+///
+/// The adapters check it when building their routes, and mount nothing if it is set.
+pub fn docs_disabled() -> bool {
+    disables(std::env::var(DISABLED_VAR).ok().as_deref())
+}
+
+fn disables(value: Option<&str>) -> bool {
+    match value.map(str::trim) {
+        None | Some("") => false,
+        Some(value) => !(value == "0" || value.eq_ignore_ascii_case("false")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::disables;
+
+    #[test]
+    fn only_disables_when_set_to_something_true() {
+        assert!(!disables(None));
+        assert!(!disables(Some("")));
+        assert!(!disables(Some("0")));
+        assert!(!disables(Some("False")));
+        assert!(disables(Some("1")));
+        assert!(disables(Some("true")));
+        assert!(disables(Some("yes")));
+    }
+}
@@ -0,0 +1,84 @@
+//! Example data for the schemas of a spec, for mock responses and request templates.
+
+use serde_json::{json, Map, Value};
+
+/// How deep nested schemas are generated before giving up with `null`, e.g. for recursive ones.
+const MAX_DEPTH: usize = 8;
+
+/// Generates data matching `schema` of `document`, from its examples, defaults and types.
+pub(crate) fn generate(document: &Value, schema: &Value) -> Value {
+    generate_nested(document, schema, 0)
+}
+
+fn generate_nested(document: &Value, schema: &Value, depth: usize) -> Value {
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+    let schema = resolve(document, schema);
+    for key in ["example", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    if let Some(value) = schema["enum"].as_array().and_then(|values| values.first()) {
+        return value.clone();
+    }
+    if let Some(all) = schema["allOf"].as_array() {
+        let mut merged = Map::new();
+        for part in all {
+            if let Value::Object(object) = generate_nested(document, part, depth + 1) {
+                merged.extend(object);
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema[key].as_array().and_then(|schemas| schemas.first()) {
+            return generate_nested(document, first, depth + 1);
+        }
+    }
+
+    let kind = match &schema["type"] {
+        // OpenAPI 3.1 type lists like `["string", "null"]`
+        Value::Array(kinds) => kinds
+            .iter()
+            .find(|kind| *kind != "null")
+            .and_then(Value::as_str),
+        kind => kind.as_str(),
+    };
+    match kind {
+        Some("string") => match schema["format"].as_str() {
+            Some("date-time") => json!("2024-01-01T00:00:00Z"),
+            Some("date") => json!("2024-01-01"),
+            Some("email") => json!("user@example.com"),
+            Some("uuid") => json!("00000000-0000-0000-0000-000000000000"),
+            Some("uri" | "url") => json!("https://example.com"),
+            _ => json!("string"),
+        },
+        Some("integer") => json!(0),
+        Some("number") => json!(0.0),
+        Some("boolean") => json!(true),
+        Some("array") => json!([generate_nested(document, &schema["items"], depth + 1)]),
+        Some("null") => Value::Null,
+        _ => {
+            let properties = schema["properties"].as_object().into_iter().flatten();
+            let object = properties
+                .map(|(name, property)| {
+                    (name.clone(), generate_nested(document, property, depth + 1))
+                })
+                .collect();
+            Value::Object(object)
+        }
+    }
+}
+
+/// Follows local `$ref`s of `document`, bounded so cyclic ones can't hang.
+pub(crate) fn resolve<'a>(document: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_DEPTH {
+        let Some(pointer) = value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) else {
+            break;
+        };
+        value = document.pointer(pointer).unwrap_or(&Value::Null);
+    }
+    value
+}
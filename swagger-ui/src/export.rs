@@ -0,0 +1,86 @@
+use crate::{Config, DocService, DocUi, MountPath, SpecOrUrl, SwaggerUiService};
+use http::Request;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes swagger-ui showing the given spec to `dir`, for hosting it as static files, e.g. on S3
+/// or GitHub Pages.
+///
+/// This writes every asset, `index.html`, the config and the spec, rendered the same way the
+/// live routes render them, see [`DocService::export`].
+pub fn export_static(
+    dir: impl AsRef<Path>,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> io::Result<()> {
+    SwaggerUiService::new(spec, config).export(dir)
+}
+
+impl<U: DocUi> DocService<U> {
+    /// Writes every document this service responds to into `dir`, creating it if needed.
+    ///
+    /// The documents link each other relatively, so the directory can be hosted under any path.
+    pub fn export(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        // the mount point itself only redirects to `index.html`
+        for path in self.paths().into_iter().filter(|path| !path.is_empty()) {
+            let mut req = Request::get(format!("/{path}"))
+                .body(())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            req.extensions_mut().insert(MountPath(".".to_string()));
+            let response = self.handle(&req);
+            if !response.status().is_success() {
+                continue;
+            }
+
+            let file = dir.join(&path);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(file, response.into_body().into_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::export_static;
+    use crate::Config;
+    use std::fs;
+
+    #[test]
+    fn exports_assets_config_and_spec() {
+        let dir = std::env::temp_dir().join(format!("swagger-ui-export-{}", std::process::id()));
+        export_static(
+            &dir,
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            None,
+        )
+        .unwrap();
+
+        assert!(dir.join("index.html").is_file());
+        assert!(dir.join("swagger-ui-bundle.js").is_file());
+        let config: Config =
+            serde_json::from_slice(&fs::read(dir.join("swagger-ui-config.json")).unwrap()).unwrap();
+        assert_eq!(config.url, "./openapi.json");
+        assert_eq!(
+            fs::read(dir.join("openapi.json")).unwrap(),
+            include_bytes!("../examples/openapi.json")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        let theme = crate::Theme::new().primary_color("#0b5fff");
+        crate::SwaggerUiService::new(crate::swagger_spec_file!("../examples/openapi.json"), None)
+            .with_theme(&theme)
+            .export(&dir)
+            .unwrap();
+        assert!(fs::read_to_string(dir.join("theme.css"))
+            .unwrap()
+            .contains("#0b5fff"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,732 @@
+//! Downloading remote specs, to serve them like local ones.
+
+use crate::{Spec, SpecOrUrl};
+use std::future::Future;
+use std::io;
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Attempts of [`Spec::fetch`] before it gives up.
+const ATTEMPTS: u32 = 3;
+
+/// Delay before the second attempt, doubled before every further one.
+const BACKOFF: Duration = Duration::from_millis(500);
+
+/// Time an attempt of [`Spec::fetch`] waits for the connection to the origin of the spec.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time an attempt of [`Spec::fetch`] may take, downloading the spec included.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Size of the largest spec [`Spec::fetch`] downloads.
+const MAX_SPEC_SIZE: usize = 32 * 1024 * 1024;
+
+/// Delay before refreshing a [`RemoteSpec`] again after its first failed refresh, doubled after
+/// every further one up to the refresh interval.
+const REFRESH_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Delay between checks whether an upstream of an aggregated [`RemoteSpec`] changed, checking is
+/// cheap as the upstreams are refreshed by their own tasks.
+const AGGREGATE_CHECK: Duration = Duration::from_secs(1);
+
+impl Spec {
+    /// Downloads the spec at `url`, named after the last segment of its path.
+    ///
+    /// Attempts that fail to connect or are answered with a server error are retried twice,
+    /// after a short delay. Every attempt gives up after 30 seconds, or 10 without a connection,
+    /// and specs larger than 32 MiB aren't downloaded. Fails with the url in the error if no
+    /// attempt succeeds.
+    pub async fn fetch(url: &str) -> io::Result<Spec> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|err| context(url, err))?;
+        let mut delay = BACKOFF;
+        let mut attempt = 1;
+        let mut response = loop {
+            let error = match client.get(url).send().await {
+                Ok(response) if response.status().is_success() => break response,
+                Ok(response) if !response.status().is_server_error() => {
+                    return Err(context(url, response.status()));
+                }
+                Ok(response) => context(url, response.status()),
+                Err(err) => context(url, err),
+            };
+            if attempt == ATTEMPTS {
+                return Err(error);
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
+        };
+
+        let yaml = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("yaml"));
+        let name = response
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        let too_large = || {
+            context(
+                url,
+                format!("the spec is larger than {MAX_SPEC_SIZE} bytes"),
+            )
+        };
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_SPEC_SIZE as u64)
+        {
+            return Err(too_large());
+        }
+        let mut content = bytes::BytesMut::new();
+        while let Some(chunk) = response.chunk().await.map_err(|err| context(url, err))? {
+            if content.len() + chunk.len() > MAX_SPEC_SIZE {
+                return Err(too_large());
+            }
+            content.extend_from_slice(&chunk);
+        }
+        let content = content.freeze();
+
+        let builder = match name {
+            Some(name) => Spec::builder().name(name),
+            None => Spec::builder(),
+        };
+        Ok(match yaml {
+            true => builder.yaml(content),
+            false => builder.content(content),
+        })
+    }
+}
+
+impl SpecOrUrl {
+    /// Downloads the spec at `url` while the routes are set up, to serve it locally from then
+    /// on, so the docs keep working if the origin of the spec goes down later.
+    ///
+    /// Falls back to the url, for the viewer to fetch, if the spec can't be downloaded, see
+    /// [`Spec::fetch`] to handle the error instead.
+    pub async fn fetch_on_startup(url: impl Into<String>) -> SpecOrUrl {
+        let url = url.into();
+        match Spec::fetch(&url).await {
+            Ok(spec) => SpecOrUrl::Spec(spec),
+            Err(err) => {
+                log::warn!("Could not download the spec, leaving it to the viewer: {err}");
+                SpecOrUrl::Url(url.into())
+            }
+        }
+    }
+}
+
+/// An upstream service whose spec is merged into a hub spec, see [`SpecDiscovery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredSpec {
+    /// The prefix the paths of the service are served under, e.g. `payments`.
+    pub prefix: String,
+    /// The url of the spec of the service.
+    pub url: String,
+}
+
+impl DiscoveredSpec {
+    /// Create a service with the spec at `url`, its paths served under `prefix`.
+    pub fn new(prefix: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// Lists the services whose specs are merged into a hub spec, e.g. from the annotations of
+/// Kubernetes services, a Consul catalog or a database, see [`RemoteSpec::discover`].
+pub trait SpecDiscovery: Send + Sync + 'static {
+    /// Returns the services to show in the docs now.
+    fn list(&self) -> impl Future<Output = io::Result<Vec<DiscoveredSpec>>> + Send;
+}
+
+/// A fixed list of services.
+impl SpecDiscovery for Vec<DiscoveredSpec> {
+    fn list(&self) -> impl Future<Output = io::Result<Vec<DiscoveredSpec>>> + Send {
+        std::future::ready(Ok(self.clone()))
+    }
+}
+
+/// A remote spec downloaded again in the background, served as the last copy that could be
+/// downloaded while it is refreshed, see [`DocService::with_remote_spec`].
+///
+/// [`DocService::with_remote_spec`]: crate::DocService::with_remote_spec
+#[derive(Debug, Clone)]
+pub struct RemoteSpec {
+    shared: Arc<RwLock<Refreshed>>,
+}
+
+#[derive(Debug)]
+struct Refreshed {
+    spec: Spec,
+    /// Counts the refreshes that changed the spec.
+    generation: u64,
+    status: RefreshStatus,
+}
+
+/// How refreshing a [`RemoteSpec`] went, see [`RemoteSpec::status`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshStatus {
+    /// When the spec was last downloaded, whether it changed or not.
+    pub last_refresh: Option<SystemTime>,
+    /// When the last refresh was attempted.
+    pub last_attempt: Option<SystemTime>,
+    /// The refreshes that failed since the last one that didn't.
+    pub failures: u32,
+    /// The error of the last refresh, if it failed.
+    pub last_error: Option<String>,
+}
+
+impl RemoteSpec {
+    /// Downloads the spec at `url`, and again every `interval` in a task spawned on the tokio
+    /// runtime, which stops once the last clone of the `RemoteSpec` is dropped.
+    ///
+    /// Fails if the spec can't be downloaded the first time, see [`Spec::fetch`]. Failed
+    /// refreshes keep the last copy and are retried sooner, after 5 seconds, doubled after every
+    /// further failure up to `interval`. Every delay is lengthened by up to a tenth, so that
+    /// replicas started together don't hit the origin of the spec at once.
+    pub async fn start(url: impl Into<String>, interval: Duration) -> io::Result<Self> {
+        let url = url.into();
+        let now = SystemTime::now();
+        let remote = Self {
+            shared: Arc::new(RwLock::new(Refreshed {
+                spec: Spec::fetch(&url).await?,
+                generation: 0,
+                status: RefreshStatus {
+                    last_refresh: Some(now),
+                    last_attempt: Some(now),
+                    ..Default::default()
+                },
+            })),
+        };
+        tokio::spawn(refresh(Arc::downgrade(&remote.shared), url, interval));
+        Ok(remote)
+    }
+
+    /// Downloads the spec of every upstream service of `upstreams`, by path prefix and url, and
+    /// merges them into one hub spec titled `title`, see [`merge_specs`](crate::merge_specs).
+    ///
+    /// Every upstream spec is refreshed every `interval` like by [`start`](Self::start), and
+    /// the hub spec is merged again once one of them changed. [`status`](Self::status) reports
+    /// the upstream refreshed longest ago and the failures of the first failing one. Fails if a
+    /// spec can't be downloaded the first time or isn't an OpenAPI 3 JSON document.
+    pub async fn aggregate<P: Into<String>, U: Into<String>>(
+        title: impl Into<String>,
+        upstreams: impl IntoIterator<Item = (P, U)>,
+        interval: Duration,
+    ) -> io::Result<Self> {
+        let services: Vec<DiscoveredSpec> = upstreams
+            .into_iter()
+            .map(|(prefix, url)| DiscoveredSpec::new(prefix, url))
+            .collect();
+        let mut upstreams = vec![];
+        for service in &services {
+            let remote = Self::start(service.url.clone(), interval).await?;
+            upstreams.push((service.clone(), remote));
+        }
+        Self::hub(title.into(), upstreams, services, interval)
+    }
+
+    /// Merges the specs of the services `discovery` lists into one hub spec titled `title`,
+    /// like [`aggregate`](Self::aggregate), listing them again every `interval`.
+    ///
+    /// Services listed since are added to the hub spec once their spec could be downloaded, and
+    /// those no longer listed are removed. Failed listings and downloads are logged and retried
+    /// on the next listing, keeping the services of the last one. Fails if the services can't be
+    /// listed the first time or one of their specs isn't an OpenAPI 3 JSON document.
+    pub async fn discover(
+        title: impl Into<String>,
+        discovery: impl SpecDiscovery,
+        interval: Duration,
+    ) -> io::Result<Self> {
+        let services = discovery.list().await?;
+        let mut upstreams = vec![];
+        discover_upstreams(&mut upstreams, services, interval).await;
+        Self::hub(title.into(), upstreams, discovery, interval)
+    }
+
+    /// Merges `upstreams` into a hub spec, refreshed in a task spawned on the tokio runtime.
+    fn hub(
+        title: String,
+        upstreams: Upstreams,
+        discovery: impl SpecDiscovery,
+        interval: Duration,
+    ) -> io::Result<Self> {
+        let (merged, spec) = merge_upstreams(&title, &upstreams)
+            .ok_or_else(|| io::Error::other("only OpenAPI 3 JSON specs can be merged"))?;
+        let remote = Self {
+            shared: Arc::new(RwLock::new(Refreshed {
+                spec,
+                generation: 0,
+                status: upstream_status(&upstreams),
+            })),
+        };
+        let hub = Hub {
+            title,
+            upstreams,
+            merged,
+            interval,
+        };
+        tokio::spawn(hub.refresh(Arc::downgrade(&remote.shared), discovery));
+        Ok(remote)
+    }
+
+    /// Returns the last copy of the spec that could be downloaded.
+    pub fn spec(&self) -> Spec {
+        self.shared.read().unwrap().spec.clone()
+    }
+
+    /// Returns how refreshing the spec went, e.g. for a health check.
+    pub fn status(&self) -> RefreshStatus {
+        self.shared.read().unwrap().status.clone()
+    }
+
+    /// Returns the last copy of the spec and the number of refreshes that changed it.
+    pub(crate) fn latest(&self) -> (u64, Spec) {
+        let refreshed = self.shared.read().unwrap();
+        (refreshed.generation, refreshed.spec.clone())
+    }
+
+    /// Returns the number of refreshes that changed the spec.
+    pub(crate) fn generation(&self) -> u64 {
+        self.shared.read().unwrap().generation
+    }
+}
+
+/// Downloads the spec at `url` into `shared` every `interval`, until it is dropped.
+async fn refresh(shared: Weak<RwLock<Refreshed>>, url: String, interval: Duration) {
+    let mut delay = interval;
+    loop {
+        tokio::time::sleep(jitter(delay)).await;
+        let result = Spec::fetch(&url).await;
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        let mut refreshed = shared.write().unwrap();
+        let now = SystemTime::now();
+        refreshed.status.last_attempt = Some(now);
+        match result {
+            Ok(spec) => {
+                if spec.content != refreshed.spec.content {
+                    refreshed.spec = spec;
+                    refreshed.generation += 1;
+                }
+                refreshed.status.last_refresh = Some(now);
+                refreshed.status.failures = 0;
+                refreshed.status.last_error = None;
+                delay = interval;
+            }
+            Err(err) => {
+                log::warn!("Could not refresh the spec, serving the last copy: {err}");
+                refreshed.status.failures += 1;
+                refreshed.status.last_error = Some(err.to_string());
+                let doublings = refreshed.status.failures - 1;
+                delay =
+                    (REFRESH_BACKOFF.saturating_mul(2_u32.saturating_pow(doublings))).min(interval);
+            }
+        }
+    }
+}
+
+/// The upstream services of a hub spec, with their remote specs.
+type Upstreams = Vec<(DiscoveredSpec, RemoteSpec)>;
+
+/// A hub spec merged from upstream specs, see [`RemoteSpec::aggregate`].
+struct Hub {
+    title: String,
+    upstreams: Upstreams,
+    /// The refreshes of the upstream specs the hub spec was last merged from.
+    merged: Vec<u64>,
+    interval: Duration,
+}
+
+impl Hub {
+    /// Lists the upstream services every interval and merges their specs into `shared` whenever
+    /// they changed, until it is dropped.
+    async fn refresh(mut self, shared: Weak<RwLock<Refreshed>>, discovery: impl SpecDiscovery) {
+        let mut listed = Instant::now();
+        loop {
+            tokio::time::sleep(self.interval.min(AGGREGATE_CHECK)).await;
+            if shared.strong_count() == 0 {
+                return;
+            }
+            let mut changed = false;
+            if listed.elapsed() >= self.interval {
+                listed = Instant::now();
+                match discovery.list().await {
+                    Ok(services) => {
+                        changed =
+                            discover_upstreams(&mut self.upstreams, services, self.interval).await;
+                    }
+                    Err(err) => {
+                        log::warn!("Could not list the specs, keeping the last ones: {err}")
+                    }
+                }
+            }
+            let generations: Vec<u64> = self
+                .upstreams
+                .iter()
+                .map(|(_, remote)| remote.generation())
+                .collect();
+            let merge = match changed || generations != self.merged {
+                true => Some(merge_upstreams(&self.title, &self.upstreams)),
+                false => None,
+            };
+
+            let Some(shared) = shared.upgrade() else {
+                return;
+            };
+            let mut refreshed = shared.write().unwrap();
+            match merge {
+                Some(Some((merged, spec))) => {
+                    refreshed.spec = spec;
+                    refreshed.generation += 1;
+                    self.merged = merged;
+                }
+                Some(None) => {
+                    log::warn!("Could not merge a spec that isn't OpenAPI 3 JSON, serving the last hub spec");
+                    self.merged = generations;
+                }
+                None => {}
+            }
+            refreshed.status = upstream_status(&self.upstreams);
+        }
+    }
+}
+
+/// Makes `upstreams` the services of `services`, downloading the specs of new ones, and returns
+/// whether any was added or removed.
+async fn discover_upstreams(
+    upstreams: &mut Upstreams,
+    services: Vec<DiscoveredSpec>,
+    interval: Duration,
+) -> bool {
+    let listed = upstreams.len();
+    upstreams.retain(|(service, _)| services.contains(service));
+    let mut changed = upstreams.len() != listed;
+    for service in services {
+        if upstreams.iter().any(|(upstream, _)| *upstream == service) {
+            continue;
+        }
+        match RemoteSpec::start(service.url.clone(), interval).await {
+            Ok(remote) => {
+                upstreams.push((service, remote));
+                changed = true;
+            }
+            Err(err) => log::warn!(
+                "Could not download the spec of {}, leaving it out: {err}",
+                service.prefix
+            ),
+        }
+    }
+    changed
+}
+
+/// Merges the last copies of `upstreams`, returning them with the refreshes they are from.
+fn merge_upstreams(title: &str, upstreams: &Upstreams) -> Option<(Vec<u64>, Spec)> {
+    let latest: Vec<(u64, Spec)> = upstreams
+        .iter()
+        .map(|(_, remote)| remote.latest())
+        .collect();
+    let specs = upstreams
+        .iter()
+        .zip(&latest)
+        .map(|((service, _), (_, spec))| (service.prefix.as_str(), spec));
+    let spec = crate::merge_specs(title, specs)?;
+    Some((
+        latest.iter().map(|(generation, _)| *generation).collect(),
+        spec,
+    ))
+}
+
+/// Returns how refreshing `upstreams` went, as one status.
+fn upstream_status(upstreams: &Upstreams) -> RefreshStatus {
+    let statuses: Vec<RefreshStatus> = upstreams
+        .iter()
+        .map(|(_, remote)| remote.status())
+        .collect();
+    let failing = upstreams
+        .iter()
+        .zip(&statuses)
+        .find(|(_, status)| status.failures > 0);
+    RefreshStatus {
+        last_refresh: statuses
+            .iter()
+            .map(|status| status.last_refresh)
+            .min()
+            .flatten(),
+        last_attempt: statuses
+            .iter()
+            .filter_map(|status| status.last_attempt)
+            .max(),
+        failures: failing.map_or(0, |(_, status)| status.failures),
+        last_error: failing.and_then(|((service, _), status)| {
+            let error = status.last_error.as_ref()?;
+            Some(format!("{}: {error}", service.prefix))
+        }),
+    }
+}
+
+/// Returns `delay` lengthened by up to a tenth, varying between calls.
+fn jitter(delay: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    delay + delay / 10 * nanos / 1_000_000_000
+}
+
+fn context(url: &str, err: impl std::fmt::Display) -> io::Error {
+    io::Error::other(format!("{url}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiscoveredSpec, RemoteSpec, SpecDiscovery};
+    use crate::{Spec, SpecOrUrl, SwaggerUiService};
+    use http::Request;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn retries_and_falls_back_to_the_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let origin = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "200 OK", "404 Not Found"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 8192];
+                let _ = stream.read(&mut request).await.unwrap();
+                let body = r#"{"openapi":"3.0.0"}"#;
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let spec = Spec::fetch(&format!("{origin}/specs/openapi.json"))
+            .await
+            .unwrap();
+        assert_eq!(spec.name, "openapi.json");
+        assert_eq!(spec.content, r#"{"openapi":"3.0.0"}"#);
+
+        let missing = format!("{origin}/missing.json");
+        let spec = SpecOrUrl::fetch_on_startup(missing.clone()).await;
+        assert!(matches!(spec, SpecOrUrl::Url(url) if url == missing));
+    }
+
+    #[tokio::test]
+    async fn refuses_specs_larger_than_the_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/openapi.json", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let _ = stream.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                super::MAX_SPEC_SIZE + 1
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let err = Spec::fetch(&url).await.unwrap_err();
+        assert!(err.to_string().contains("larger than"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn serves_the_last_copy_while_refreshing() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/openapi.json", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for version in 1.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 8192];
+                let _ = stream.read(&mut request).await.unwrap();
+                let body = format!(
+                    r#"{{"openapi":"3.0.0","info":{{"version":"{}"}}}}"#,
+                    version.min(2)
+                );
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let remote = RemoteSpec::start(url, Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(remote.spec().version().unwrap(), "1");
+        let service = SwaggerUiService::new(remote.spec(), None).with_remote_spec(remote.clone());
+        let version = || {
+            let response = service.handle(&Request::get("/openapi.json").body(()).unwrap());
+            let body = response.into_body().into_bytes();
+            let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            spec["info"]["version"].as_str().unwrap().to_string()
+        };
+        assert_eq!(version(), "1");
+
+        while remote.spec().version().unwrap() == "1" {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(version(), "2");
+        let status = remote.status();
+        assert!(status.last_refresh.is_some());
+        assert_eq!(status.failures, 0);
+    }
+
+    #[tokio::test]
+    async fn asks_about_the_flags_later_copies_name() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/openapi.json", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for copy in 1.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 8192];
+                let _ = stream.read(&mut request).await.unwrap();
+                let body = match copy {
+                    1 => r#"{"paths":{"/pets":{"get":{}}}}"#,
+                    _ => r#"{"paths":{"/pets":{"get":{},"post":{"x-feature-flag":"adoption"}}}}"#,
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let remote = RemoteSpec::start(url, Duration::from_millis(20))
+            .await
+            .unwrap();
+        let service = SwaggerUiService::new(remote.spec(), None)
+            .with_remote_spec(remote.clone())
+            .with_feature_flags(|_: &str| false);
+        while remote.generation() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let response = service.handle(&Request::get("/openapi.json").body(()).unwrap());
+        let body = response.into_body().into_bytes();
+        let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(spec["paths"]["/pets"]["get"].is_object());
+        assert!(spec["paths"]["/pets"].get("post").is_none());
+    }
+
+    #[tokio::test]
+    async fn merges_upstream_specs_again_once_they_change() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let origin = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for request in 1.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 8192];
+                let read = stream.read(&mut buffer).await.unwrap();
+                let request_line = String::from_utf8_lossy(&buffer[..read]);
+                let service = match request_line.starts_with("GET /users") {
+                    true => "users".to_string(),
+                    false => format!("orders{}", if request > 4 { "-v2" } else { "" }),
+                };
+                let body = format!(r#"{{"openapi":"3.0.0","paths":{{"/{service}":{{}}}}}}"#);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let remote = RemoteSpec::aggregate(
+            "Platform",
+            [
+                ("users", format!("{origin}/users.json")),
+                ("orders", format!("{origin}/orders.json")),
+            ],
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+        let paths = || {
+            let spec: serde_json::Value = serde_json::from_slice(&remote.spec().content).unwrap();
+            spec["paths"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(), ["/orders/orders", "/users/users"]);
+
+        while !paths().contains(&"/orders/orders-v2".to_string()) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(remote.status().failures, 0);
+    }
+
+    #[tokio::test]
+    async fn adds_and_removes_the_services_discovery_lists() {
+        struct Registry(Arc<Mutex<Vec<DiscoveredSpec>>>);
+
+        impl SpecDiscovery for Registry {
+            async fn list(&self) -> std::io::Result<Vec<DiscoveredSpec>> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let origin = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 8192];
+                let read = stream.read(&mut buffer).await.unwrap();
+                let request_line = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let service = request_line.split(['/', '.']).nth(1).unwrap().to_string();
+                let body = format!(r#"{{"openapi":"3.0.0","paths":{{"/{service}":{{}}}}}}"#);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let service = |name: &str| DiscoveredSpec::new(name, format!("{origin}/{name}.json"));
+        let services = Arc::new(Mutex::new(vec![service("users")]));
+        let remote = RemoteSpec::discover(
+            "Platform",
+            Registry(services.clone()),
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+        let paths = || {
+            let spec: serde_json::Value = serde_json::from_slice(&remote.spec().content).unwrap();
+            spec["paths"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(), ["/users/users"]);
+
+        *services.lock().unwrap() = vec![service("orders")];
+        while paths() != ["/orders/orders"] {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
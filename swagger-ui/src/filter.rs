@@ -0,0 +1,379 @@
+//! Filtering a spec down to the operations a user may see.
+
+use crate::markdown::METHODS;
+use crate::Spec;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Returns `spec` with only the operations `allowed` grants, or `None` if the spec isn't JSON.
+///
+/// An operation is kept if one of its tags is allowed, or if all the scopes of one of its
+/// security requirements are. The operations of OpenAPI 3.1 `webhooks` are filtered like those of
+/// `paths`. Path items without operations, declared tags without operations and components no
+/// longer referenced are removed as well, so the filtered spec doesn't mention what was left out.
+pub fn filter_spec(spec: &Spec, allowed: &BTreeSet<String>) -> Option<Spec> {
+    filter(spec, |operation, global_security| {
+        is_allowed(operation, global_security, allowed)
+    })
+}
+
+/// Returns the slice of `spec` with the operations tagged `tag`, and the components they refer
+/// to, or `None` if the spec isn't JSON.
+///
+/// Everything else is removed like by [`filter_spec`].
+pub fn tag_spec(spec: &Spec, tag: &str) -> Option<Spec> {
+    filter(spec, |operation, _| tags(operation).any(|t| t == tag))
+}
+
+/// Decides which features are released, e.g. backed by the flag service the API itself asks.
+pub trait FlagProvider: Send + Sync {
+    /// Returns whether the feature `flag` is enabled.
+    fn is_enabled(&self, flag: &str) -> bool;
+}
+
+impl<F: Fn(&str) -> bool + Send + Sync> FlagProvider for F {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self(flag)
+    }
+}
+
+/// Returns `spec` without the operations behind a disabled feature flag, or `None` if the spec
+/// isn't JSON.
+///
+/// Operations name their flag with the `x-feature-flag` extension, e.g.
+/// `"x-feature-flag": "new-checkout"`, or a list of flags that all need to be enabled.
+/// Operations without it are always kept. Everything the left out operations leave unused is
+/// removed like by [`filter_spec`].
+pub fn flag_spec(spec: &Spec, flags: &dyn FlagProvider) -> Option<Spec> {
+    filter(spec, |operation, _| {
+        operation_flags(operation).all(|flag| flags.is_enabled(flag))
+    })
+}
+
+/// Returns the feature flags the operations of `spec` name, see [`flag_spec`].
+pub(crate) fn flag_names(spec: &Spec) -> BTreeSet<String> {
+    let Ok(document) = serde_json::from_slice::<Value>(&spec.content) else {
+        return BTreeSet::new();
+    };
+    let mut names = BTreeSet::new();
+    for section in ["paths", "webhooks"] {
+        let items = document[section].as_object().into_iter().flatten();
+        for item in items.map(|(_, item)| item) {
+            for operation in METHODS.iter().filter_map(|method| item.get(method)) {
+                names.extend(operation_flags(operation).map(str::to_string));
+            }
+        }
+    }
+    names
+}
+
+/// Returns the names of the tags of spec in the order they are declared, followed by those only
+/// used by operations.
+pub(crate) fn tag_names(spec: &Spec) -> Vec<String> {
+    let Ok(document) = serde_json::from_slice::<Value>(&spec.content) else {
+        return vec![];
+    };
+    let declared = document["tags"].as_array().into_iter().flatten();
+    let mut names: Vec<String> = declared
+        .filter_map(|tag| tag["name"].as_str())
+        .map(str::to_string)
+        .collect();
+    for section in ["paths", "webhooks"] {
+        let items = document[section].as_object().into_iter().flatten();
+        for item in items.map(|(_, item)| item) {
+            for operation in METHODS.iter().filter_map(|method| item.get(method)) {
+                for tag in tags(operation) {
+                    if !names.iter().any(|name| name == tag) {
+                        names.push(tag.to_string());
+                    }
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Returns `spec` with only the operations `keep` accepts, given the global security.
+fn filter(spec: &Spec, keep: impl Fn(&Value, &Value) -> bool) -> Option<Spec> {
+    let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+    let global_security = document["security"].clone();
+
+    let mut kept = BTreeSet::new();
+    // OpenAPI 3.1 webhooks are path items as well, filtered the same way
+    for section in ["paths", "webhooks"] {
+        let Some(items) = document[section].as_object_mut() else {
+            continue;
+        };
+        for item in items.values_mut() {
+            let Some(item) = item.as_object_mut() else {
+                continue;
+            };
+            for method in METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                if keep(operation, &global_security) {
+                    kept.extend(tags(operation).map(str::to_string));
+                } else {
+                    item.remove(method);
+                }
+            }
+        }
+        items.retain(|_, item| METHODS.iter().any(|method| item.get(method).is_some()));
+    }
+    if let Some(declared) = document["tags"].as_array_mut() {
+        declared.retain(|tag| tag["name"].as_str().is_some_and(|name| kept.contains(name)));
+    }
+    remove_unreferenced_components(&mut document);
+
+    let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+fn tags(operation: &Value) -> impl Iterator<Item = &str> {
+    operation["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+}
+
+fn operation_flags(operation: &Value) -> impl Iterator<Item = &str> {
+    let flags = &operation["x-feature-flag"];
+    flags.as_str().into_iter().chain(
+        flags
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str),
+    )
+}
+
+fn is_allowed(operation: &Value, global_security: &Value, allowed: &BTreeSet<String>) -> bool {
+    let tagged = tags(operation).any(|tag| allowed.contains(tag));
+    let security = operation.get("security").unwrap_or(global_security);
+    let scoped = security
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+        .any(|requirement| {
+            let mut scopes = requirement
+                .values()
+                .filter_map(Value::as_array)
+                .flatten()
+                .peekable();
+            scopes.peek().is_some()
+                && scopes.all(|scope| scope.as_str().is_some_and(|scope| allowed.contains(scope)))
+        });
+    tagged || scoped
+}
+
+/// Removes the `components`, or Swagger 2.0 `definitions`, `parameters` and `responses`, nothing
+/// refers to anymore. Security schemes are referred to by name and always kept.
+fn remove_unreferenced_components(document: &mut Value) {
+    let sections: Vec<String> = match document["components"].as_object() {
+        Some(components) => components
+            .keys()
+            .filter(|kind| *kind != "securitySchemes")
+            .map(|kind| format!("/components/{kind}"))
+            .collect(),
+        None => ["/definitions", "/parameters", "/responses"]
+            .map(str::to_string)
+            .to_vec(),
+    };
+
+    // the references outside of the components, then the ones of the components they reach
+    let mut outside = document.clone();
+    for section in &sections {
+        if let Some(components) = outside.pointer_mut(section).and_then(Value::as_object_mut) {
+            components.clear();
+        }
+    }
+    let mut pending = Vec::new();
+    references(&outside, &mut pending);
+    let mut referenced = BTreeSet::new();
+    while let Some(reference) = pending.pop() {
+        if let Some(target) = document.pointer(&reference) {
+            if referenced.insert(reference) {
+                references(target, &mut pending);
+            }
+        }
+    }
+
+    for section in &sections {
+        if let Some(components) = document.pointer_mut(section).and_then(Value::as_object_mut) {
+            components.retain(|name, _| {
+                let name = name.replace('~', "~0").replace('/', "~1");
+                referenced.contains(&format!("{section}/{name}"))
+            });
+        }
+    }
+}
+
+/// Collects the JSON pointers of the local `$ref`s in `value`, e.g. `/components/schemas/Pet`.
+fn references(value: &Value, pointers: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(pointer) = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+            {
+                pointers.push(pointer.to_string());
+            }
+            for value in object.values() {
+                references(value, pointers);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                references(value, pointers);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_spec, flag_names, flag_spec, tag_names, tag_spec};
+    use crate::Spec;
+    use bytes::Bytes;
+    use serde_json::{json, Value};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn keeps_only_allowed_operations() {
+        let spec = Spec::builder().json(Bytes::from(
+                serde_json::to_vec(&json!({
+                    "tags": [{"name": "pets"}, {"name": "admin"}, {"name": "orders"}],
+                    "paths": {
+                        "/pets": {"get": {"tags": ["pets"], "responses": {"200": {
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        }}}},
+                        "/users": {
+                            "get": {"tags": ["admin"], "security": [{"oauth": ["users:read"]}]},
+                            "delete": {"tags": ["admin"], "responses": {"204": {
+                                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}}
+                            }}}
+                        }
+                    },
+                    "components": {"schemas": {
+                        "Pet": {"properties": {"owner": {"$ref": "#/components/schemas/Owner"}}},
+                        "Owner": {},
+                        "User": {}
+                    }}
+                }))
+                .unwrap(),
+            ));
+        let allowed = BTreeSet::from(["pets".to_string(), "users:read".to_string()]);
+        let filtered = filter_spec(&spec, &allowed).unwrap();
+        let document: Value = serde_json::from_slice(&filtered.content).unwrap();
+
+        assert!(document["paths"]["/pets"]["get"].is_object());
+        assert!(document["paths"]["/users"]["get"].is_object());
+        assert!(document["paths"]["/users"].get("delete").is_none());
+        // granted by its scope, the operation keeps its tag
+        assert_eq!(
+            document["tags"],
+            json!([{"name": "pets"}, {"name": "admin"}])
+        );
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        assert!(schemas.contains_key("Pet") && schemas.contains_key("Owner"));
+        assert!(!schemas.contains_key("User"));
+    }
+
+    #[test]
+    fn filters_webhooks_like_paths() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "openapi": "3.1.0",
+                "tags": [{"name": "events"}, {"name": "admin"}],
+                "webhooks": {
+                    "newPet": {"post": {"tags": ["events"], "requestBody": {
+                        "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                    }}},
+                    "userDeleted": {"post": {"tags": ["admin"]}}
+                },
+                "components": {"schemas": {"Pet": {}}}
+            }))
+            .unwrap(),
+        );
+        let allowed = BTreeSet::from(["events".to_string()]);
+        let filtered = filter_spec(&spec, &allowed).unwrap();
+        let document: Value = serde_json::from_slice(&filtered.content).unwrap();
+
+        assert!(document["webhooks"]["newPet"]["post"].is_object());
+        assert!(document["webhooks"].get("userDeleted").is_none());
+        assert_eq!(document["tags"], json!([{"name": "events"}]));
+        assert!(document["components"]["schemas"]["Pet"].is_object());
+    }
+
+    #[test]
+    fn slices_the_spec_by_tag() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "tags": [{"name": "pets"}, {"name": "users"}],
+                "paths": {
+                    "/pets": {"get": {"tags": ["pets"], "parameters": [{"$ref": "#/components/parameters/Limit"}]}},
+                    "/users": {"get": {"tags": ["users", "admin"], "security": [{"oauth": ["pets"]}]}}
+                },
+                "components": {
+                    "parameters": {"Limit": {"schema": {"$ref": "#/components/schemas/Count"}}, "Page": {}},
+                    "schemas": {"Count": {}, "User": {}},
+                    "securitySchemes": {"oauth": {}}
+                }
+            }))
+            .unwrap(),
+        );
+        assert_eq!(tag_names(&spec), ["pets", "users", "admin"]);
+
+        let pets = tag_spec(&spec, "pets").unwrap();
+        let document: Value = serde_json::from_slice(&pets.content).unwrap();
+        // the scope named like the tag doesn't count
+        assert!(document["paths"].get("/users").is_none());
+        assert_eq!(document["tags"], json!([{"name": "pets"}]));
+        assert_eq!(
+            document["components"],
+            json!({
+                "parameters": {"Limit": {"schema": {"$ref": "#/components/schemas/Count"}}},
+                "schemas": {"Count": {}},
+                "securitySchemes": {"oauth": {}}
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_out_operations_behind_disabled_flags() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "paths": {
+                    "/pets": {
+                        "get": {},
+                        "post": {"x-feature-flag": "adoption", "responses": {"201": {
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Adoption"}}}
+                        }}}
+                    },
+                    "/checkout": {"post": {"x-feature-flag": ["adoption", "checkout"]}}
+                },
+                "components": {"schemas": {"Adoption": {}}}
+            }))
+            .unwrap(),
+        );
+        assert_eq!(
+            flag_names(&spec),
+            BTreeSet::from(["adoption".to_string(), "checkout".to_string()])
+        );
+
+        let flagged = flag_spec(&spec, &|flag: &str| flag == "adoption").unwrap();
+        let document: Value = serde_json::from_slice(&flagged.content).unwrap();
+        assert!(document["paths"]["/pets"]["post"].is_object());
+        assert!(document["paths"].get("/checkout").is_none());
+
+        let flagged = flag_spec(&spec, &|_: &str| false).unwrap();
+        let document: Value = serde_json::from_slice(&flagged.content).unwrap();
+        assert_eq!(document["paths"], json!({"/pets": {"get": {}}}));
+        assert!(document["components"]["schemas"].get("Adoption").is_none());
+    }
+}
@@ -0,0 +1,171 @@
+//! Building blocks of [`SwaggerUiService`](crate::SwaggerUiService), for composing routes by
+//! hand when the provided ones don't fit.
+
+use crate::{mime, Body, Config, Spec};
+use bytes::Bytes;
+use http::header::{ALLOW, CONTENT_LENGTH, CONTENT_TYPE, LOCATION};
+use http::{HeaderValue, Response, StatusCode};
+use http_body::Body as _;
+
+/// Size of the chunks a spec is streamed in.
+const SPEC_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Serves the embedded swagger-ui asset at `path`, e.g. `index.html`, or `404 Not Found` if
+/// there is none.
+pub fn serve_asset(path: &str) -> Response<Body> {
+    let path = path.trim_start_matches('/');
+    match swagger_ui_assets::get(path) {
+        Some(asset) => content(
+            HeaderValue::from_static(asset.content_type),
+            Bytes::from_static(asset.content).into(),
+        ),
+        None => status(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Serves the content of `spec` with its content type.
+pub fn serve_spec(spec: &Spec) -> Response<Body> {
+    content(
+        HeaderValue::from_str(&spec.content_type).unwrap_or(mime::JSON),
+        Body::chunked(spec.content.clone(), SPEC_CHUNK_SIZE),
+    )
+}
+
+/// Serves `config` for swagger-ui mounted at `base`, e.g. `/docs`.
+///
+/// Relative spec urls in `config` are resolved against `base`, so `openapi.json` becomes
+/// `/docs/openapi.json`.
+pub fn serve_config(config: &Config, base: &str) -> Response<Body> {
+    let mut config = config.clone();
+    config.url = resolve(base, &config.url);
+    for url in &mut config.urls {
+        url.url = resolve(base, &url.url);
+    }
+    let json = serde_json::to_vec(&config).expect("Could not serialize config as JSON.");
+    content(mime::JSON, Bytes::from(json).into())
+}
+
+/// Returns `url` relative to a swagger-ui mounted at `base`, unless it is absolute.
+///
+/// The `.` and `..` segments of `url` are applied to `base` if it is a path, so
+/// `../openapi.json` of a ui mounted at `/api/docs` becomes `/api/openapi.json`, and `..`
+/// above the root stays at the root.
+pub(crate) fn resolve(base: &str, url: &str) -> String {
+    if url.is_empty() || url.starts_with('/') || url.contains("://") {
+        return url.to_string();
+    }
+    if !base.is_empty() && !base.starts_with('/') {
+        // e.g. `.` of static exports, left for the browser to resolve
+        return format!("{}/{url}", base.trim_end_matches('/'));
+    }
+
+    let (path, suffix) = url.split_at(url.find(['?', '#']).unwrap_or(url.len()));
+    let mut segments: Vec<&str> = base.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    // keeps the trailing slash of paths like `specs/` or `..`
+    if path.ends_with("/.") || path.ends_with("/..") || matches!(path, "." | "..") {
+        segments.push("");
+    }
+    format!("/{}{suffix}", segments.join("/"))
+}
+
+pub(crate) fn content(content_type: HeaderValue, body: Body) -> Response<Body> {
+    let mut response = Response::new(body);
+    response.headers_mut().insert(CONTENT_TYPE, content_type);
+    response
+}
+
+pub(crate) fn redirect(location: &str) -> Response<Body> {
+    let mut response = status(StatusCode::PERMANENT_REDIRECT);
+    response.headers_mut().insert(
+        LOCATION,
+        HeaderValue::from_str(location).expect("redirect location is a valid header value"),
+    );
+    response
+}
+
+pub(crate) fn status(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+/// Turns the response to a `GET` into the one to a `HEAD`, keeping its `Content-Length`.
+pub(crate) fn head(mut response: Response<Body>) -> Response<Body> {
+    let length = response.body().size_hint().exact().unwrap_or_default();
+    response.headers_mut().insert(CONTENT_LENGTH, length.into());
+    *response.body_mut() = Body::empty();
+    response
+}
+
+pub(crate) fn method_not_allowed() -> Response<Body> {
+    let mut response = status(StatusCode::METHOD_NOT_ALLOWED);
+    response
+        .headers_mut()
+        .insert(ALLOW, HeaderValue::from_static("GET, HEAD"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serve_asset, serve_config, serve_spec};
+    use crate::{Config, UrlObject};
+    use http::header::CONTENT_TYPE;
+    use http::StatusCode;
+
+    #[test]
+    fn serves_assets_by_path() {
+        let response = serve_asset("/swagger-ui.css");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/css");
+
+        assert_eq!(serve_asset("missing.css").status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn serves_spec_content() {
+        let spec = crate::swagger_spec_file!("../examples/openapi.json");
+        let response = serve_spec(&spec);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(response.into_body().into_bytes(), spec.content);
+    }
+
+    #[test]
+    fn resolves_relative_config_urls() {
+        let config = Config {
+            url: "openapi.json".to_string(),
+            urls: vec![
+                UrlObject::new("v1", "v1.json"),
+                UrlObject::new("v2", "https://example.com/v2.json"),
+            ],
+            ..Default::default()
+        };
+        let response = serve_config(&config, "/docs/");
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+        assert_eq!(config.urls[0].url, "/docs/v1.json");
+        assert_eq!(config.urls[1].url, "https://example.com/v2.json");
+    }
+
+    #[test]
+    fn resolves_dot_segments_against_the_mount_path() {
+        use super::resolve;
+
+        assert_eq!(resolve("/api/docs", "../openapi.json"), "/api/openapi.json");
+        assert_eq!(
+            resolve("/docs", "./v1/../v2.json?raw#top"),
+            "/docs/v2.json?raw#top"
+        );
+        assert_eq!(resolve("/docs", "../../openapi.json"), "/openapi.json");
+        assert_eq!(resolve("", "specs/.."), "/");
+        assert_eq!(resolve(".", "../openapi.json"), "./../openapi.json");
+    }
+}
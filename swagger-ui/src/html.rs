@@ -0,0 +1,133 @@
+//! Escaping of values filled into the generated pages.
+
+#[cfg(any(feature = "elements", feature = "rapidoc"))]
+use serde::Serialize;
+
+/// Escapes `value` for a double-quoted HTML attribute or text.
+pub(crate) fn attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Adds `script` to `index` right before its `</body>`.
+pub(crate) fn inject_script(index: &[u8], script: &str) -> bytes::Bytes {
+    let index = String::from_utf8_lossy(index);
+    let at = index.rfind("</body>").unwrap_or(index.len());
+    bytes::Bytes::from(format!("{}{script}{}", &index[..at], &index[at..]))
+}
+
+/// Replaces the stylesheets and scripts `index` loads from next to it, e.g. `./index.css`, with
+/// their content from `asset` if they are at most `limit` bytes long.
+///
+/// Files that would close the element they are inlined into are left alone.
+pub(crate) fn inline_assets(
+    index: &[u8],
+    asset: impl Fn(&str) -> Option<std::borrow::Cow<'static, [u8]>>,
+    limit: usize,
+) -> bytes::Bytes {
+    let index = String::from_utf8_lossy(index);
+    let inlined = |tag: &str, attribute: &str, element: &str| {
+        let path = attribute_value(tag, attribute)?.strip_prefix("./")?;
+        let content = asset(path).filter(|content| content.len() <= limit)?;
+        let content = std::str::from_utf8(&content).ok()?;
+        let closing = format!("</{element}");
+        if content.to_ascii_lowercase().contains(&closing) {
+            return None;
+        }
+        Some(format!("<{element}>\n{content}\n</{element}>"))
+    };
+
+    let mut out = String::with_capacity(index.len());
+    let mut rest = index.as_ref();
+    while let Some(start) = ["<link ", "<script "]
+        .iter()
+        .filter_map(|tag| rest.find(tag))
+        .min()
+    {
+        let Some(end) = rest[start..].find('>').map(|end| start + end + 1) else {
+            break;
+        };
+        let tag = &rest[start..end];
+        out.push_str(&rest[..start]);
+        rest = &rest[end..];
+        let replacement = match tag.starts_with("<link ") {
+            true if attribute_value(tag, "rel") == Some("stylesheet") => {
+                inlined(tag, "href", "style")
+            }
+            true => None,
+            false => {
+                let after = rest.trim_start();
+                match after.strip_prefix("</script>") {
+                    Some(after) => inlined(tag, "src", "script").inspect(|_| rest = after),
+                    None => None,
+                }
+            }
+        };
+        out.push_str(replacement.as_deref().unwrap_or(tag));
+    }
+    out.push_str(rest);
+    bytes::Bytes::from(out)
+}
+
+/// Returns the double-quoted value of `attribute` in `tag`, e.g. `./index.css` for `href`.
+fn attribute_value<'t>(tag: &'t str, attribute: &str) -> Option<&'t str> {
+    let start = tag.find(&format!(" {attribute}=\""))? + attribute.len() + 3;
+    let end = tag[start..].find('"')? + start;
+    Some(&tag[start..end])
+}
+
+/// Keeps JSON inlined into a `<script>` from closing it.
+#[cfg(any(feature = "redoc", feature = "swagger-editor"))]
+pub(crate) fn script(json: &str) -> String {
+    json.replace("</", "<\\/")
+}
+
+/// Renders the fields of `config` as attributes of an element, each with a leading space.
+#[cfg(any(feature = "elements", feature = "rapidoc"))]
+pub(crate) fn attributes(config: &impl Serialize) -> String {
+    let value = serde_json::to_value(config).expect("Could not serialize config as JSON.");
+    let serde_json::Value::Object(attributes) = value else {
+        unreachable!("configs are structs")
+    };
+    attributes
+        .iter()
+        .map(|(name, value)| match value {
+            serde_json::Value::String(value) => format!(r#" {name}="{}""#, attribute(value)),
+            value => format!(r#" {name}="{}""#, attribute(&value.to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inline_assets;
+    use std::borrow::Cow;
+
+    #[test]
+    fn inlines_the_small_stylesheets_and_scripts() {
+        let index = br#"<link rel="stylesheet" href="./big.css" /><link rel="stylesheet" href="./index.css" /><link rel="icon" href="./favicon.png" />
+<script src="./bundle.js"> </script><script src="./init.js" charset="UTF-8"> </script><script src="./closing.js"></script>"#;
+        let asset = |path: &str| -> Option<Cow<'static, [u8]>> {
+            match path {
+                "big.css" => Some(Cow::Owned(vec![b' '; 100])),
+                "index.css" => Some(Cow::Borrowed(b"body { margin: 0; }")),
+                "init.js" => Some(Cow::Borrowed(b"SwaggerUIBundle({});")),
+                "closing.js" => Some(Cow::Borrowed(b"'</SCRIPT>'")),
+                _ => None,
+            }
+        };
+        let inlined = inline_assets(index, asset, 50);
+        assert_eq!(
+            inlined,
+            r#"<link rel="stylesheet" href="./big.css" /><style>
+body { margin: 0; }
+</style><link rel="icon" href="./favicon.png" />
+<script src="./bundle.js"> </script><script>
+SwaggerUIBundle({});
+</script><script src="./closing.js"></script>"#
+        );
+    }
+}
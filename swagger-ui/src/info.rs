@@ -0,0 +1,163 @@
+//! Metadata of a spec that depends on where it is served, injected into its `info`.
+
+use crate::Spec;
+use serde_json::{Map, Value};
+
+/// The environment variables [`SpecInfo::from_env`] reads, by the field of `info` they set.
+const VARS: [(&str, &str); 4] = [
+    ("SWAGGER_UI_INFO_VERSION", "version"),
+    ("SWAGGER_UI_CONTACT_NAME", "name"),
+    ("SWAGGER_UI_CONTACT_EMAIL", "email"),
+    ("SWAGGER_UI_CONTACT_URL", "url"),
+];
+
+/// Fields of `info` and `externalDocs` set on the served spec, so the committed spec doesn't
+/// need edits per environment, see [`DocService::with_info`](crate::DocService::with_info).
+///
+/// ```ignore
+/// let info = SpecInfo::from_env()
+///     .version(env!("CARGO_PKG_VERSION"))
+///     .license("MIT", "https://opensource.org/licenses/MIT");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecInfo {
+    version: Option<String>,
+    contact: Map<String, Value>,
+    license: Option<Value>,
+    external_docs: Option<Value>,
+}
+
+impl SpecInfo {
+    /// Create metadata from the environment: `SWAGGER_UI_INFO_VERSION` sets the version and
+    /// `SWAGGER_UI_CONTACT_NAME`, `SWAGGER_UI_CONTACT_EMAIL` and `SWAGGER_UI_CONTACT_URL` the
+    /// contact. Variables that aren't set or are empty are left out.
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let mut info = Self::default();
+        for (name, field) in VARS {
+            let Some(value) = var(name).filter(|value| !value.trim().is_empty()) else {
+                continue;
+            };
+            match field {
+                "version" => info.version = Some(value),
+                field => {
+                    info.contact.insert(field.to_string(), value.into());
+                }
+            }
+        }
+        info
+    }
+
+    /// Set `info.version`, e.g. to `env!("CARGO_PKG_VERSION")`.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the name of `info.contact`.
+    pub fn contact_name(mut self, name: impl Into<String>) -> Self {
+        self.contact.insert("name".into(), name.into().into());
+        self
+    }
+
+    /// Set the email address of `info.contact`.
+    pub fn contact_email(mut self, email: impl Into<String>) -> Self {
+        self.contact.insert("email".into(), email.into().into());
+        self
+    }
+
+    /// Set the url of `info.contact`.
+    pub fn contact_url(mut self, url: impl Into<String>) -> Self {
+        self.contact.insert("url".into(), url.into().into());
+        self
+    }
+
+    /// Replace `info.license` with the license `name`, with the text at `url`.
+    pub fn license(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.license = Some(serde_json::json!({"name": name.into(), "url": url.into()}));
+        self
+    }
+
+    /// Replace `externalDocs` with a link to `url`, described by `description`.
+    pub fn external_docs(mut self, url: impl Into<String>, description: impl Into<String>) -> Self {
+        let docs = serde_json::json!({"url": url.into(), "description": description.into()});
+        self.external_docs = Some(docs);
+        self
+    }
+
+    /// Returns `spec` with the metadata set, or `None` if the spec isn't a JSON object.
+    ///
+    /// The fields of the contact are set one by one, keeping the others the spec has, while the
+    /// license and external docs are replaced as a whole.
+    pub fn apply(&self, spec: &Spec) -> Option<Spec> {
+        let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+        let document_object = document.as_object_mut()?;
+        if let Some(docs) = &self.external_docs {
+            document_object.insert("externalDocs".into(), docs.clone());
+        }
+        let info = document_object
+            .entry("info")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()?;
+        if let Some(version) = &self.version {
+            info.insert("version".into(), version.clone().into());
+        }
+        if !self.contact.is_empty() {
+            let contact = info
+                .entry("contact")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !contact.is_object() {
+                *contact = Value::Object(Map::new());
+            }
+            let contact = contact.as_object_mut().expect("contact is an object");
+            contact.extend(self.contact.clone());
+        }
+        if let Some(license) = &self.license {
+            info.insert("license".into(), license.clone());
+        }
+
+        let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+        Some(Spec::builder().name(spec.name.clone()).json(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpecInfo;
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn injects_info_into_the_spec() {
+        let spec = Spec::builder().json(
+            r#"{"openapi": "3.0.3", "info": {"title": "Pets", "version": "0.0.0",
+                "contact": {"name": "Pets team", "email": "old@example.com"}}}"#,
+        );
+        let vars = |name: &str| match name {
+            "SWAGGER_UI_CONTACT_EMAIL" => Some("pets@example.com".to_string()),
+            "SWAGGER_UI_CONTACT_URL" => Some(" ".to_string()),
+            _ => None,
+        };
+        let info = SpecInfo::from_vars(vars)
+            .version("1.2.3")
+            .license("MIT", "https://opensource.org/licenses/MIT")
+            .external_docs("https://docs.example.com", "Guides");
+        let document: Value = serde_json::from_slice(&info.apply(&spec).unwrap().content).unwrap();
+
+        assert_eq!(document["info"]["title"], "Pets");
+        assert_eq!(document["info"]["version"], "1.2.3");
+        assert_eq!(
+            document["info"]["contact"],
+            json!({"name": "Pets team", "email": "pets@example.com"})
+        );
+        assert_eq!(document["info"]["license"]["name"], "MIT");
+        assert_eq!(document["externalDocs"]["url"], "https://docs.example.com");
+
+        assert!(info
+            .apply(&Spec::builder().yaml("openapi: 3.0.3"))
+            .is_none());
+    }
+}
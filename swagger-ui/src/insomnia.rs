@@ -0,0 +1,189 @@
+//! Conversion of a spec into an Insomnia export.
+
+use crate::collection::{substitute, Auth, Collection, Parameter, RequestTemplate};
+use crate::Spec;
+use serde_json::{json, Map, Value};
+
+/// Id of the workspace of the export, the parent of every other resource.
+const WORKSPACE_ID: &str = "wrk_swagger_ui";
+
+/// Converts `spec` into an Insomnia v4 export with one workspace, or returns `None` if the spec
+/// isn't JSON.
+///
+/// Requests are grouped into a folder per tag and sent to `{{ _.base_url }}`, a variable of the
+/// base environment holding the url of the first server, like the
+/// [`postman_collection`](crate::postman_collection) is. Server and path parameters become
+/// environment variables too, as do the credentials of the security schemes.
+pub fn insomnia_export(spec: &Spec) -> Option<String> {
+    let collection = Collection::new(spec)?;
+    let variable = |name: &str| format!("{{{{ _.{name} }}}}");
+
+    let mut workspace = Map::new();
+    workspace.insert("_id".into(), json!(WORKSPACE_ID));
+    workspace.insert("_type".into(), json!("workspace"));
+    workspace.insert("parentId".into(), Value::Null);
+    workspace.insert("name".into(), json!(collection.name));
+    if let Some(description) = &collection.description {
+        workspace.insert("description".into(), json!(description));
+    }
+
+    let mut data = Map::new();
+    data.insert(
+        "base_url".into(),
+        json!(substitute(&collection.base_url, variable)),
+    );
+    for parameter in &collection.variables {
+        data.insert(parameter.name.clone(), json!(parameter.value));
+    }
+    let auths = collection.auth.iter().chain(
+        collection
+            .requests()
+            .filter_map(|request| request.auth.as_ref()),
+    );
+    for key in auths.flat_map(Auth::credentials) {
+        data.entry(*key).or_insert(json!(""));
+    }
+    for parameter in collection.requests().flat_map(|r| &r.path_parameters) {
+        let value = data.entry(&parameter.name).or_insert(json!(""));
+        if *value == "" {
+            *value = json!(parameter.value);
+        }
+    }
+
+    let mut resources = vec![
+        Value::Object(workspace),
+        json!({
+            "_id": "env_swagger_ui",
+            "_type": "environment",
+            "parentId": WORKSPACE_ID,
+            "name": "Base Environment",
+            "data": data,
+        }),
+    ];
+    let mut requests = 0;
+    for (index, folder) in collection.folders.iter().enumerate() {
+        let id = format!("fld_{}", index + 1);
+        let mut group = Map::new();
+        group.insert("_id".into(), json!(id));
+        group.insert("_type".into(), json!("request_group"));
+        group.insert("parentId".into(), json!(WORKSPACE_ID));
+        group.insert("name".into(), json!(folder.name));
+        if let Some(description) = &folder.description {
+            group.insert("description".into(), json!(description));
+        }
+        resources.push(Value::Object(group));
+
+        for request in &folder.requests {
+            let auth = request.auth.as_ref().or(collection.auth.as_ref());
+            requests += 1;
+            resources.push(resource(request, &format!("req_{requests}"), &id, auth));
+        }
+    }
+
+    let export = json!({
+        "_type": "export",
+        "__export_format": 4,
+        "__export_source": "swagger-ui",
+        "resources": resources,
+    });
+    Some(serde_json::to_string_pretty(&export).expect("Could not serialize export as JSON."))
+}
+
+fn resource(template: &RequestTemplate, id: &str, parent: &str, auth: Option<&Auth>) -> Value {
+    let path = substitute(&template.path, |name| format!("{{{{ _.{name} }}}}"));
+    let pairs = |parameters: &[Parameter]| -> Vec<Value> {
+        parameters
+            .iter()
+            .map(|parameter| {
+                let mut pair = Map::new();
+                pair.insert("name".into(), json!(parameter.name));
+                pair.insert("value".into(), json!(parameter.value));
+                if let Some(description) = &parameter.description {
+                    pair.insert("description".into(), json!(description));
+                }
+                Value::Object(pair)
+            })
+            .collect()
+    };
+
+    let mut request = Map::new();
+    request.insert("_id".into(), json!(id));
+    request.insert("_type".into(), json!("request"));
+    request.insert("parentId".into(), json!(parent));
+    request.insert("name".into(), json!(template.name));
+    if let Some(description) = &template.description {
+        request.insert("description".into(), json!(description));
+    }
+    request.insert("method".into(), json!(template.method));
+    request.insert("url".into(), json!(format!("{{{{ _.base_url }}}}{path}")));
+    request.insert("parameters".into(), json!(pairs(&template.query)));
+    request.insert("headers".into(), json!(pairs(&template.headers)));
+    let body = match &template.body {
+        Some(body) => json!({"mimeType": body.content_type, "text": body.text}),
+        None => json!({}),
+    };
+    request.insert("body".into(), body);
+    request.insert(
+        "authentication".into(),
+        auth.map_or(json!({}), insomnia_auth),
+    );
+    Value::Object(request)
+}
+
+fn insomnia_auth(auth: &Auth) -> Value {
+    match auth {
+        Auth::None => json!({"type": "none"}),
+        Auth::Basic => json!({
+            "type": "basic",
+            "username": "{{ _.username }}",
+            "password": "{{ _.password }}",
+        }),
+        Auth::Bearer => json!({"type": "bearer", "token": "{{ _.bearerToken }}"}),
+        Auth::ApiKey { name, in_query } => json!({
+            "type": "apikey",
+            "key": name,
+            "value": "{{ _.apiKey }}",
+            "addTo": if *in_query { "queryParams" } else { "header" },
+        }),
+        // sent like a token obtained beforehand, the grant flows need client credentials
+        Auth::OAuth2 => json!({"type": "bearer", "token": "{{ _.accessToken }}"}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::insomnia_export;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn converts_operations_into_resources() {
+        let spec = crate::swagger_spec_file!("../examples/openapi.json");
+        let export: Value = serde_json::from_str(&insomnia_export(&spec).unwrap()).unwrap();
+        assert_eq!(export["__export_format"], 4);
+
+        let resources = export["resources"].as_array().unwrap();
+        assert_eq!(resources[0]["_type"], "workspace");
+        assert_eq!(resources[0]["name"], "Swagger Petstore");
+        let data = &resources[1]["data"];
+        assert_eq!(data["base_url"], "https://petstore.swagger.io/v2");
+        assert_eq!(data["apiKey"], "");
+        assert_eq!(data["petId"], "");
+
+        let find = resources
+            .iter()
+            .find(|resource| resource["name"] == "Find pet by ID")
+            .unwrap();
+        assert_eq!(find["_type"], "request");
+        assert_eq!(find["method"], "GET");
+        assert_eq!(find["url"], "{{ _.base_url }}/pet/{{ _.petId }}");
+        assert_eq!(
+            find["authentication"],
+            json!({"type": "apikey", "key": "api_key", "value": "{{ _.apiKey }}", "addTo": "header"})
+        );
+        let folder = resources
+            .iter()
+            .find(|resource| resource["_id"] == find["parentId"])
+            .unwrap();
+        assert_eq!(folder["name"], "pet");
+    }
+}
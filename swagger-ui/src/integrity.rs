@@ -0,0 +1,81 @@
+//! Subresource Integrity hashes of the assets a viewer's `index.html` loads.
+
+use crate::DocUi;
+use base64::Engine;
+use bytes::Bytes;
+use sha2::{Digest, Sha384};
+use std::collections::HashMap;
+
+/// Returns the SRI hash of every script and stylesheet asset of `U`, by path.
+pub(crate) fn hashes<U: DocUi>() -> HashMap<String, String> {
+    U::asset_paths()
+        .into_iter()
+        .filter(|path| path.ends_with(".js") || path.ends_with(".css"))
+        .filter_map(|path| {
+            let hash = match U::asset_integrity(&path) {
+                Some(hash) => hash.to_string(),
+                None => hash(&U::asset(&path)?),
+            };
+            Some((path, hash))
+        })
+        .collect()
+}
+
+/// Returns the SRI hash of `content`, e.g. `sha384-oqVuAf...`.
+pub(crate) fn hash(content: &[u8]) -> String {
+    let digest = Sha384::digest(content);
+    format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(digest)
+    )
+}
+
+/// Adds `integrity` and `crossorigin` attributes to every `src` and `href` of `index` that
+/// references one of the hashed assets, e.g. `src="./redoc.standalone.js"`.
+pub(crate) fn add_attributes(index: &[u8], hashes: &HashMap<String, String>) -> Bytes {
+    let index = String::from_utf8_lossy(index);
+    let mut out = String::with_capacity(index.len());
+    let mut rest = index.as_ref();
+    while let Some(start) = ["src=\"", "href=\""]
+        .iter()
+        .filter_map(|attribute| rest.find(attribute).map(|at| at + attribute.len()))
+        .min()
+    {
+        let Some(end) = rest[start..].find('"').map(|end| start + end) else {
+            break;
+        };
+        let path = rest[start..end].trim_start_matches("./");
+        out.push_str(&rest[..=end]);
+        if let Some(hash) = hashes.get(path) {
+            out.push_str(&format!(r#" integrity="{hash}" crossorigin="anonymous""#));
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Bytes::from(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_attributes, hash};
+    use std::collections::HashMap;
+
+    #[test]
+    fn adds_hashes_to_referenced_assets() {
+        // from the examples of the Subresource Integrity specification
+        assert_eq!(
+            hash(b"alert('Hello, world.');"),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+
+        let hashes = HashMap::from([("app.js".to_string(), "sha384-abc".to_string())]);
+        let index = add_attributes(
+            br#"<link href="./favicon.png"><script src="./app.js"></script>"#,
+            &hashes,
+        );
+        assert_eq!(
+            index,
+            r#"<link href="./favicon.png"><script src="./app.js" integrity="sha384-abc" crossorigin="anonymous"></script>"#
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Reformatting JSON documents without reordering their keys.
+
+use bytes::Bytes;
+
+/// Returns `content` pretty-printed with two spaces, or without any whitespace, or `None` if it
+/// isn't JSON.
+///
+/// Unlike a round trip through `serde_json::Value`, this keeps the keys in the order they
+/// appear, which viewers show the paths of a spec in.
+pub(crate) fn reformat(content: &[u8], pretty: bool) -> Option<Bytes> {
+    serde_json::from_slice::<serde::de::IgnoredAny>(content).ok()?;
+
+    let mut out = Vec::with_capacity(content.len());
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let newline = |out: &mut Vec<u8>, depth: usize| {
+        out.push(b'\n');
+        out.extend(std::iter::repeat_n(b' ', 2 * depth));
+    };
+    let mut bytes = content.iter().copied().peekable();
+    while let Some(byte) = bytes.next() {
+        if in_string {
+            out.push(byte);
+            match (escaped, byte) {
+                (false, b'\\') => escaped = true,
+                (false, b'"') => in_string = false,
+                _ => escaped = false,
+            }
+            continue;
+        }
+        match byte {
+            b' ' | b'\t' | b'\n' | b'\r' => {}
+            _ if !pretty => {
+                in_string = byte == b'"';
+                out.push(byte);
+            }
+            b'"' => {
+                in_string = true;
+                out.push(byte);
+            }
+            b'{' | b'[' => {
+                out.push(byte);
+                while bytes.next_if(u8::is_ascii_whitespace).is_some() {}
+                if bytes.peek().is_some_and(|next| matches!(next, b'}' | b']')) {
+                    out.extend(bytes.next());
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            b'}' | b']' => {
+                depth -= 1;
+                newline(&mut out, depth);
+                out.push(byte);
+            }
+            b',' => {
+                out.push(byte);
+                newline(&mut out, depth);
+            }
+            b':' => out.extend_from_slice(b": "),
+            _ => out.push(byte),
+        }
+    }
+    if pretty {
+        out.push(b'\n');
+    }
+    Some(Bytes::from(out))
+}
+
+/// Returns whether the `pretty` parameter of `query` asks for pretty-printed JSON, e.g.
+/// `?pretty=1`, or compact JSON, e.g. `?pretty=0`, or `None` if it isn't there.
+pub(crate) fn pretty_param(query: Option<&str>) -> Option<bool> {
+    form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+        .find(|(name, _)| name == "pretty")
+        .map(|(_, value)| !matches!(value.as_ref(), "0" | "false"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pretty_param, reformat};
+
+    #[test]
+    fn reformats_keeping_the_order_of_keys() {
+        let content = br#"{ "b": [1, {}], "a": {"s": "x, \"y\": [z]"} }"#;
+        assert_eq!(
+            reformat(content, false).unwrap(),
+            r#"{"b":[1,{}],"a":{"s":"x, \"y\": [z]"}}"#
+        );
+        assert_eq!(
+            reformat(content, true).unwrap(),
+            "{\n  \"b\": [\n    1,\n    {}\n  ],\n  \"a\": {\n    \"s\": \"x, \\\"y\\\": [z]\"\n  }\n}\n"
+        );
+        assert!(reformat(b"openapi: 3.0.0", true).is_none());
+
+        assert_eq!(pretty_param(Some("pretty=1")), Some(true));
+        assert_eq!(pretty_param(Some("a=b&pretty")), Some(true));
+        assert_eq!(pretty_param(Some("pretty=false")), Some(false));
+        assert_eq!(pretty_param(None), None);
+    }
+}
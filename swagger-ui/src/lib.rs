@@ -1,13 +1,145 @@
-use rust_embed::RustEmbed;
+mod acl;
+mod aggregate;
+mod analytics;
+mod audit;
+mod bundle;
+mod cache;
+mod changelog;
+mod collection;
+#[cfg(feature = "console")]
+mod console;
+mod cors;
+mod diff;
+mod doc_ui;
+#[cfg(feature = "elements")]
+mod elements;
+mod env;
+mod example;
+mod export;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod filter;
+mod handlers;
+mod html;
+mod info;
+mod insomnia;
+#[cfg(feature = "integrity")]
+mod integrity;
+mod json;
+mod lru;
+mod markdown;
+mod mime;
+mod minify;
+#[cfg(feature = "mock")]
+mod mock;
+#[cfg(feature = "oidc")]
+mod oidc;
+mod postman;
+mod precompressed;
+#[cfg(feature = "proxy")]
+mod proxy;
+mod query;
+#[cfg(feature = "rapidoc")]
+mod rapidoc;
+#[cfg(feature = "redact")]
+mod redact;
+#[cfg(feature = "redoc")]
+mod redoc;
+mod registry;
+#[cfg(feature = "scalar")]
+mod scalar;
+mod schemas;
+#[cfg(feature = "serve")]
+mod serve;
+mod servers;
+mod service;
+#[cfg(feature = "sessions")]
+mod session;
+#[cfg(feature = "signed-urls")]
+mod signed;
+mod sort;
+mod sunset;
+#[cfg(feature = "swagger-editor")]
+mod swagger_editor;
+mod tenant;
+mod terms;
+mod theme;
+mod validate;
+mod webhooks;
+
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 
+pub use crate::acl::{Acl, Roles};
+pub use crate::aggregate::merge_specs;
+pub use crate::analytics::Analytics;
+pub use crate::audit::AccessEvent;
+pub use crate::bundle::bundle;
+pub use crate::cache::CachePolicy;
+pub use crate::changelog::{changelog, ChangelogService};
+#[cfg(feature = "console")]
+pub use crate::console::{Console, ConsoleConfig, ConsoleService};
+pub use crate::cors::Cors;
+pub use crate::diff::{diff, Change, ChangeKind, DiffService, SpecDiff};
+pub use crate::doc_ui::{DocUi, SwaggerUi};
+#[cfg(feature = "elements")]
+pub use crate::elements::{
+    Elements, ElementsAssets, ElementsConfig, ElementsLayout, ElementsRouter, ElementsService,
+};
+pub use crate::env::{docs_disabled, DISABLED_VAR};
+pub use crate::export::export_static;
+#[cfg(feature = "fetch")]
+pub use crate::fetch::{DiscoveredSpec, RefreshStatus, RemoteSpec, SpecDiscovery};
+pub use crate::filter::{filter_spec, flag_spec, tag_spec, FlagProvider};
+pub use crate::handlers::{serve_asset, serve_config, serve_spec};
+pub use crate::info::SpecInfo;
+pub use crate::insomnia::insomnia_export;
+pub use crate::markdown::spec_markdown;
+pub use crate::mime::ContentType;
+pub use crate::minify::minify_spec;
+#[cfg(feature = "mock")]
+pub use crate::mock::MockService;
+#[cfg(feature = "oidc")]
+pub use crate::oidc::{Oidc, OidcGuard, OidcUser};
+pub use crate::postman::postman_collection;
+pub use crate::precompressed::Precompressed;
+#[cfg(feature = "proxy")]
+pub use crate::proxy::{Proxy, ProxyClient, ProxyService, ProxyUser, RateLimit};
+pub use crate::query::QueryParam;
+#[cfg(feature = "rapidoc")]
+pub use crate::rapidoc::{
+    Rapidoc, RapidocAssets, RapidocConfig, RapidocRenderStyle, RapidocService, RapidocTheme,
+};
+#[cfg(feature = "redact")]
+pub use crate::redact::Redactor;
+#[cfg(feature = "redoc")]
+pub use crate::redoc::{Redoc, RedocAssets, RedocConfig, RedocService};
+pub use crate::registry::{ApiEntry, LandingPageService, SpecRegistry};
+#[cfg(feature = "scalar")]
+pub use crate::scalar::{Scalar, ScalarAssets, ScalarConfig, ScalarLayout, ScalarService};
+pub use crate::schemas::json_schemas;
+#[cfg(feature = "serve")]
+pub use crate::serve::{serve, serve_service};
+pub use crate::servers::Server;
+pub use crate::service::{Body, DocService, MountPath, SpecService, SwaggerUiService};
+#[cfg(feature = "sessions")]
+pub use crate::session::{AuthorizationService, AuthorizationStore, MemoryAuthorizationStore};
+#[cfg(feature = "signed-urls")]
+pub use crate::signed::UrlSigner;
+pub use crate::sort::sort_spec;
+pub use crate::sunset::sunset_notices;
+#[cfg(feature = "swagger-editor")]
+pub use crate::swagger_editor::{
+    EditorMode, SwaggerEditor, SwaggerEditorAssets, SwaggerEditorService,
+};
+pub use crate::tenant::{tenant_from_header, tenant_of, TenantService, TENANT_HEADER};
+pub use crate::terms::Terms;
+pub use crate::theme::Theme;
+pub use crate::validate::{lint, validate, Diagnostic, Severity};
+pub use crate::webhooks::webhooks_as_tag;
 pub use bytes::Bytes;
-
-/// Assets from swagger-ui-dist
-#[derive(RustEmbed)]
-#[folder = "$CARGO_MANIFEST_DIR/.dist"]
-pub struct Assets;
+pub use swagger_ui_assets::Assets;
 
 /// Contains a named url.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -61,21 +193,221 @@ pub enum Filter {
 }
 
 /// Used to represent openapi specification file
+///
+/// Specs are created with [`Spec::builder`] or [`Spec::from_file`], the struct can't be built
+/// with a literal outside this crate, so that fields can be added without breaking users.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Spec {
     /// Spec file name
     pub name: Cow<'static, str>,
     /// Spec file content
     pub content: Bytes,
+    /// Content type the spec is served with, e.g. `application/json`
+    pub content_type: Cow<'static, str>,
+    /// The content compressed at build time, see [`swagger_spec_file!`]. Specs changed after
+    /// they are built, e.g. filtered or redacted, have none.
+    pub precompressed: Option<Precompressed>,
+}
+
+impl Spec {
+    /// Starts building a spec, e.g. `Spec::builder().name("openapi.json").json(content)`.
+    pub fn builder() -> SpecBuilder {
+        SpecBuilder::default()
+    }
+
+    /// Reads the spec at `path`, named by its file name, for specs located at runtime.
+    ///
+    /// The error names the path, e.g. `./openapi.json: No such file or directory`.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read(path)
+            .map_err(|err| std::io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+        let builder = match path.file_name() {
+            Some(name) => Spec::builder().name(name.to_string_lossy().into_owned()),
+            None => Spec::builder(),
+        };
+        Ok(builder.content(content))
+    }
+
+    /// Parses the content of the spec, failing if it isn't JSON.
+    pub fn as_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::from_slice(&self.content)
+    }
+
+    /// Returns `info.title` of the spec, or `None` if it has none or isn't JSON.
+    pub fn title(&self) -> Option<String> {
+        self.info_field("title")
+    }
+
+    /// Returns `info.version` of the spec, the version of the API rather than of OpenAPI, or
+    /// `None` if it has none or isn't JSON.
+    pub fn version(&self) -> Option<String> {
+        self.info_field("version")
+    }
+
+    /// Sets `info.version` of the spec, e.g. to the version of the crate serving it.
+    ///
+    /// The content is serialized again, which sorts the keys of its objects. Fails if the spec
+    /// isn't a JSON object.
+    pub fn set_info_version(&mut self, version: impl Into<String>) -> serde_json::Result<()> {
+        let mut document = self.as_json()?;
+        let Some(object) = document.as_object_mut() else {
+            return Err(serde::de::Error::custom("spec isn't a JSON object"));
+        };
+        let info = object
+            .entry("info")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        match info.as_object_mut() {
+            Some(info) => info.insert("version".into(), version.into().into()),
+            None => return Err(serde::de::Error::custom("`info` of spec isn't an object")),
+        };
+        self.content = Bytes::from(serde_json::to_vec(&document)?);
+        self.precompressed = None;
+        Ok(())
+    }
+
+    fn info_field(&self, field: &str) -> Option<String> {
+        let document = self.as_json().ok()?;
+        Some(document["info"][field].as_str()?.to_string())
+    }
+}
+
+/// Builder of a [`Spec`], see [`Spec::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct SpecBuilder {
+    name: Option<Cow<'static, str>>,
+}
+
+impl SpecBuilder {
+    /// Sets the file name the spec is served under.
+    /// Default: `openapi.json`, or `openapi.yaml` for YAML specs.
+    pub fn name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Builds a spec of JSON `content`, served as `application/json`.
+    pub fn json(self, content: impl Into<Bytes>) -> Spec {
+        self.build(content.into(), "openapi.json", "application/json")
+    }
+
+    /// Builds a spec of YAML `content`, served as `application/yaml`.
+    pub fn yaml(self, content: impl Into<Bytes>) -> Spec {
+        self.build(content.into(), "openapi.yaml", "application/yaml")
+    }
+
+    /// Builds a spec of `content`, which is YAML if the name ends in `.yaml` or `.yml` and JSON
+    /// otherwise.
+    pub fn content(self, content: impl Into<Bytes>) -> Spec {
+        let yaml = self
+            .name
+            .as_deref()
+            .is_some_and(|name| name.ends_with(".yaml") || name.ends_with(".yml"));
+        match yaml {
+            true => self.yaml(content),
+            false => self.json(content),
+        }
+    }
+
+    /// Builds a spec of the content compressed at build time, see [`swagger_spec_file!`], which
+    /// is YAML or JSON like with [`content`](Self::content).
+    ///
+    /// The content is decompressed once, here. Panics if it isn't valid for its encoding.
+    #[cfg(feature = "compression")]
+    pub fn precompressed(self, precompressed: Precompressed) -> Spec {
+        let content = precompressed
+            .decompress()
+            .expect("Could not decompress spec.");
+        Spec {
+            precompressed: Some(precompressed),
+            ..self.content(content)
+        }
+    }
+
+    fn build(self, content: Bytes, name: &'static str, content_type: &'static str) -> Spec {
+        Spec {
+            name: self.name.unwrap_or(Cow::Borrowed(name)),
+            content,
+            content_type: Cow::Borrowed(content_type),
+            precompressed: None,
+        }
+    }
 }
 
 /// Helper type to accept both provided or existing spec
+///
+/// Strings and uris convert into urls, which may be relative to where the viewer is mounted,
+/// and paths, with `try_from`, into the spec read from the file, see [`Spec::from_file`].
+/// Converting an invalid url panics in debug builds, see [`SpecOrUrl::url`] to handle it.
 #[derive(Debug, Clone)]
 pub enum SpecOrUrl {
     Spec(Spec),
     Url(Cow<'static, str>),
 }
 
+impl SpecOrUrl {
+    /// Creates the url of a spec, failing if it is empty, contains whitespace or control
+    /// characters or has a scheme other than `http` and `https`.
+    ///
+    /// Relative urls like `../openapi.json` are resolved against the path the viewer is mounted
+    /// at, so they point to the same spec in every browser and behind reverse proxies.
+    pub fn url(url: impl Into<Cow<'static, str>>) -> Result<Self, InvalidSpecUrl> {
+        let url = url.into();
+        let invalid = |reason| InvalidSpecUrl {
+            url: url.to_string(),
+            reason,
+        };
+        if url.is_empty() {
+            return Err(invalid("it is empty"));
+        }
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(invalid("it contains whitespace or control characters"));
+        }
+        let scheme = url
+            .split_once(':')
+            .map(|(scheme, _)| scheme)
+            .filter(|scheme| {
+                scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            });
+        if scheme.is_some_and(|scheme| {
+            !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https")
+        }) {
+            return Err(invalid(
+                "only http and https urls can be fetched by the viewer",
+            ));
+        }
+        Ok(Self::Url(url))
+    }
+
+    /// Creates the url of a spec converted from a string or uri, checked like by
+    /// [`url`](Self::url) in debug builds only.
+    fn converted_url(url: Cow<'static, str>) -> Self {
+        #[cfg(debug_assertions)]
+        if let Err(err) = Self::url(url.clone()) {
+            panic!("{err}, see `SpecOrUrl::url` to handle it");
+        }
+        Self::Url(url)
+    }
+}
+
+/// Error of [`SpecOrUrl::url`], naming the url and why it was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSpecUrl {
+    url: String,
+    reason: &'static str,
+}
+
+impl std::fmt::Display for InvalidSpecUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid spec url `{}`: {}", self.url, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidSpecUrl {}
+
 impl From<Spec> for SpecOrUrl {
     fn from(value: Spec) -> Self {
         Self::Spec(value)
@@ -84,39 +416,131 @@ impl From<Spec> for SpecOrUrl {
 
 impl From<String> for SpecOrUrl {
     fn from(value: String) -> Self {
-        Self::Url(value.into())
+        Self::converted_url(value.into())
     }
 }
 
 impl From<&'static str> for SpecOrUrl {
     fn from(value: &'static str) -> Self {
-        Self::Url(Cow::Borrowed(value))
+        Self::converted_url(Cow::Borrowed(value))
+    }
+}
+
+impl From<http::Uri> for SpecOrUrl {
+    fn from(value: http::Uri) -> Self {
+        Self::converted_url(value.to_string().into())
+    }
+}
+
+#[cfg(feature = "url")]
+impl From<url::Url> for SpecOrUrl {
+    fn from(value: url::Url) -> Self {
+        Self::converted_url(String::from(value).into())
+    }
+}
+
+impl TryFrom<PathBuf> for SpecOrUrl {
+    type Error = std::io::Error;
+
+    /// Reads the spec at `value`, see [`Spec::from_file`].
+    fn try_from(value: PathBuf) -> std::io::Result<Self> {
+        value.as_path().try_into()
+    }
+}
+
+impl TryFrom<&Path> for SpecOrUrl {
+    type Error = std::io::Error;
+
+    fn try_from(value: &Path) -> std::io::Result<Self> {
+        Spec::from_file(value).map(Self::Spec)
     }
 }
 
 /// Macro used to create `Spec` struct,
 /// loads file using `include_bytes!`
+///
+/// The path is a string literal relative to the current file, use [`try_swagger_spec_file!`]
+/// for files located at runtime.
+///
+/// With the `compression` feature, `swagger_spec_file!("openapi.json", gzip)` or `brotli`
+/// embeds the spec compressed instead, see [`SpecBuilder::precompressed`]. It is decompressed
+/// once on startup and sent compressed as it is to clients accepting the encoding.
 #[macro_export]
 macro_rules! swagger_spec_file {
     ($name: literal) => {
-        $crate::Spec {
-            name: std::borrow::Cow::Borrowed(($name).split("/").last().unwrap()),
-            content: $crate::Bytes::from_static(include_bytes!($name)),
-        }
+        $crate::Spec::builder()
+            .name(($name).split("/").last().unwrap())
+            .content($crate::Bytes::from_static(include_bytes!($name)))
+    };
+    ($name: literal, $encoding: ident) => {{
+        let (encoding, content, raw_len, digest) =
+            $crate::__include_precompressed!($name, $encoding);
+        $crate::Spec::builder()
+            .name(($name).split("/").last().unwrap())
+            .precompressed($crate::Precompressed {
+                encoding,
+                content,
+                raw_len,
+                digest,
+            })
+    }};
+    ($name: expr) => {
+        compile_error!(concat!(
+            "swagger_spec_file! takes a string literal relative to the current file, `",
+            stringify!($name),
+            "` isn't one. Use try_swagger_spec_file! to read a spec located at runtime."
+        ))
+    };
+}
+
+#[cfg(feature = "compression")]
+#[doc(hidden)]
+pub use swagger_ui_macros::include_precompressed as __include_precompressed;
+
+#[cfg(not(feature = "compression"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __include_precompressed {
+    ($($args: tt)*) => {
+        compile_error!("swagger_spec_file! compresses specs with the `compression` feature only")
+    };
+}
+
+/// Macro used to read a `Spec` at runtime, returning an `std::io::Result` instead of failing the
+/// build like [`swagger_spec_file!`] does.
+///
+/// The path may be any expression, e.g. from an environment variable, and relative paths are
+/// resolved against the working directory. See [`Spec::from_file`].
+#[macro_export]
+macro_rules! try_swagger_spec_file {
+    ($path: expr) => {
+        $crate::Spec::from_file($path)
     };
 }
 
 /// Swagger UI configuration
+///
+/// Fields missing when deserializing, e.g. from a partial config file, keep their defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(default, rename_all = "camelCase")]
 pub struct Config {
     /// The url to a single `openapi.json` file that is showed when the web ui is first opened.
+    /// Left empty, it is set to the url of the served spec, otherwise it is kept, e.g. to point
+    /// the web ui at a spec hosted elsewhere.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub url: String,
     /// A list of named urls that contain all the `openapi.json` files that you want to display in
     /// your web ui. If this field is populated, the `url` field is not used.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub urls: Vec<UrlObject>,
+    /// The name of the entry of `urls` that is shown when the web ui is first opened.
+    /// Default: `None` (the first entry).
+    #[serde(
+        rename = "urls.primaryName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub urls_primary_name: Option<String>,
 
     // display options:
     /// If set to true, enables deep linking for tags and operations. See the
@@ -161,6 +585,59 @@ pub struct Config {
     /// `minimum`) fields and values for Parameters.
     /// Default: `false`.
     pub show_common_extensions: bool,
+
+    // served spec options:
+    /// Servers the served spec lists in place of its own, e.g. those of the environment it is
+    /// deployed to, so try-it-out requests go there. Only applies to OpenAPI 3 JSON specs.
+    /// Default: `[]` (the servers of the spec).
+    #[serde(skip)]
+    pub servers: Vec<Server>,
+    /// If set to true, `servers` are listed before the servers of the spec instead of replacing
+    /// them.
+    /// Default: `false`.
+    #[serde(skip)]
+    pub keep_spec_servers: bool,
+    /// The label or url of the server try-it-out requests go to by default, which the served
+    /// spec lists first, e.g. that of the environment this deployment belongs to.
+    /// Default: `None` (the first server).
+    #[serde(skip)]
+    pub default_server: Option<String>,
+}
+
+impl Config {
+    /// Serve the spec with `servers` in place of its own.
+    pub fn servers(mut self, servers: Vec<Server>) -> Self {
+        self.servers = servers;
+        self.keep_spec_servers = false;
+        self
+    }
+
+    /// Serve the spec with `servers` listed before its own.
+    pub fn add_servers(mut self, servers: Vec<Server>) -> Self {
+        self.servers = servers;
+        self.keep_spec_servers = true;
+        self
+    }
+
+    /// Serve the spec with a server per named environment in place of its own, e.g.
+    /// `[("Staging", "https://staging.example.com/api")]`, labeled with its name.
+    pub fn environments<N: Into<String>, U: Into<String>>(
+        self,
+        environments: impl IntoIterator<Item = (N, U)>,
+    ) -> Self {
+        let servers = environments
+            .into_iter()
+            .map(|(name, url)| Server::new(url).label(name))
+            .collect();
+        self.servers(servers)
+    }
+
+    /// Make try-it-out requests go to the server labeled, or at the url, `server` by default,
+    /// e.g. the environment of this deployment.
+    pub fn default_server(mut self, server: impl Into<String>) -> Self {
+        self.default_server = Some(server.into());
+        self
+    }
 }
 
 fn is_zero(num: &u32) -> bool {
@@ -172,6 +649,7 @@ impl Default for Config {
         Self {
             url: String::new(),
             urls: vec![],
+            urls_primary_name: None,
             deep_linking: false,
             display_operation_id: false,
             default_model_expand_depth: 1,
@@ -183,6 +661,9 @@ impl Default for Config {
             max_displayed_tags: 0,
             show_extensions: false,
             show_common_extensions: false,
+            servers: vec![],
+            keep_spec_servers: false,
+            default_server: None,
         }
     }
 }
@@ -190,7 +671,6 @@ impl Default for Config {
 #[cfg(test)]
 mod tests {
     use crate::Assets;
-    use std::path::Path;
 
     fn asset_list() -> [&'static str; 8] {
         [
@@ -210,14 +690,107 @@ mod tests {
         println!("Checking if assets exists in binary");
         for asset in &asset_list() {
             println!("\t{}", asset);
-            let data = Assets::get(&asset).unwrap();
+            let data = Assets::get(asset).unwrap();
             assert!(!data.is_empty());
         }
     }
 
+    #[test]
+    fn spec_or_url_conversions() {
+        use crate::SpecOrUrl;
+        use std::path::PathBuf;
+
+        let url = String::from("https://example.com/openapi.json");
+        assert!(matches!(SpecOrUrl::from(url.clone()), SpecOrUrl::Url(u) if u == url));
+        let uri: http::Uri = "/api/openapi.json".parse().unwrap();
+        assert!(matches!(SpecOrUrl::from(uri), SpecOrUrl::Url(u) if u == "/api/openapi.json"));
+
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/openapi.json");
+        let SpecOrUrl::Spec(spec) = SpecOrUrl::try_from(path.clone()).unwrap() else {
+            panic!("paths are read");
+        };
+        assert!(SpecOrUrl::try_from(path.with_file_name("missing.json").as_path()).is_err());
+        assert_eq!(spec.name, "openapi.json");
+        assert_eq!(
+            spec.content,
+            swagger_spec_file!("../examples/openapi.json").content
+        );
+    }
+
     #[test]
     fn swagger_ui_macro_name() {
         let spec = swagger_spec_file!("../examples/openapi.json");
         assert_eq!(&spec.name, "openapi.json")
     }
+
+    #[test]
+    fn try_macro_names_the_missing_file() {
+        let spec = try_swagger_spec_file!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/openapi.json"
+        ));
+        assert_eq!(spec.unwrap().name, "openapi.json");
+
+        let err = try_swagger_spec_file!("./missing/openapi.json").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.to_string().starts_with("./missing/openapi.json: "));
+    }
+
+    #[test]
+    fn inspects_and_sets_the_info_of_specs() {
+        let mut spec = swagger_spec_file!("../examples/openapi.json");
+        assert_eq!(spec.title().as_deref(), Some("Swagger Petstore"));
+        assert_eq!(spec.version().as_deref(), Some("1.0.5"));
+
+        spec.set_info_version("2.0.0").unwrap();
+        assert_eq!(spec.version().as_deref(), Some("2.0.0"));
+        assert_eq!(spec.title().as_deref(), Some("Swagger Petstore"));
+
+        let mut yaml = crate::Spec::builder().yaml("openapi: 3.0.0\n");
+        assert!(yaml.as_json().is_err());
+        assert_eq!(yaml.title(), None);
+        assert!(yaml.set_info_version("2.0.0").is_err());
+    }
+
+    #[test]
+    fn validates_spec_urls() {
+        use crate::SpecOrUrl;
+
+        assert!(SpecOrUrl::url("../openapi.json").is_ok());
+        assert!(SpecOrUrl::url("https://example.com/openapi.json").is_ok());
+        assert!(SpecOrUrl::url("").is_err());
+        assert!(SpecOrUrl::url("open api.json").is_err());
+        let err = SpecOrUrl::url("javascript:alert(1)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid spec url `javascript:alert(1)`: only http and https urls can be fetched by the viewer"
+        );
+        assert!(matches!(
+            SpecOrUrl::from("/openapi.json"),
+            SpecOrUrl::Url(std::borrow::Cow::Borrowed("/openapi.json"))
+        ));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "only http and https urls")]
+    fn panics_on_converting_invalid_spec_urls_in_debug_builds() {
+        let _ = crate::SpecOrUrl::from("javascript:alert(1)".to_string());
+    }
+
+    #[test]
+    fn builds_specs_with_content_types() {
+        let json = crate::Spec::builder().json("{}");
+        assert_eq!(
+            (json.name.as_ref(), json.content_type.as_ref()),
+            ("openapi.json", "application/json")
+        );
+        let yaml = crate::Spec::builder()
+            .name("api.yml")
+            .content("openapi: 3.0.0");
+        assert_eq!(
+            (yaml.name.as_ref(), yaml.content_type.as_ref()),
+            ("api.yml", "application/yaml")
+        );
+    }
 }
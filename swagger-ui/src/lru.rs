@@ -0,0 +1,97 @@
+//! A map of a bounded size shared between requests, e.g. for the serialized configs.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// A map of up to `capacity` entries, which evicts the least recently used entry to make room
+/// for a new one.
+pub(crate) struct Lru<K, V> {
+    capacity: usize,
+    /// The entries, with the tick of the clock they were last used at.
+    entries: RwLock<HashMap<K, (V, AtomicU64)>>,
+    /// Advanced on every use of an entry.
+    clock: AtomicU64,
+}
+
+impl<K: Eq + Hash, V: Clone> Lru<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Returns the value of `key`, marking it as used.
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let entries = self.entries.read().unwrap();
+        let (value, used) = entries.get(key)?;
+        used.store(self.tick(), Ordering::Relaxed);
+        Some(value.clone())
+    }
+
+    /// Sets the value of `key`, evicting the least recently used entry if the map is full.
+    pub(crate) fn insert(&self, key: K, value: V) {
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            let oldest = entries
+                .values()
+                .map(|(_, used)| used.load(Ordering::Relaxed))
+                .min();
+            entries.retain(|_, (_, used)| Some(used.load(Ordering::Relaxed)) != oldest);
+        }
+        entries.insert(key, (value, AtomicU64::new(self.tick())));
+    }
+
+    /// Removes the entry of `key`.
+    pub(crate) fn remove<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries.write().unwrap().remove(key);
+    }
+
+    /// Removes all entries.
+    pub(crate) fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+
+    /// Returns the number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lru;
+
+    #[test]
+    fn evicts_the_least_recently_used_entry() {
+        let lru = Lru::new(2);
+        lru.insert("a", 1);
+        lru.insert("b", 2);
+        assert_eq!(lru.get("a"), Some(1));
+        lru.insert("c", 3);
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get("b"), None);
+        assert_eq!(lru.get("a"), Some(1));
+        assert_eq!(lru.get("c"), Some(3));
+
+        lru.insert("c", 4);
+        assert_eq!(lru.get("a"), Some(1));
+        assert_eq!(lru.get("c"), Some(4));
+    }
+}
@@ -0,0 +1,342 @@
+//! Rendering of a spec into Markdown, e.g. for pasting API summaries into a wiki.
+
+use crate::Spec;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Methods of a path item, in the order operations are listed.
+pub(crate) const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Section of the operations without a tag.
+const UNTAGGED: &str = "Other";
+
+/// Renders `spec` into Markdown, with a section per tag, a heading per operation and a table per
+/// schema, or `None` if the spec isn't JSON.
+///
+/// Swagger 2.0 `definitions` are listed like OpenAPI 3 `components.schemas`.
+pub fn spec_markdown(spec: &Spec) -> Option<String> {
+    let document: Value = serde_json::from_slice(&spec.content).ok()?;
+    let mut out = String::new();
+
+    let info = &document["info"];
+    let title = info["title"].as_str().unwrap_or(&spec.name);
+    match info["version"].as_str() {
+        Some(version) => writeln!(out, "# {title} ({version})"),
+        None => writeln!(out, "# {title}"),
+    }
+    .unwrap();
+    if let Some(description) = text(&info["description"]) {
+        writeln!(out, "\n{}", description).unwrap();
+    }
+
+    for (tag, operations) in sections(&document) {
+        writeln!(out, "\n## {tag}").unwrap();
+        let description = document["tags"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|entry| entry["name"] == tag)
+            .and_then(|entry| text(&entry["description"]));
+        if let Some(description) = description {
+            writeln!(out, "\n{}", description).unwrap();
+        }
+        for (path, method, operation) in operations {
+            render_operation(&mut out, &document, path, method, operation);
+        }
+    }
+
+    let schemas = document["components"]["schemas"]
+        .as_object()
+        .or_else(|| document["definitions"].as_object());
+    if let Some(schemas) = schemas.filter(|schemas| !schemas.is_empty()) {
+        writeln!(out, "\n## Schemas").unwrap();
+        for (name, schema) in schemas {
+            writeln!(out, "\n### {name}\n").unwrap();
+            render_schema(&mut out, &document, schema);
+        }
+    }
+    Some(out)
+}
+
+pub(crate) type Operation<'a> = (&'a str, &'a str, &'a Value);
+
+/// Groups the operations by their first tag, with the tags declared in the spec first.
+pub(crate) fn sections(document: &Value) -> Vec<(String, Vec<Operation<'_>>)> {
+    let mut sections: Vec<(String, Vec<Operation>)> = document["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| tag["name"].as_str())
+        .map(|name| (name.to_string(), vec![]))
+        .collect();
+
+    let paths = document["paths"].as_object().into_iter().flatten();
+    for (path, item) in paths {
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let tag = operation["tags"][0].as_str().unwrap_or(UNTAGGED);
+            let index = match sections.iter().position(|(name, _)| name == tag) {
+                Some(index) => index,
+                None => {
+                    sections.push((tag.to_string(), vec![]));
+                    sections.len() - 1
+                }
+            };
+            sections[index].1.push((path, method, operation));
+        }
+    }
+    sections.retain(|(_, operations)| !operations.is_empty());
+    sections
+}
+
+fn render_operation(out: &mut String, document: &Value, path: &str, method: &str, op: &Value) {
+    writeln!(out, "\n### `{} {path}`", method.to_uppercase()).unwrap();
+    if let Some(summary) = text(&op["summary"]) {
+        writeln!(out, "\n{}", summary).unwrap();
+    }
+    if let Some(description) = text(&op["description"]) {
+        writeln!(out, "\n{}", description).unwrap();
+    }
+    if op["deprecated"] == true {
+        writeln!(out, "\n**Deprecated**").unwrap();
+    }
+
+    let item = &document["paths"][path];
+    let parameters: Vec<&Value> = [&item["parameters"], &op["parameters"]]
+        .into_iter()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|parameter| resolve(document, parameter))
+        .collect();
+    let (body, parameters): (Vec<&Value>, Vec<&Value>) = parameters
+        .into_iter()
+        .partition(|parameter| parameter["in"] == "body");
+
+    if !parameters.is_empty() {
+        writeln!(out, "\n| Name | In | Type | Required | Description |").unwrap();
+        writeln!(out, "| --- | --- | --- | --- | --- |").unwrap();
+        for parameter in parameters {
+            let kind = match parameter.get("schema") {
+                Some(schema) => type_name(document, schema),
+                // Swagger 2.0 parameters carry the schema fields themselves
+                None => type_name(document, parameter),
+            };
+            writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                cell(parameter["name"].as_str().unwrap_or_default()),
+                cell(parameter["in"].as_str().unwrap_or_default()),
+                cell(&kind),
+                if parameter["required"] == true {
+                    "yes"
+                } else {
+                    "no"
+                },
+                cell(parameter["description"].as_str().unwrap_or_default()),
+            )
+            .unwrap();
+        }
+    }
+
+    let request_body = match body.first() {
+        Some(parameter) => parameter.get("schema"),
+        None => body_schema(resolve(document, &op["requestBody"])),
+    };
+    if let Some(schema) = request_body {
+        writeln!(out, "\nRequest body: {}", type_name(document, schema)).unwrap();
+    }
+
+    if let Some(responses) = op["responses"].as_object() {
+        writeln!(out, "\n| Status | Description | Type |").unwrap();
+        writeln!(out, "| --- | --- | --- |").unwrap();
+        for (status, response) in responses {
+            let response = resolve(document, response);
+            let kind = body_schema(response)
+                .map(|schema| type_name(document, schema))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "| {} | {} | {} |",
+                cell(status),
+                cell(response["description"].as_str().unwrap_or_default()),
+                cell(&kind),
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn render_schema(out: &mut String, document: &Value, schema: &Value) {
+    let schema = resolve(document, schema);
+    if let Some(description) = text(&schema["description"]) {
+        writeln!(out, "{}\n", description).unwrap();
+    }
+    let Some(properties) = schema["properties"].as_object() else {
+        writeln!(out, "Type: {}", type_name(document, schema)).unwrap();
+        return;
+    };
+    let required = |name: &str| {
+        schema["required"]
+            .as_array()
+            .is_some_and(|required| required.iter().any(|entry| entry == name))
+    };
+    writeln!(out, "| Property | Type | Required | Description |").unwrap();
+    writeln!(out, "| --- | --- | --- | --- |").unwrap();
+    for (name, property) in properties {
+        writeln!(
+            out,
+            "| `{}` | {} | {} | {} |",
+            cell(name),
+            cell(&type_name(document, property)),
+            if required(name) { "yes" } else { "no" },
+            cell(
+                resolve(document, property)["description"]
+                    .as_str()
+                    .unwrap_or_default()
+            ),
+        )
+        .unwrap();
+    }
+}
+
+/// Returns the schema of the first media type of a request body or response, or the Swagger 2.0
+/// `schema` of a response.
+fn body_schema(container: &Value) -> Option<&Value> {
+    container.get("schema").or_else(|| {
+        let content = container["content"].as_object()?;
+        content.values().next()?.get("schema")
+    })
+}
+
+/// Describes the type of `schema`, e.g. `Pet`, `Pet[]` or `string (date-time)`.
+fn type_name(document: &Value, schema: &Value) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    let schema = resolve(document, schema);
+    match (schema["type"].as_str(), schema["format"].as_str()) {
+        (Some("array"), _) => format!("{}[]", type_name(document, &schema["items"])),
+        (Some(kind), Some(format)) => format!("{kind} ({format})"),
+        (Some(kind), None) => kind.to_string(),
+        (None, _) if schema.get("enum").is_some() => "enum".to_string(),
+        (None, _) => "object".to_string(),
+    }
+}
+
+/// Follows local `$ref`s like `#/components/schemas/Pet`, returning `Value::Null` for dangling
+/// ones.
+fn resolve<'a>(document: &'a Value, mut value: &'a Value) -> &'a Value {
+    // bounded, so cyclic references can't hang the rendering
+    for _ in 0..16 {
+        let Some(reference) = value["$ref"].as_str() else {
+            break;
+        };
+        value = reference
+            .strip_prefix('#')
+            .and_then(|pointer| document.pointer(pointer))
+            .unwrap_or(&Value::Null);
+    }
+    value
+}
+
+/// Returns the trimmed string `value`, or `None` if it is blank.
+fn text(value: &Value) -> Option<&str> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Keeps `value` on one line of a table cell.
+fn cell(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spec_markdown;
+    use crate::Spec;
+    use bytes::Bytes;
+
+    #[test]
+    fn renders_tags_operations_and_schemas() {
+        let spec = Spec::builder().json(Bytes::from_static(
+                br##"{
+                    "openapi": "3.0.0",
+                    "info": {"title": "Pets", "version": "1.0"},
+                    "tags": [{"name": "pets", "description": "Everything about pets"}],
+                    "paths": {
+                        "/pets/{id}": {
+                            "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "integer", "format": "int64"}}],
+                            "get": {
+                                "tags": ["pets"],
+                                "summary": "Find a pet",
+                                "responses": {
+                                    "200": {"description": "The pet", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}
+                                }
+                            }
+                        },
+                        "/health": {"get": {"responses": {"204": {"description": "Up | running"}}}}
+                    },
+                    "components": {"schemas": {"Pet": {
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string"}, "tags": {"type": "array", "items": {"type": "string"}}}
+                    }}}
+                }"##,
+            ));
+
+        let markdown = spec_markdown(&spec).unwrap();
+        let expected = "\
+# Pets (1.0)
+
+## pets
+
+Everything about pets
+
+### `GET /pets/{id}`
+
+Find a pet
+
+| Name | In | Type | Required | Description |
+| --- | --- | --- | --- | --- |
+| `id` | path | integer (int64) | yes |  |
+
+| Status | Description | Type |
+| --- | --- | --- |
+| 200 | The pet | Pet |
+
+## Other
+
+### `GET /health`
+
+| Status | Description | Type |
+| --- | --- | --- |
+| 204 | Up \\| running |  |
+
+## Schemas
+
+### Pet
+
+| Property | Type | Required | Description |
+| --- | --- | --- | --- |
+| `name` | string | yes |  |
+| `tags` | string[] | no |  |
+";
+        assert_eq!(markdown, expected);
+
+        let yaml = Spec::builder().yaml(Bytes::from_static(b"openapi: 3.0.0\n"));
+        assert!(spec_markdown(&yaml).is_none());
+    }
+}
@@ -0,0 +1,98 @@
+use http::HeaderValue;
+
+/// Content type of `index.html` and the other generated pages.
+pub(crate) const HTML: HeaderValue = HeaderValue::from_static("text/html");
+
+/// Content type of JSON documents, e.g. the config and JSON specs.
+pub(crate) const JSON: HeaderValue = HeaderValue::from_static("application/json");
+
+/// Content type of the Markdown rendering of the spec.
+pub(crate) const MARKDOWN: HeaderValue = HeaderValue::from_static("text/markdown; charset=utf-8");
+
+/// Content type of files whose type can't be determined.
+const OCTET_STREAM: HeaderValue = HeaderValue::from_static("application/octet-stream");
+
+/// A content type of the docs, for checking the `Content-Type` of responses without parsing it,
+/// e.g. `assert_eq!(response.headers()[CONTENT_TYPE], ContentType::html())`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType(HeaderValue);
+
+impl ContentType {
+    /// `text/html`, of `index.html`.
+    pub const fn html() -> Self {
+        Self(HTML)
+    }
+
+    /// `application/json`, of the config and JSON specs.
+    pub const fn json() -> Self {
+        Self(JSON)
+    }
+
+    /// The content type the file at `path` is served with, e.g. `text/css` for `swagger-ui.css`.
+    pub fn of(path: &str) -> Self {
+        Self(content_type(path))
+    }
+}
+
+impl From<ContentType> for HeaderValue {
+    fn from(content_type: ContentType) -> Self {
+        content_type.0
+    }
+}
+
+impl PartialEq<ContentType> for HeaderValue {
+    fn eq(&self, other: &ContentType) -> bool {
+        *self == other.0
+    }
+}
+
+/// Returns the content type to serve the file at `path` with.
+///
+/// Embedded assets are looked up in the index generated at build time, other files are guessed
+/// from their extension.
+pub(crate) fn content_type(path: &str) -> HeaderValue {
+    match swagger_ui_assets::get(path) {
+        Some(asset) => HeaderValue::from_static(asset.content_type),
+        None => guess(path),
+    }
+}
+
+fn guess(path: &str) -> HeaderValue {
+    let filename = path.rsplit('/').next().unwrap_or_default();
+    match filename.rsplit_once('.') {
+        // files without an extension, including dotfiles like `.gitignore`
+        None | Some(("", _)) => OCTET_STREAM,
+        // source maps are JSON documents
+        Some((_, "map")) => JSON,
+        Some((_, extension)) => {
+            let mime = mime_guess::from_ext(extension).first_or_octet_stream();
+            HeaderValue::from_str(mime.as_ref()).unwrap_or(OCTET_STREAM)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_type, ContentType};
+    use http::HeaderValue;
+
+    #[test]
+    fn looks_up_embedded_assets() {
+        assert_eq!(content_type("index.html"), "text/html");
+        assert_eq!(content_type("swagger-ui.css"), "text/css");
+        assert_eq!(content_type("index.html"), ContentType::html());
+        assert_eq!(
+            HeaderValue::from(ContentType::of("swagger-ui.css")),
+            "text/css"
+        );
+    }
+
+    #[test]
+    fn guesses_other_files() {
+        assert_eq!(content_type("openapi.json"), "application/json");
+        assert_eq!(content_type("swagger-ui.js.map"), "application/json");
+        assert_eq!(content_type("LICENSE"), "application/octet-stream");
+        assert_eq!(content_type("specs/.hidden"), "application/octet-stream");
+        assert_eq!(content_type("specs.v1/openapi"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,86 @@
+//! Shrinking a spec to what machines read of it.
+
+use crate::Spec;
+use serde_json::Value;
+
+/// Fields stripped from every object of the spec, besides the vendor extensions.
+const STRIPPED: [&str; 3] = ["description", "example", "examples"];
+
+/// Fields whose values map names chosen by the spec's authors to objects, whose keys are kept
+/// even if they look like a stripped field, e.g. a `description` property of a schema.
+const NAMED: [&str; 5] = [
+    "properties",
+    "patternProperties",
+    "definitions",
+    "$defs",
+    "schemas",
+];
+
+/// Returns `spec` without descriptions, examples and `x-` vendor extensions, serialized without
+/// whitespace, or `None` if the spec isn't JSON.
+///
+/// What's left still describes every operation and schema, for clients and tools that fetch the
+/// spec often and don't show it to anyone.
+pub fn minify_spec(spec: &Spec) -> Option<Spec> {
+    let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+    strip(&mut document);
+    let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+fn strip(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !(key.starts_with("x-") || STRIPPED.contains(&key.as_str())));
+            for (key, value) in object.iter_mut() {
+                match (NAMED.contains(&key.as_str()), value) {
+                    (true, Value::Object(named)) => named.values_mut().for_each(strip),
+                    (_, value) => strip(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::minify_spec;
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn strips_descriptions_examples_and_extensions() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "info": {"title": "Pets", "description": "All about pets", "x-logo": {}},
+                "paths": {"/pets": {"get": {
+                    "description": "Lists pets",
+                    "responses": {"200": {"description": "The pets", "content": {
+                        "application/json": {"example": [], "schema": {"$ref": "#/components/schemas/Pet"}}
+                    }}}
+                }}},
+                "components": {"schemas": {"Pet": {
+                    "properties": {"description": {"type": "string", "description": "About the pet"}}
+                }}}
+            }))
+            .unwrap(),
+        );
+        let minified = minify_spec(&spec).unwrap();
+        let document: Value = serde_json::from_slice(&minified.content).unwrap();
+
+        assert_eq!(document["info"], json!({"title": "Pets"}));
+        let get = &document["paths"]["/pets"]["get"];
+        assert!(get.get("description").is_none());
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"],
+            json!({"schema": {"$ref": "#/components/schemas/Pet"}})
+        );
+        // a property named like a stripped field is kept, without its own description
+        assert_eq!(
+            document["components"]["schemas"]["Pet"]["properties"]["description"],
+            json!({"type": "string"})
+        );
+    }
+}
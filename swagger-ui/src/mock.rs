@@ -0,0 +1,289 @@
+use crate::example;
+use crate::handlers::{content, head, status};
+use crate::markdown::METHODS;
+use crate::{mime, Body, Spec};
+use bytes::Bytes;
+use http::header::ALLOW;
+use http::{HeaderValue, Method, Request, Response, StatusCode};
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// A `tower::Service` answering every operation of a spec with an example response, so clients
+/// can be developed against the documented API before it exists.
+///
+/// Requests are matched against the paths of the spec, relative to where the service is mounted
+/// and, for Swagger 2.0, below the `basePath`. Each operation responds with its first successful
+/// response, or the `default` one, using the documented example or, if there is none, data
+/// generated from the schema. Paths that aren't in the spec are answered with `404 Not Found`,
+/// methods without an operation with `405 Method Not Allowed`.
+#[derive(Debug, Clone)]
+pub struct MockService {
+    document: Arc<Value>,
+}
+
+/// A response of the mock, with everything needed to send it.
+struct Mock {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Option<Value>,
+}
+
+impl MockService {
+    /// Create a mock of the JSON spec `spec`, or `None` if it isn't JSON.
+    pub fn new(spec: &Spec) -> Option<Self> {
+        let document = serde_json::from_slice(&spec.content).ok()?;
+        Some(Self {
+            document: Arc::new(document),
+        })
+    }
+
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        let path = req.uri().path();
+        let path = match self.document["basePath"].as_str() {
+            Some(base) if base != "/" => path.strip_prefix(base).unwrap_or(path),
+            _ => path,
+        };
+        let Some(item) = self.path_item(path) else {
+            return status(StatusCode::NOT_FOUND);
+        };
+
+        let method = match *req.method() {
+            // answered like `GET` unless documented separately
+            Method::HEAD if item.get("head").is_none() => "get".to_string(),
+            ref method => method.as_str().to_lowercase(),
+        };
+        let Some(operation) = item.get(method.as_str()) else {
+            let allow: Vec<String> = METHODS
+                .iter()
+                .filter(|method| item.get(**method).is_some())
+                .map(|method| method.to_uppercase())
+                .collect();
+            let mut response = status(StatusCode::METHOD_NOT_ALLOWED);
+            if let Ok(allow) = HeaderValue::from_str(&allow.join(", ")) {
+                response.headers_mut().insert(ALLOW, allow);
+            }
+            return response;
+        };
+
+        let mock = self.mock(operation);
+        let mut response = match (mock.body, mock.content_type) {
+            (Some(body), Some(content_type)) => {
+                let body = match body {
+                    // text media types get their example as is
+                    Value::String(text) if !is_json(&content_type) => text.into_bytes(),
+                    body => serde_json::to_vec(&body).expect("Could not serialize mock as JSON."),
+                };
+                content(content_type, Bytes::from(body).into())
+            }
+            _ => status(mock.status),
+        };
+        *response.status_mut() = mock.status;
+        match *req.method() {
+            Method::HEAD => head(response),
+            _ => response,
+        }
+    }
+
+    /// Returns the path item whose template matches `path`, preferring templates with fewer
+    /// parameters, so `/pets/mine` wins over `/pets/{id}`.
+    fn path_item(&self, path: &str) -> Option<&Value> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        self.document["paths"]
+            .as_object()?
+            .iter()
+            .filter_map(|(template, item)| {
+                let parts: Vec<&str> = template.trim_matches('/').split('/').collect();
+                if parts.len() != segments.len() {
+                    return None;
+                }
+                let mut parameters = 0;
+                for (part, segment) in parts.iter().zip(&segments) {
+                    if part.starts_with('{') && part.ends_with('}') && !segment.is_empty() {
+                        parameters += 1;
+                    } else if part != segment {
+                        return None;
+                    }
+                }
+                Some((parameters, item))
+            })
+            .min_by_key(|(parameters, _)| *parameters)
+            .map(|(_, item)| item)
+    }
+
+    fn mock(&self, operation: &Value) -> Mock {
+        let empty = Map::new();
+        let responses = operation["responses"].as_object().unwrap_or(&empty);
+        let chosen = responses
+            .iter()
+            .find(|(code, _)| code.starts_with('2'))
+            .or_else(|| responses.iter().find(|(code, _)| *code == "default"));
+        let Some((code, response)) = chosen else {
+            return Mock {
+                status: StatusCode::OK,
+                content_type: None,
+                body: None,
+            };
+        };
+        let status = match code.as_str() {
+            // codes like `2XX`
+            code if code.len() == 3 && code.ends_with("XX") => StatusCode::OK,
+            code => code.parse().unwrap_or(StatusCode::OK),
+        };
+        let response = example::resolve(&self.document, response);
+
+        // OpenAPI 3: `content` by media type
+        if let Some((media_type, content)) = response["content"]
+            .as_object()
+            .and_then(|content| content.iter().next())
+        {
+            let example = content.get("example").cloned().or_else(|| {
+                let (_, example) = content["examples"].as_object()?.iter().next()?;
+                example::resolve(&self.document, example)
+                    .get("value")
+                    .cloned()
+            });
+            let body = example.or_else(|| {
+                content
+                    .get("schema")
+                    .map(|schema| example::generate(&self.document, schema))
+            });
+            return Mock {
+                status,
+                content_type: HeaderValue::from_str(media_type).ok(),
+                body,
+            };
+        }
+
+        // Swagger 2.0: `examples` by media type and one `schema`
+        let produces = operation["produces"]
+            .as_array()
+            .or_else(|| self.document["produces"].as_array())
+            .and_then(|produces| produces.first())
+            .and_then(Value::as_str)
+            .unwrap_or("application/json");
+        if let Some((media_type, example)) = response["examples"]
+            .as_object()
+            .and_then(|examples| examples.iter().next())
+        {
+            return Mock {
+                status,
+                content_type: HeaderValue::from_str(media_type).ok(),
+                body: Some(example.clone()),
+            };
+        }
+        Mock {
+            status,
+            content_type: HeaderValue::from_str(produces).ok(),
+            body: response
+                .get("schema")
+                .map(|schema| example::generate(&self.document, schema)),
+        }
+    }
+}
+
+fn is_json(content_type: &HeaderValue) -> bool {
+    content_type
+        .to_str()
+        .is_ok_and(|content_type| content_type == mime::JSON || content_type.ends_with("+json"))
+}
+
+impl<B> Service<Request<B>> for MockService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockService;
+    use crate::Spec;
+    use bytes::Bytes;
+    use http::header::{ALLOW, CONTENT_TYPE};
+    use http::{Method, Request, StatusCode};
+    use serde_json::{json, Value};
+
+    fn request(method: Method, uri: &str) -> Request<()> {
+        Request::builder().method(method).uri(uri).body(()).unwrap()
+    }
+
+    #[test]
+    fn answers_operations_with_examples() {
+        let spec = Spec::builder().json(Bytes::from(
+                serde_json::to_vec(&json!({
+                    "openapi": "3.0.3",
+                    "paths": {
+                        "/pets/{id}": {"get": {"responses": {
+                            "404": {"description": "Not found"},
+                            "200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}
+                        }}},
+                        "/pets/mine": {"get": {"responses": {"200": {"content": {"text/plain": {"example": "mine"}}}}}},
+                        "/pets": {"post": {"responses": {"201": {"content": {"application/json": {"example": {"id": 7}}}}}}}
+                    },
+                    "components": {"schemas": {"Pet": {
+                        "properties": {
+                            "id": {"type": "integer", "format": "int64"},
+                            "born": {"type": "string", "format": "date"},
+                            "kind": {"type": "string", "enum": ["cat", "dog"]},
+                            "tags": {"type": "array", "items": {"type": "string"}}
+                        }
+                    }}}
+                }))
+                .unwrap(),
+            ));
+        let mock = MockService::new(&spec).unwrap();
+        let json = |method: Method, uri: &str| {
+            let response = mock.handle(&request(method, uri));
+            let status = response.status();
+            let body: Value = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+            (status, body)
+        };
+
+        assert_eq!(
+            json(Method::GET, "/pets/1"),
+            (
+                StatusCode::OK,
+                json!({"id": 0, "born": "2024-01-01", "kind": "cat", "tags": ["string"]})
+            )
+        );
+        assert_eq!(
+            json(Method::POST, "/pets"),
+            (StatusCode::CREATED, json!({"id": 7}))
+        );
+
+        let response = mock.handle(&request(Method::GET, "/pets/mine"));
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(response.into_body().into_bytes(), "mine");
+
+        let response = mock.handle(&request(Method::DELETE, "/pets"));
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "POST");
+        assert_eq!(
+            mock.handle(&request(Method::GET, "/owners")).status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn mocks_swagger_2_below_the_base_path() {
+        let mock =
+            MockService::new(&crate::swagger_spec_file!("../examples/openapi.json")).unwrap();
+        let response = mock.handle(&request(Method::GET, "/v2/pet/1"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let pet: Value = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(pet["name"], "doggie");
+    }
+}
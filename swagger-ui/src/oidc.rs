@@ -0,0 +1,610 @@
+//! Protecting the docs with an OpenID Connect login.
+
+use crate::handlers::{redirect, status};
+use crate::Body;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use http::header::{COOKIE, LOCATION, SET_COOKIE};
+use http::{HeaderMap, HeaderValue, Response, StatusCode, Uri};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Cookie of an authenticated session.
+const SESSION_COOKIE: &str = "swagger_ui_session";
+
+/// Cookie of a login in progress, holding what the callback checks the provider's answer by.
+const LOGIN_COOKIE: &str = "swagger_ui_login";
+
+/// How long a login at the provider may take.
+const LOGIN_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// How an [`OidcGuard`] logs users in.
+#[derive(Clone)]
+pub struct Oidc {
+    /// Url of the provider, which its configuration is discovered below, e.g.
+    /// `https://accounts.example.com`. Only `http` urls of loopback hosts are taken besides
+    /// `https` ones, as the tokens and the client secret are sent there.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Public url of the callback the provider redirects to after the login, below the docs,
+    /// e.g. `https://api.example.com/docs/oidc/callback`.
+    pub redirect_url: String,
+    /// Default: `openid email`.
+    pub scopes: Vec<String>,
+    /// Secret key the cookies are signed with, keep it secret.
+    pub session_key: Vec<u8>,
+    /// How long a login is valid. Default: 8 hours.
+    pub session_duration: Duration,
+}
+
+impl fmt::Debug for Oidc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Oidc")
+            .field("issuer", &self.issuer)
+            .field("client_id", &self.client_id)
+            .field("redirect_url", &self.redirect_url)
+            .field("scopes", &self.scopes)
+            .field("session_duration", &self.session_duration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Oidc {
+    /// Log in with the client `client_id` at `issuer`, which redirects back to `redirect_url`.
+    pub fn new(
+        issuer: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_url: impl Into<String>,
+        session_key: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            issuer: issuer.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_url: redirect_url.into(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            session_key: session_key.into(),
+            session_duration: Duration::from_secs(8 * 60 * 60),
+        }
+    }
+}
+
+/// Who logged in, inserted as a request extension for the handlers behind an [`OidcGuard`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidcUser {
+    /// The `sub` claim of the ID token.
+    pub subject: String,
+    /// The `email` claim of the ID token, if it has one.
+    pub email: Option<String>,
+}
+
+/// The signed content of the session cookie.
+#[derive(Serialize, Deserialize)]
+struct Session {
+    #[serde(flatten)]
+    user: OidcUser,
+    expires: u64,
+}
+
+/// The signed content of the login cookie.
+#[derive(Serialize, Deserialize)]
+struct Login {
+    state: String,
+    nonce: String,
+    verifier: String,
+    /// Where the user was going, to return them there.
+    target: String,
+    expires: u64,
+}
+
+/// The endpoints of the provider, from its discovered configuration.
+struct Provider {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+}
+
+/// Lets only users logged in at an OpenID Connect provider through, see [`check`](Self::check).
+///
+/// Logins use the authorization code flow with PKCE. The ID token is taken from the token
+/// endpoint directly, over TLS, which the provider is authenticated by instead of the signature
+/// of the token.
+#[derive(Clone)]
+pub struct OidcGuard {
+    oidc: Arc<Oidc>,
+    client: reqwest::Client,
+    provider: Arc<RwLock<Option<Arc<Provider>>>>,
+}
+
+impl fmt::Debug for OidcGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OidcGuard")
+            .field("oidc", &self.oidc)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OidcGuard {
+    /// Create a guard logging users in as `oidc` configures.
+    pub fn new(oidc: Oidc) -> Self {
+        Self {
+            oidc: Arc::new(oidc),
+            client: reqwest::Client::new(),
+            provider: Default::default(),
+        }
+    }
+
+    /// Checks a request for the docs, returning the logged in user if it may pass.
+    ///
+    /// Otherwise, returns the response to send instead: a redirect to the provider for users
+    /// that aren't logged in, and for requests to the callback a redirect back to where the
+    /// user was going with the session cookie, or `400 Bad Request` if the login failed. Answers
+    /// `502 Bad Gateway` if the provider can't be reached or isn't served over `https`.
+    pub async fn check(&self, uri: &Uri, headers: &HeaderMap) -> Result<OidcUser, Response<Body>> {
+        let callback = self.oidc.redirect_url.parse::<Uri>().ok();
+        if callback.is_some_and(|callback| callback.path() == uri.path()) {
+            return Err(self.callback(uri, headers).await);
+        }
+        if let Some(session) = self.verify::<Session>(cookie(headers, SESSION_COOKIE)) {
+            if session.expires > now() {
+                return Ok(session.user);
+            }
+        }
+        Err(self.login(uri).await)
+    }
+
+    /// Redirects to the provider to log in, and back to `uri` afterwards.
+    async fn login(&self, uri: &Uri) -> Response<Body> {
+        let Some(provider) = self.provider().await else {
+            return status(StatusCode::BAD_GATEWAY);
+        };
+        let login = Login {
+            state: random(),
+            nonce: random(),
+            verifier: random(),
+            target: uri
+                .path_and_query()
+                .map(ToString::to_string)
+                .filter(|target| is_local(target))
+                .unwrap_or_else(|| "/".to_string()),
+            expires: now() + LOGIN_DURATION.as_secs(),
+        };
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(login.verifier.as_bytes()));
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.oidc.client_id)
+            .append_pair("redirect_uri", &self.oidc.redirect_url)
+            .append_pair("scope", &self.oidc.scopes.join(" "))
+            .append_pair("state", &login.state)
+            .append_pair("nonce", &login.nonce)
+            .append_pair("code_challenge", &challenge)
+            .append_pair("code_challenge_method", "S256")
+            .finish();
+        let separator = match provider.authorization_endpoint.contains('?') {
+            true => '&',
+            false => '?',
+        };
+
+        let mut response = status(StatusCode::SEE_OTHER);
+        let location = format!("{}{separator}{query}", provider.authorization_endpoint);
+        match HeaderValue::from_str(&location) {
+            Ok(location) => response.headers_mut().insert(LOCATION, location),
+            Err(_) => return status(StatusCode::BAD_GATEWAY),
+        };
+        self.set_cookie(&mut response, LOGIN_COOKIE, &login, LOGIN_DURATION);
+        response
+    }
+
+    /// Exchanges the code the provider redirected back with for the ID token of the user.
+    async fn callback(&self, uri: &Uri, headers: &HeaderMap) -> Response<Body> {
+        let (mut code, mut state) = (None, None);
+        for (key, value) in form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+            match key.as_ref() {
+                "code" => code = Some(value.into_owned()),
+                "state" => state = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let login = self
+            .verify::<Login>(cookie(headers, LOGIN_COOKIE))
+            .filter(|login| login.expires > now() && state.as_ref() == Some(&login.state));
+        let (Some(code), Some(login)) = (code, login) else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+        let Some(provider) = self.provider().await else {
+            return status(StatusCode::BAD_GATEWAY);
+        };
+
+        let token = self
+            .client
+            .post(&provider.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", &code),
+                ("redirect_uri", &self.oidc.redirect_url),
+                ("client_id", &self.oidc.client_id),
+                ("client_secret", &self.oidc.client_secret),
+                ("code_verifier", &login.verifier),
+            ])
+            .send()
+            .await;
+        let body = match token {
+            Ok(token) if token.status().is_success() => token.bytes().await.ok(),
+            Ok(_) => return status(StatusCode::BAD_REQUEST),
+            Err(_) => None,
+        };
+        let Some(body) = body else {
+            return status(StatusCode::BAD_GATEWAY);
+        };
+        let Some(user) = self.user(&provider, &login, &body) else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+
+        let mut response = redirect(&login.target);
+        *response.status_mut() = StatusCode::SEE_OTHER;
+        let session = Session {
+            user,
+            expires: now() + self.oidc.session_duration.as_secs(),
+        };
+        self.set_cookie(
+            &mut response,
+            SESSION_COOKIE,
+            &session,
+            self.oidc.session_duration,
+        );
+        self.set_cookie(&mut response, LOGIN_COOKIE, &(), Duration::ZERO);
+        response
+    }
+
+    /// Returns the user of the ID token in the response of the token endpoint, if it was issued
+    /// by the provider to this client for `login`.
+    fn user(&self, provider: &Provider, login: &Login, token: &[u8]) -> Option<OidcUser> {
+        let token: Value = serde_json::from_slice(token).ok()?;
+        let payload = token["id_token"].as_str()?.split('.').nth(1)?;
+        let claims: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        let audience = match &claims["aud"] {
+            Value::Array(audiences) => audiences.iter().any(|aud| aud == &*self.oidc.client_id),
+            aud => aud == &*self.oidc.client_id,
+        };
+        let valid = claims["iss"] == *provider.issuer
+            && audience
+            && claims["exp"].as_u64().is_some_and(|exp| exp > now())
+            && claims["nonce"] == *login.nonce;
+        let subject = claims["sub"].as_str()?;
+        valid.then(|| OidcUser {
+            subject: subject.to_string(),
+            email: claims["email"].as_str().map(str::to_string),
+        })
+    }
+
+    /// Returns the endpoints of the provider, discovering them on first use, or `None` if they
+    /// aren't `https` or the discovery document is of another issuer.
+    async fn provider(&self) -> Option<Arc<Provider>> {
+        if let Some(provider) = self.provider.read().unwrap().as_ref() {
+            return Some(provider.clone());
+        }
+        if !is_secure(&self.oidc.issuer) {
+            return None;
+        }
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.oidc.issuer.trim_end_matches('/')
+        );
+        let response = self.client.get(url).send().await.ok()?;
+        let configuration: Value =
+            serde_json::from_slice(&response.error_for_status().ok()?.bytes().await.ok()?).ok()?;
+        let provider = Arc::new(Provider {
+            issuer: configuration["issuer"].as_str()?.to_string(),
+            authorization_endpoint: configuration["authorization_endpoint"]
+                .as_str()?
+                .to_string(),
+            token_endpoint: configuration["token_endpoint"].as_str()?.to_string(),
+        });
+        if provider.issuer.trim_end_matches('/') != self.oidc.issuer.trim_end_matches('/') {
+            return None;
+        }
+        if !is_secure(&provider.authorization_endpoint) || !is_secure(&provider.token_endpoint) {
+            return None;
+        }
+        *self.provider.write().unwrap() = Some(provider.clone());
+        Some(provider)
+    }
+
+    /// Sets the cookie `name` to `value`, signed, for `duration`.
+    fn set_cookie(
+        &self,
+        response: &mut Response<Body>,
+        name: &str,
+        value: &impl Serialize,
+        duration: Duration,
+    ) {
+        let value = serde_json::to_vec(value).expect("Could not serialize cookie as JSON.");
+        let value = URL_SAFE_NO_PAD.encode(value);
+        let secure = match self.oidc.redirect_url.starts_with("https:") {
+            true => "; Secure",
+            false => "",
+        };
+        let cookie = format!(
+            "{name}={value}.{}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{secure}",
+            self.sign(&value),
+            duration.as_secs()
+        );
+        let cookie = HeaderValue::from_str(&cookie).expect("cookie is a valid header value");
+        response.headers_mut().append(SET_COOKIE, cookie);
+    }
+
+    fn sign(&self, value: &str) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(value).finalize().into_bytes())
+    }
+
+    fn mac(&self, value: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.oidc.session_key)
+            .expect("HMAC takes keys of any size");
+        mac.update(value.as_bytes());
+        mac
+    }
+
+    /// Returns the content of a cookie set by [`set_cookie`](Self::set_cookie), if its
+    /// signature is valid.
+    fn verify<T: for<'de> Deserialize<'de>>(&self, cookie: Option<&str>) -> Option<T> {
+        let (value, signature) = cookie?.split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        self.mac(value).verify_slice(&signature).ok()?;
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(value).ok()?).ok()
+    }
+}
+
+/// Returns the value of the cookie `name` of a request.
+fn cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(';'))
+        .find_map(|cookie| cookie.trim().strip_prefix(name)?.strip_prefix('='))
+}
+
+/// Whether `target` is a path on this site, to return to after the login. Browsers take `//host`
+/// and `/\host` for urls of another site, which some servers also decode `/%2F` and `/%5C` to.
+fn is_local(target: &str) -> bool {
+    let Some(rest) = target.strip_prefix('/') else {
+        return false;
+    };
+    let next = match rest.get(..3) {
+        Some(escape) if escape.starts_with('%') => {
+            u8::from_str_radix(&escape[1..], 16).map_or('%', char::from)
+        }
+        _ => rest.chars().next().unwrap_or_default(),
+    };
+    !matches!(next, '/' | '\\')
+}
+
+/// Whether `url` is `https`, or `http` of a loopback host such as a provider for development.
+fn is_secure(url: &str) -> bool {
+    let Ok(url) = url.parse::<Uri>() else {
+        return false;
+    };
+    let loopback = url.host().is_some_and(|host| {
+        host == "localhost"
+            || host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback())
+    });
+    match url.scheme_str() {
+        Some("https") => true,
+        Some("http") => loopback,
+        _ => false,
+    }
+}
+
+/// Returns 32 random bytes, encoded.
+fn random() -> String {
+    let mut bytes = [0; 32];
+    getrandom::getrandom(&mut bytes).expect("Could not get random bytes.");
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_local, is_secure, Oidc, OidcGuard, OidcUser};
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use http::header::{COOKIE, LOCATION, SET_COOKIE};
+    use http::{HeaderMap, StatusCode, Uri};
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    /// Returns the `name=value` pairs of the cookies `headers` set.
+    fn cookies(headers: &HeaderMap) -> String {
+        headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|cookie| cookie.to_str().unwrap().split(';').next().unwrap())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    #[tokio::test]
+    async fn logs_in_through_the_provider() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let (nonce_tx, nonce_rx) = oneshot::channel::<String>();
+        let provider = issuer.clone();
+        tokio::spawn(async move {
+            let mut nonce = Some(nonce_rx);
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 8192];
+                let len = stream.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..len]).to_string();
+                let body = if request.starts_with("GET /.well-known/openid-configuration") {
+                    json!({
+                        "issuer": provider,
+                        "authorization_endpoint": format!("{provider}/authorize"),
+                        "token_endpoint": format!("{provider}/token"),
+                    })
+                } else {
+                    assert!(request.contains("code=abc") && request.contains("code_verifier="));
+                    let claims = json!({
+                        "iss": provider,
+                        "aud": "docs",
+                        "sub": "alice",
+                        "exp": u64::MAX / 2,
+                        "nonce": nonce.take().unwrap().await.unwrap(),
+                    });
+                    let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+                    json!({"id_token": format!("e30.{payload}.c2ln")})
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let guard = OidcGuard::new(Oidc::new(
+            issuer.clone(),
+            "docs",
+            "secret",
+            "http://localhost/docs/oidc/callback",
+            b"key".to_vec(),
+        ));
+
+        let docs: Uri = "/docs/index.html".parse().unwrap();
+        let login = guard.check(&docs, &HeaderMap::new()).await.unwrap_err();
+        assert_eq!(login.status(), StatusCode::SEE_OTHER);
+        let location = login.headers()[LOCATION]
+            .to_str()
+            .unwrap()
+            .parse::<Uri>()
+            .unwrap();
+        assert_eq!(location.path(), "/authorize");
+        let query: Vec<(String, String)> =
+            form_urlencoded::parse(location.query().unwrap().as_bytes())
+                .into_owned()
+                .collect();
+        let param = |name: &str| query.iter().find(|(key, _)| key == name).unwrap().1.clone();
+        assert_eq!(param("code_challenge_method"), "S256");
+        nonce_tx.send(param("nonce")).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, cookies(login.headers()).parse().unwrap());
+        let callback = format!("/docs/oidc/callback?code=abc&state={}", param("state"));
+        let session = guard
+            .check(&callback.parse().unwrap(), &headers)
+            .await
+            .unwrap_err();
+        assert_eq!(session.status(), StatusCode::SEE_OTHER);
+        assert_eq!(session.headers()[LOCATION], "/docs/index.html");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, cookies(session.headers()).parse().unwrap());
+        let user = guard.check(&docs, &headers).await.unwrap();
+        assert_eq!(
+            user,
+            OidcUser {
+                subject: "alice".to_string(),
+                email: None
+            }
+        );
+
+        // a forged session doesn't pass
+        let mut headers = HeaderMap::new();
+        let forged = URL_SAFE_NO_PAD.encode(br#"{"subject":"mallory","expires":99999999999}"#);
+        headers.insert(
+            COOKIE,
+            format!("swagger_ui_session={forged}.c2ln").parse().unwrap(),
+        );
+        assert!(guard.check(&docs, &headers).await.is_err());
+    }
+
+    #[test]
+    fn returns_only_to_paths_on_this_site() {
+        assert!(is_local("/docs/index.html?tab=1"));
+        assert!(is_local("/"));
+        assert!(is_local("/%41"));
+        for target in [
+            "//evil.com",
+            "/\\evil.com",
+            "/%5Cevil.com",
+            "/%5cevil.com",
+            "/%2F/evil.com",
+        ] {
+            assert!(!is_local(target), "{target}");
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_providers_without_tls() {
+        assert!(is_secure("https://accounts.example.com"));
+        assert!(is_secure("http://localhost:8080"));
+        assert!(is_secure("http://127.0.0.1:8080"));
+        assert!(is_secure("http://[::1]:8080"));
+        assert!(!is_secure("http://accounts.example.com"));
+        assert!(!is_secure("http://127.0.0.1.example.com"));
+
+        let guard = OidcGuard::new(Oidc::new(
+            "http://accounts.example.com",
+            "docs",
+            "secret",
+            "https://api.example.com/docs/oidc/callback",
+            b"key".to_vec(),
+        ));
+        let docs = "/docs/index.html".parse().unwrap();
+        let login = guard.check(&docs, &HeaderMap::new()).await.unwrap_err();
+        assert_eq!(login.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn refuses_discovery_documents_of_another_issuer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let _ = stream.read(&mut request).await.unwrap();
+            let body = json!({
+                "issuer": "http://localhost:1",
+                "authorization_endpoint": "http://localhost:1/authorize",
+                "token_endpoint": "http://localhost:1/token",
+            })
+            .to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let guard = OidcGuard::new(Oidc::new(
+            issuer,
+            "docs",
+            "secret",
+            "http://localhost/docs/oidc/callback",
+            b"key".to_vec(),
+        ));
+        let docs = "/docs/index.html".parse().unwrap();
+        let login = guard.check(&docs, &HeaderMap::new()).await.unwrap_err();
+        assert_eq!(login.status(), StatusCode::BAD_GATEWAY);
+    }
+}
@@ -0,0 +1,224 @@
+//! Conversion of a spec into a Postman collection.
+
+use crate::collection::{substitute, Auth, Collection, Parameter, RequestTemplate};
+use crate::Spec;
+use serde_json::{json, Map, Value};
+
+/// Schema of the collections generated, Postman's v2.1 format.
+const SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Converts `spec` into a Postman v2.1 collection, or returns `None` if the spec isn't JSON.
+///
+/// Requests are grouped into a folder per tag and sent to `{{baseUrl}}`, a collection variable
+/// holding the url of the first server, or of the Swagger 2.0 `host` and `basePath`. Server
+/// variables become collection variables too. Security schemes map to Postman auth, with the
+/// credentials left to the variables `username`, `password`, `bearerToken`, `apiKey` and
+/// `accessToken`.
+pub fn postman_collection(spec: &Spec) -> Option<String> {
+    let collection = Collection::new(spec)?;
+
+    let mut info = Map::new();
+    info.insert("name".into(), json!(collection.name));
+    if let Some(description) = &collection.description {
+        info.insert("description".into(), json!(description));
+    }
+    info.insert("schema".into(), json!(SCHEMA));
+
+    let base_url = substitute(&collection.base_url, |name| format!("{{{{{name}}}}}"));
+    let mut variables = vec![json!({"key": "baseUrl", "value": base_url})];
+    variables.extend(collection.variables.iter().map(variable));
+    let auths = collection.auth.iter().chain(
+        collection
+            .requests()
+            .filter_map(|request| request.auth.as_ref()),
+    );
+    for auth in auths {
+        for key in auth.credentials() {
+            let variable = json!({"key": key, "value": ""});
+            if !variables.contains(&variable) {
+                variables.push(variable);
+            }
+        }
+    }
+
+    let folders: Vec<Value> = collection
+        .folders
+        .iter()
+        .map(|folder| {
+            let mut item = Map::new();
+            item.insert("name".into(), json!(folder.name));
+            if let Some(description) = &folder.description {
+                item.insert("description".into(), json!(description));
+            }
+            let requests: Vec<Value> = folder.requests.iter().map(request).collect();
+            item.insert("item".into(), json!(requests));
+            Value::Object(item)
+        })
+        .collect();
+
+    let mut out = Map::new();
+    out.insert("info".into(), Value::Object(info));
+    out.insert("item".into(), json!(folders));
+    if let Some(auth) = &collection.auth {
+        out.insert("auth".into(), postman_auth(auth));
+    }
+    out.insert("variable".into(), json!(variables));
+    Some(serde_json::to_string_pretty(&out).expect("Could not serialize collection as JSON."))
+}
+
+fn request(template: &RequestTemplate) -> Value {
+    let path = substitute(&template.path, |name| format!(":{name}"));
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let query: Vec<String> = template
+        .query
+        .iter()
+        .map(|parameter| format!("{}={}", parameter.name, parameter.value))
+        .collect();
+    let raw = match query.is_empty() {
+        true => format!("{{{{baseUrl}}}}{path}"),
+        false => format!("{{{{baseUrl}}}}{path}?{}", query.join("&")),
+    };
+
+    let mut url = Map::new();
+    url.insert("raw".into(), json!(raw));
+    url.insert("host".into(), json!(["{{baseUrl}}"]));
+    url.insert("path".into(), json!(segments));
+    if !template.query.is_empty() {
+        url.insert("query".into(), json!(parameters(&template.query)));
+    }
+    if !template.path_parameters.is_empty() {
+        url.insert(
+            "variable".into(),
+            json!(parameters(&template.path_parameters)),
+        );
+    }
+
+    let mut request = Map::new();
+    request.insert("method".into(), json!(template.method));
+    request.insert("header".into(), json!(parameters(&template.headers)));
+    request.insert("url".into(), Value::Object(url));
+    if let Some(body) = &template.body {
+        let language = match body.content_type.as_str() {
+            content_type if content_type.contains("json") => "json",
+            content_type if content_type.contains("xml") => "xml",
+            _ => "text",
+        };
+        request.insert(
+            "body".into(),
+            json!({"mode": "raw", "raw": body.text, "options": {"raw": {"language": language}}}),
+        );
+    }
+    if let Some(description) = &template.description {
+        request.insert("description".into(), json!(description));
+    }
+    if let Some(auth) = &template.auth {
+        request.insert("auth".into(), postman_auth(auth));
+    }
+    json!({"name": template.name, "request": request})
+}
+
+fn parameters(parameters: &[Parameter]) -> Vec<Value> {
+    parameters.iter().map(variable).collect()
+}
+
+fn variable(parameter: &Parameter) -> Value {
+    let mut variable = Map::new();
+    variable.insert("key".into(), json!(parameter.name));
+    variable.insert("value".into(), json!(parameter.value));
+    if let Some(description) = &parameter.description {
+        variable.insert("description".into(), json!(description));
+    }
+    Value::Object(variable)
+}
+
+fn postman_auth(auth: &Auth) -> Value {
+    let entry = |key: &str, value: &str| json!({"key": key, "value": value, "type": "string"});
+    match auth {
+        Auth::None => json!({"type": "noauth"}),
+        Auth::Basic => json!({"type": "basic", "basic": [
+            entry("username", "{{username}}"),
+            entry("password", "{{password}}"),
+        ]}),
+        Auth::Bearer => json!({"type": "bearer", "bearer": [entry("token", "{{bearerToken}}")]}),
+        Auth::ApiKey { name, in_query } => json!({"type": "apikey", "apikey": [
+            entry("key", name),
+            entry("value", "{{apiKey}}"),
+            entry("in", if *in_query { "query" } else { "header" }),
+        ]}),
+        Auth::OAuth2 => json!({"type": "oauth2", "oauth2": [
+            entry("accessToken", "{{accessToken}}"),
+            entry("addTokenTo", "header"),
+        ]}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::postman_collection;
+    use crate::Spec;
+    use bytes::Bytes;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn converts_operations_into_requests() {
+        let spec = Spec::builder().json(Bytes::from(
+                serde_json::to_vec(&json!({
+                    "openapi": "3.0.3",
+                    "info": {"title": "Pets", "version": "1"},
+                    "servers": [{"url": "https://{region}.example.com/v1", "variables": {"region": {"default": "eu"}}}],
+                    "security": [{"token": []}],
+                    "paths": {
+                        "/pets/{id}": {"put": {
+                            "tags": ["pets"],
+                            "summary": "Update a pet",
+                            "parameters": [
+                                {"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}},
+                                {"name": "dry", "in": "query", "example": true}
+                            ],
+                            "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}},
+                            "responses": {"200": {"description": "Updated"}}
+                        }},
+                        "/health": {"get": {"security": [], "responses": {"200": {"description": "Up"}}}}
+                    },
+                    "components": {
+                        "schemas": {"Pet": {"properties": {"name": {"type": "string", "example": "Rex"}}}},
+                        "securitySchemes": {"token": {"type": "http", "scheme": "bearer"}}
+                    }
+                }))
+                .unwrap(),
+            ));
+
+        let collection: Value = serde_json::from_str(&postman_collection(&spec).unwrap()).unwrap();
+        assert_eq!(
+            collection["variable"],
+            json!([
+                {"key": "baseUrl", "value": "https://{{region}}.example.com/v1"},
+                {"key": "region", "value": "eu"},
+                {"key": "bearerToken", "value": ""}
+            ])
+        );
+        assert_eq!(collection["auth"]["type"], "bearer");
+
+        // `json!` sorts the paths, so the untagged one comes first
+        let update = &collection["item"][1]["item"][0];
+        assert_eq!(collection["item"][1]["name"], "pets");
+        assert_eq!(update["name"], "Update a pet");
+        let request = &update["request"];
+        assert_eq!(request["method"], "PUT");
+        assert_eq!(request["url"]["raw"], "{{baseUrl}}/pets/:id?dry=true");
+        assert_eq!(request["url"]["path"], json!(["pets", ":id"]));
+        assert_eq!(
+            request["url"]["variable"],
+            json!([{"key": "id", "value": ""}])
+        );
+        assert_eq!(
+            request["header"],
+            json!([{"key": "Content-Type", "value": "application/json"}])
+        );
+        assert_eq!(request["body"]["raw"], "{\n  \"name\": \"Rex\"\n}");
+
+        let health = &collection["item"][0]["item"][0];
+        assert_eq!(health["name"], "GET /health");
+        assert_eq!(health["request"]["auth"], json!({"type": "noauth"}));
+    }
+}
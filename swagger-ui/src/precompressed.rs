@@ -0,0 +1,101 @@
+//! Specs compressed at build time, served without compressing them on every request.
+
+use crate::handlers::content;
+use crate::{mime, Body, Spec};
+use bytes::Bytes;
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+use http::{HeaderMap, HeaderValue, Response};
+
+/// Content of a spec compressed at build time, e.g. by
+/// `swagger_spec_file!("openapi.json", gzip)`, which is sent as it is to clients accepting its
+/// encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precompressed {
+    /// The content coding as in `Content-Encoding`, `gzip` or `br`.
+    pub encoding: &'static str,
+    /// The compressed content.
+    pub content: &'static [u8],
+    /// The length of the content before compression.
+    pub raw_len: usize,
+    /// The 64-bit FNV-1a hash of the content before compression, see
+    /// [`DocService::with_spec_headers`](crate::DocService::with_spec_headers).
+    pub digest: u64,
+}
+
+impl Precompressed {
+    /// Returns the content before compression, failing if it isn't valid for its encoding.
+    #[cfg(feature = "compression")]
+    pub fn decompress(&self) -> std::io::Result<Vec<u8>> {
+        use std::io::Read;
+
+        let mut raw = Vec::with_capacity(self.raw_len);
+        match self.encoding {
+            "gzip" => flate2::read::GzDecoder::new(self.content).read_to_end(&mut raw)?,
+            "br" => {
+                brotli_decompressor::Decompressor::new(self.content, 4096).read_to_end(&mut raw)?
+            }
+            encoding => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("unknown content coding `{encoding}`"),
+                ))
+            }
+        };
+        Ok(raw)
+    }
+}
+
+/// Returns whether the `Accept-Encoding` of `headers` accepts `encoding`, by name or `*`.
+pub(crate) fn accepts(headers: &HeaderMap, encoding: &str) -> bool {
+    let mut named = None;
+    let mut any = None;
+    let values = headers.get_all(ACCEPT_ENCODING).iter();
+    for item in values.flat_map(|value| value.to_str().unwrap_or_default().split(',')) {
+        let mut params = item.split(';');
+        let name = params.next().unwrap_or_default().trim();
+        let quality = params
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .and_then(|quality| quality.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if name.eq_ignore_ascii_case(encoding) {
+            named = Some(quality > 0.0);
+        } else if name == "*" {
+            any = Some(quality > 0.0);
+        }
+    }
+    named.or(any).unwrap_or(false)
+}
+
+/// Serves the compressed content of `spec` with its content type and `Content-Encoding`.
+pub(crate) fn serve(spec: &Spec, precompressed: &Precompressed) -> Response<Body> {
+    let mut response = content(
+        HeaderValue::from_str(&spec.content_type).unwrap_or(mime::JSON),
+        Bytes::from_static(precompressed.content).into(),
+    );
+    response.headers_mut().insert(
+        CONTENT_ENCODING,
+        HeaderValue::from_static(precompressed.encoding),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::accepts;
+    use http::header::ACCEPT_ENCODING;
+    use http::HeaderMap;
+
+    #[test]
+    fn negotiates_the_encoding() {
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT_ENCODING, value.parse().unwrap());
+            headers
+        };
+        assert!(accepts(&headers("gzip, deflate, br"), "br"));
+        assert!(accepts(&headers("*"), "gzip"));
+        assert!(!accepts(&headers("gzip;q=0, *"), "gzip"));
+        assert!(!accepts(&headers("identity"), "gzip"));
+        assert!(!accepts(&HeaderMap::new(), "gzip"));
+    }
+}
@@ -0,0 +1,497 @@
+//! Forwarding of try-it-out requests server-side, for APIs that don't allow the docs' origin.
+
+use crate::handlers::status;
+use crate::Body;
+use bytes::{Bytes, BytesMut};
+use http::header::{
+    HeaderName, CONNECTION, CONTENT_LENGTH, COOKIE, HOST, ORIGIN, PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION, REFERER, RETRY_AFTER, SET_COOKIE, TE, TRAILER, TRANSFER_ENCODING, UPGRADE,
+};
+use http::{HeaderValue, Method, Request, Response, StatusCode};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_service::Service;
+
+/// Wraps `SwaggerUIBundle` so every request to another origin goes through `./proxy?url=...`.
+pub(crate) const INTERCEPTOR: &str = r#"<script>
+(function () {
+  var proxy = new URL("./proxy", document.baseURI).href;
+  var bundle = window.SwaggerUIBundle;
+  window.SwaggerUIBundle = Object.assign(function (options) {
+    var intercept = options.requestInterceptor;
+    options.requestInterceptor = function (request) {
+      var url = new URL(request.url, document.baseURI);
+      if (url.origin !== location.origin) {
+        request.url = proxy + "?url=" + encodeURIComponent(url.href);
+      }
+      return intercept ? intercept(request) : request;
+    };
+    return bundle(options);
+  }, bundle);
+})();
+</script>
+"#;
+
+/// Headers that only apply to one connection, or that carry credentials of the docs' origin,
+/// which are never forwarded in either direction.
+const FILTERED_HEADERS: [HeaderName; 12] = [
+    CONNECTION,
+    CONTENT_LENGTH,
+    COOKIE,
+    HOST,
+    ORIGIN,
+    PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION,
+    REFERER,
+    SET_COOKIE,
+    TE,
+    TRAILER,
+    TRANSFER_ENCODING,
+];
+
+/// Which requests a [`ProxyService`] forwards, and how large they may be.
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    /// Hosts requests may be forwarded to, e.g. `api.example.com`, or `*.example.com` for every
+    /// subdomain. Requests to other hosts are answered with `403 Forbidden`.
+    pub allowed_hosts: Vec<String>,
+    /// Largest request body forwarded, larger ones are answered with `413 Payload Too Large`.
+    /// Default: 1 MiB.
+    pub max_request_size: usize,
+    /// Largest response body returned, larger ones are answered with `502 Bad Gateway`.
+    /// Default: 10 MiB.
+    pub max_response_size: usize,
+    /// How long the target server may take to respond before `504 Gateway Timeout`.
+    /// Default: 30 seconds.
+    pub timeout: Duration,
+    /// How many requests each client may send, with more answered with
+    /// `429 Too Many Requests`. Clients are told apart by their [`ProxyUser`], or else by their
+    /// [`ProxyClient`] address. Default: 60 per minute, `None` for no limit.
+    pub rate_limit: Option<RateLimit>,
+    /// How many requests are forwarded at the same time, with more answered with
+    /// `503 Service Unavailable`. Default: 32, `None` for no limit.
+    pub max_concurrent_requests: Option<usize>,
+}
+
+/// At most `requests` per client in every window of `per`, see [`Proxy::rate_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// At most `requests` per client every minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            per: Duration::from_secs(60),
+        }
+    }
+}
+
+impl Default for Proxy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: vec![],
+            max_request_size: 1024 * 1024,
+            max_response_size: 10 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            rate_limit: Some(RateLimit::per_minute(60)),
+            max_concurrent_requests: Some(32),
+        }
+    }
+}
+
+impl Proxy {
+    /// Forward requests to the specified hosts.
+    pub fn hosts(hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_hosts: hosts.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether requests to `host` may be forwarded, comparing host names
+    /// case-insensitively.
+    fn allows(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.allowed_hosts.iter().any(|allowed| {
+            let allowed = allowed.to_ascii_lowercase();
+            match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.ends_with('.')),
+                None => allowed == host,
+            }
+        })
+    }
+}
+
+/// Who sent a request to a [`ProxyService`], passed to its
+/// [`on_proxied_request`](ProxyService::on_proxied_request) hook.
+///
+/// Insert it as a request extension, e.g. from the middleware authenticating the docs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyUser(pub String);
+
+/// The address a request to a [`ProxyService`] came from, which its rate limit tells clients
+/// without a [`ProxyUser`] apart by.
+///
+/// Insert it as a request extension, the adapters do so from the address of the connection.
+/// Without it, those clients share one limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProxyClient(pub IpAddr);
+
+/// Clients tracked by the rate limit at most, after which the ones whose window has passed are
+/// forgotten.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Requests per client in their current window, see [`Proxy::rate_limit`].
+#[derive(Default)]
+struct Limits {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+    in_flight: AtomicUsize,
+}
+
+impl Limits {
+    /// Counts a request by `client`, returning how long it has to wait if it is over `limit`.
+    fn count(&self, client: String, limit: RateLimit) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() >= MAX_TRACKED_CLIENTS {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < limit.per);
+        }
+        let (start, count) = windows.entry(client).or_insert((now, 0));
+        if now.duration_since(*start) >= limit.per {
+            (*start, *count) = (now, 0);
+        }
+        if *count >= limit.requests {
+            return Err(limit.per - now.duration_since(*start));
+        }
+        *count += 1;
+        Ok(())
+    }
+
+    /// Counts a forwarded request until the returned guard is dropped, or returns `None` if
+    /// `max` are already.
+    fn start(&self, max: Option<usize>) -> Option<InFlight<'_>> {
+        let before = self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight(&self.in_flight);
+        max.is_none_or(|max| before < max).then_some(guard)
+    }
+}
+
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Called with the method, target url and user of every forwarded request.
+type Hook = Arc<dyn Fn(&Method, &str, Option<&str>) + Send + Sync>;
+
+/// A `tower::Service` forwarding try-it-out requests to the server in their `url` query
+/// parameter, so APIs on other origins can be tried from the docs even if they don't send CORS
+/// headers.
+///
+/// Mount it at `proxy` next to a service created with
+/// [`with_proxy`](crate::DocService::with_proxy), e.g. at `/docs/proxy` for docs at `/docs`.
+/// Only the hosts of the [`Proxy`] are forwarded to and redirects aren't followed, so the
+/// service can't be used to reach other servers. Cookies and connection headers aren't
+/// forwarded.
+#[derive(Clone)]
+pub struct ProxyService {
+    proxy: Arc<Proxy>,
+    client: reqwest::Client,
+    on_proxied_request: Option<Hook>,
+    limits: Arc<Limits>,
+}
+
+impl fmt::Debug for ProxyService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyService")
+            .field("proxy", &self.proxy)
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProxyService {
+    /// Create a service forwarding the requests `proxy` allows.
+    pub fn new(proxy: Proxy) -> Self {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(proxy.timeout)
+            .build()
+            .expect("Could not create the HTTP client of the proxy.");
+        Self {
+            proxy: Arc::new(proxy),
+            client,
+            on_proxied_request: None,
+            limits: Default::default(),
+        }
+    }
+
+    /// Call `hook` with the method, target url and [`ProxyUser`] of every request before it is
+    /// forwarded, e.g. to audit who exercised which endpoint from the docs.
+    ///
+    /// Requests that aren't forwarded, e.g. because their host isn't allowed, aren't passed.
+    pub fn on_proxied_request(
+        mut self,
+        hook: impl Fn(&Method, &str, Option<&str>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_proxied_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// Returns the largest request body forwarded, for adapters to limit how much they read.
+    pub fn max_request_size(&self) -> usize {
+        self.proxy.max_request_size
+    }
+
+    /// Forward `req` to the server in its `url` query parameter.
+    pub async fn handle(&self, req: Request<Bytes>) -> Response<Body> {
+        if let Some(limit) = self.proxy.rate_limit {
+            let client = match (
+                req.extensions().get::<ProxyUser>(),
+                req.extensions().get::<ProxyClient>(),
+            ) {
+                (Some(user), _) => format!("user {}", user.0),
+                (None, Some(client)) => format!("ip {}", client.0),
+                (None, None) => String::new(),
+            };
+            if let Err(wait) = self.limits.count(client, limit) {
+                let mut response = status(StatusCode::TOO_MANY_REQUESTS);
+                let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                response.headers_mut().insert(RETRY_AFTER, seconds.into());
+                return response;
+            }
+        }
+        let target = form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == "url")
+            .and_then(|(_, url)| reqwest::Url::parse(&url).ok())
+            .filter(|url| matches!(url.scheme(), "http" | "https"));
+        let Some(target) = target else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+        if !target
+            .host_str()
+            .is_some_and(|host| self.proxy.allows(host))
+        {
+            return status(StatusCode::FORBIDDEN);
+        }
+        if req.body().len() > self.proxy.max_request_size {
+            return status(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+
+        let Ok(method) = reqwest::Method::from_bytes(req.method().as_str().as_bytes()) else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+        let Some(_in_flight) = self.limits.start(self.proxy.max_concurrent_requests) else {
+            return status(StatusCode::SERVICE_UNAVAILABLE);
+        };
+        if let Some(hook) = &self.on_proxied_request {
+            let user = req.extensions().get::<ProxyUser>();
+            hook(
+                req.method(),
+                target.as_str(),
+                user.map(|user| user.0.as_str()),
+            );
+        }
+        let mut forwarded = self.client.request(method, target);
+        for (name, value) in req.headers() {
+            if !is_filtered(name) {
+                forwarded = forwarded.header(name.as_str(), value.as_bytes());
+            }
+        }
+        if *req.method() != Method::GET && *req.method() != Method::HEAD {
+            forwarded = forwarded.body(req.into_body());
+        }
+
+        let mut upstream = match forwarded.send().await {
+            Ok(upstream) => upstream,
+            Err(err) if err.is_timeout() => return status(StatusCode::GATEWAY_TIMEOUT),
+            Err(_) => return status(StatusCode::BAD_GATEWAY),
+        };
+        if upstream
+            .content_length()
+            .is_some_and(|length| length > self.proxy.max_response_size as u64)
+        {
+            return status(StatusCode::BAD_GATEWAY);
+        }
+        let mut content = BytesMut::new();
+        loop {
+            match upstream.chunk().await {
+                Ok(Some(chunk)) if content.len() + chunk.len() <= self.proxy.max_response_size => {
+                    content.extend_from_slice(&chunk)
+                }
+                Ok(None) => break,
+                Err(err) if err.is_timeout() => return status(StatusCode::GATEWAY_TIMEOUT),
+                Ok(Some(_)) | Err(_) => return status(StatusCode::BAD_GATEWAY),
+            }
+        }
+
+        let mut response = Response::new(Body::from(content.freeze()));
+        *response.status_mut() =
+            StatusCode::from_u16(upstream.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+        for (name, value) in upstream.headers() {
+            let name = HeaderName::from_bytes(name.as_str().as_bytes());
+            let value = HeaderValue::from_bytes(value.as_bytes());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                if !is_filtered(&name) {
+                    response.headers_mut().append(name, value);
+                }
+            }
+        }
+        response
+    }
+}
+
+impl Service<Request<Bytes>> for ProxyService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Bytes>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { Ok(service.handle(req).await) })
+    }
+}
+
+fn is_filtered(name: &HeaderName) -> bool {
+    FILTERED_HEADERS.contains(name)
+        || *name == UPGRADE
+        || name.as_str() == "keep-alive"
+        || name.as_str().starts_with("proxy-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Proxy, ProxyClient, ProxyService, ProxyUser, RateLimit};
+    use bytes::Bytes;
+    use http::{Request, StatusCode};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn allows_listed_hosts() {
+        let proxy = Proxy::hosts(["api.example.com", "*.Example.org"]);
+        assert!(proxy.allows("api.example.com"));
+        assert!(proxy.allows("API.example.com"));
+        assert!(proxy.allows("eu.api.example.org"));
+        assert!(proxy.allows("EU.api.EXAMPLE.org"));
+        assert!(!proxy.allows("example.org"));
+        assert!(!proxy.allows("evilexample.org"));
+        assert!(!proxy.allows("example.com"));
+    }
+
+    #[tokio::test]
+    async fn limits_requests_per_client() {
+        let service = ProxyService::new(Proxy {
+            rate_limit: Some(RateLimit::per_minute(2)),
+            ..Proxy::hosts(["api.example.com"])
+        });
+        let send = |client: [u8; 4]| {
+            let mut req = Request::get("/docs/proxy?url=https%3A%2F%2Fother.example.com%2F")
+                .body(Bytes::new())
+                .unwrap();
+            req.extensions_mut().insert(ProxyClient(client.into()));
+            service.handle(req)
+        };
+
+        assert_eq!(send([10, 0, 0, 1]).await.status(), StatusCode::FORBIDDEN);
+        assert_eq!(send([10, 0, 0, 1]).await.status(), StatusCode::FORBIDDEN);
+        let limited = send([10, 0, 0, 1]).await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(
+            limited.headers()["retry-after"]
+                .to_str()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+                <= 60
+        );
+        assert_eq!(send([10, 0, 0, 2]).await.status(), StatusCode::FORBIDDEN);
+
+        let limits = &service.limits;
+        let first = limits.start(Some(1)).unwrap();
+        assert!(limits.start(Some(1)).is_none());
+        drop(first);
+        assert!(limits.start(Some(1)).is_some());
+    }
+
+    #[tokio::test]
+    async fn forwards_allowed_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let len = stream.read(&mut request).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 201 Created\r\nContent-Length: 2\r\nSet-Cookie: a=b\r\nX-Pet: 1\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..len]).to_lowercase()
+        });
+
+        let audit = Arc::new(Mutex::new(vec![]));
+        let log = audit.clone();
+        let service = ProxyService::new(Proxy::hosts(["127.0.0.1"])).on_proxied_request(
+            move |method, url, user| {
+                log.lock()
+                    .unwrap()
+                    .push(format!("{method} {url} {}", user.unwrap_or("-")))
+            },
+        );
+        let uri = format!("/docs/proxy?url=http%3A%2F%2F{addr}%2Fpets");
+        let mut req = Request::post(uri)
+            .header("authorization", "Bearer token")
+            .header("cookie", "session=docs")
+            .body(Bytes::from_static(b"{}"))
+            .unwrap();
+        req.extensions_mut().insert(ProxyUser("alice".into()));
+        let response = service.handle(req).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["x-pet"], "1");
+        assert!(response.headers().get("set-cookie").is_none());
+        assert_eq!(response.into_body().into_bytes(), "ok");
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("post /pets "));
+        assert!(request.contains("authorization: bearer token"));
+        assert!(!request.contains("session=docs"));
+        assert_eq!(
+            *audit.lock().unwrap(),
+            [format!("POST http://{addr}/pets alice")]
+        );
+
+        let req = Request::get("/docs/proxy?url=https%3A%2F%2Fexample.com%2F")
+            .body(Bytes::new())
+            .unwrap();
+        assert_eq!(service.handle(req).await.status(), StatusCode::FORBIDDEN);
+        let req = Request::post(format!("/docs/proxy?url=http%3A%2F%2F{addr}%2F"))
+            .body(Bytes::from(vec![0; 2 * 1024 * 1024]))
+            .unwrap();
+        assert_eq!(
+            service.handle(req).await.status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+}
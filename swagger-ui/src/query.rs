@@ -0,0 +1,170 @@
+use crate::{Config, DocExpansion, Filter};
+use std::collections::BTreeMap;
+
+/// Query parameters that may be forwarded from `index.html?...` into the generated config.
+///
+/// Only the parameters passed to [`SwaggerUiService::with_query_params`] are forwarded, every
+/// other parameter is ignored.
+///
+/// [`SwaggerUiService::with_query_params`]: crate::SwaggerUiService::with_query_params
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QueryParam {
+    /// `?api=<name>` selects the spec of [`Config::urls`] with that name as the primary one.
+    Api,
+    /// `?expand=list|full|none` sets [`Config::doc_expansion`].
+    Expand,
+    /// `?filter=<expression>` enables filtering with a preconfigured expression, `true` or
+    /// `false` toggles it.
+    Filter,
+}
+
+impl QueryParam {
+    /// The name of the query parameter.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Expand => "expand",
+            Self::Filter => "filter",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Api, Self::Expand, Self::Filter]
+            .into_iter()
+            .find(|param| param.name() == name)
+    }
+
+    /// Returns whether `value` is valid for this parameter.
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::Expand => matches!(value, "list" | "full" | "none"),
+            Self::Api | Self::Filter => true,
+        }
+    }
+
+    /// Applies `value`, which it [accepts](Self::accepts), to `config`.
+    fn apply(self, value: &str, config: &mut Config) {
+        match self {
+            Self::Api => config.urls_primary_name = Some(value.to_string()),
+            Self::Expand => {
+                config.doc_expansion = match value {
+                    "list" => DocExpansion::List,
+                    "full" => DocExpansion::Full,
+                    _ => DocExpansion::None,
+                }
+            }
+            Self::Filter => {
+                config.filter = match value {
+                    "true" => Filter::Bool(true),
+                    "false" => Filter::Bool(false),
+                    _ => Filter::Str(value.to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the parameters of `query` that are in `allowed`, in the order they appear.
+pub(crate) fn forwarded<'a>(
+    allowed: &'a [QueryParam],
+    query: Option<&'a str>,
+) -> impl Iterator<Item = (QueryParam, String)> + 'a {
+    form_urlencoded::parse(query.unwrap_or_default().as_bytes()).filter_map(|(name, value)| {
+        let param = QueryParam::from_name(&name).filter(|param| allowed.contains(param))?;
+        Some((param, value.into_owned()))
+    })
+}
+
+/// Returns the allowed parameters of `query` with a valid value, each once with the last of its
+/// values and in a fixed order, so queries setting the config alike are equal.
+pub(crate) fn normalize(allowed: &[QueryParam], query: Option<&str>) -> Vec<(QueryParam, String)> {
+    let mut params = BTreeMap::new();
+    for (param, value) in forwarded(allowed, query) {
+        if param.accepts(&value) {
+            params.insert(param, value);
+        }
+    }
+    params.into_iter().collect()
+}
+
+/// Applies the parameters returned by [`normalize`] to `config`.
+pub(crate) fn apply(params: &[(QueryParam, String)], config: &mut Config) {
+    for (param, value) in params {
+        param.apply(value, config);
+    }
+}
+
+/// Returns the `index.html` query for a request to the mount point, pointing swagger-ui to a
+/// config url that carries the allowed parameters of `query`.
+///
+/// Returns `None` if `query` has no allowed parameters or already picks a config url.
+pub(crate) fn index_query(
+    allowed: &[QueryParam],
+    query: Option<&str>,
+    base: &str,
+) -> Option<String> {
+    let query = query?;
+    let has_config_url =
+        form_urlencoded::parse(query.as_bytes()).any(|(name, _)| name == "configUrl");
+    if has_config_url {
+        return None;
+    }
+    let mut params = form_urlencoded::Serializer::new(String::new());
+    for (param, value) in forwarded(allowed, Some(query)) {
+        params.append_pair(param.name(), &value);
+    }
+    let params = params.finish();
+    if params.is_empty() {
+        return None;
+    }
+    let config_url = format!("{base}/swagger-ui-config.json?{params}");
+    let mut index_query = form_urlencoded::Serializer::new(query.to_string());
+    index_query.append_pair("configUrl", &config_url);
+    Some(index_query.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, index_query, normalize, QueryParam};
+    use crate::{Config, DocExpansion, Filter};
+
+    #[test]
+    fn applies_only_allowed_params() {
+        let mut config = Config::default();
+        let query = Some("expand=full&api=payments&filter=orders");
+        apply(&normalize(&[QueryParam::Expand], query), &mut config);
+        assert!(matches!(config.doc_expansion, DocExpansion::Full));
+        assert_eq!(config.urls_primary_name, None);
+        assert!(matches!(config.filter, Filter::Bool(false)));
+
+        let allowed = [QueryParam::Api, QueryParam::Expand];
+        assert!(normalize(&allowed, Some("expand=everything")).is_empty());
+        assert!(normalize(&[], query).is_empty());
+        assert_eq!(
+            normalize(
+                &allowed,
+                Some("junk=1&expand=list&api=a&expand=full&expand=all")
+            ),
+            normalize(&allowed, Some("api=a&expand=full")),
+        );
+    }
+
+    #[test]
+    fn index_query_points_to_forwarding_config_url() {
+        let allowed = [QueryParam::Api, QueryParam::Expand];
+        assert_eq!(
+            index_query(&allowed, Some("api=payments&other=1"), "/docs").unwrap(),
+            "api=payments&other=1&configUrl=%2Fdocs%2Fswagger-ui-config.json%3Fapi%3Dpayments"
+        );
+        assert_eq!(index_query(&allowed, Some("other=1"), "/docs"), None);
+        assert_eq!(
+            index_query(
+                &allowed,
+                Some("api=payments&configUrl=/config.json"),
+                "/docs"
+            ),
+            None
+        );
+        assert_eq!(index_query(&allowed, None, "/docs"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the RapiDoc bundle, with the spec url and attributes filled in.
+const INDEX: &str = include_str!("rapidoc.html");
+
+/// The RapiDoc bundle
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.viewers/rapidoc"]
+pub struct RapidocAssets;
+
+/// Used to control how RapiDoc lays out the operations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RapidocRenderStyle {
+    /// Show every operation in one long page.
+    Read,
+    /// Show the operations as collapsible tree.
+    View,
+    /// Show only the operation selected in the side menu.
+    Focused,
+}
+
+/// Used to pick the RapiDoc color scheme.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RapidocTheme {
+    /// Light colors.
+    Light,
+    /// Dark colors.
+    Dark,
+}
+
+/// Attributes of the `<rapi-doc>` element, see <https://rapidocweb.com/api.html>. Attributes
+/// that are `None` keep the RapiDoc defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RapidocConfig {
+    /// How the operations are laid out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render_style: Option<RapidocRenderStyle>,
+    /// The color scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<RapidocTheme>,
+    /// Allow trying out the operations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_try: Option<bool>,
+    /// Allow authenticating with the security schemes of the spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_authentication: Option<bool>,
+    /// Show the header bar with the spec url input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_header: Option<bool>,
+    /// Color of the buttons, tabs and the like, e.g. `#ff791a`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_color: Option<String>,
+}
+
+/// RapiDoc, served from the embedded [`RapidocAssets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rapidoc;
+
+impl DocUi for Rapidoc {
+    type Config = RapidocConfig;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        RapidocAssets::get(path)
+    }
+
+    fn asset_paths() -> Vec<String> {
+        RapidocAssets::iter().map(Cow::into_owned).collect()
+    }
+
+    fn index(spec_url: &str, config: &RapidocConfig) -> Option<Bytes> {
+        let page = INDEX
+            .replace("{{spec_url}}", &html::attribute(spec_url))
+            .replace("{{attributes}}", &html::attributes(config));
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with RapiDoc.
+///
+/// It is mounted like [`SwaggerUiService`](crate::SwaggerUiService), see
+/// [`MountPath`](crate::MountPath).
+pub type RapidocService = DocService<Rapidoc>;
+
+#[cfg(test)]
+mod tests {
+    use super::{RapidocConfig, RapidocRenderStyle, RapidocService, RapidocTheme};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn serves_rapidoc_below_the_mount() {
+        let service = RapidocService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            RapidocConfig {
+                render_style: Some(RapidocRenderStyle::Focused),
+                theme: Some(RapidocTheme::Dark),
+                allow_try: Some(false),
+                primary_color: Some("\"><script>".to_string()),
+                ..Default::default()
+            },
+        );
+        let get = |uri: &str| {
+            let mut req = Request::get(uri).body(()).unwrap();
+            req.extensions_mut()
+                .insert(MountPath("/rapidoc".to_string()));
+            service.handle(&req)
+        };
+
+        let response = get("/");
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/rapidoc/index.html");
+
+        let response = get("/index.html");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains(concat!(
+            r#"<rapi-doc spec-url="/rapidoc/openapi.json" allow-try="false""#,
+            r#" primary-color="&quot;&gt;&lt;script&gt;" render-style="focused" theme="dark">"#
+        )));
+
+        assert_eq!(get("/rapidoc-min.js").status(), StatusCode::OK);
+        assert_eq!(get("/openapi.json").status(), StatusCode::OK);
+        assert_eq!(get("/index.css").status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,211 @@
+//! Scrubbing credentials from the examples of a spec before it is served.
+
+use crate::Spec;
+use bytes::Bytes;
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+/// Patterns of credentials found in examples by default: bearer tokens, JWTs, `key=value` pairs
+/// of keys named like secrets and well-known API key formats.
+const DEFAULT_PATTERNS: [&str; 4] = [
+    r"(?i)\bbearer\s+(?P<secret>[a-z0-9\-._~+/]+=*)",
+    r"\beyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*",
+    r#"(?i)\b(?:api[_-]?key|access[_-]?token|secret|password|token)["']?\s*[:=]\s*["']?(?P<secret>[^\s"'&,;]{6,})"#,
+    r"\b(?:sk_live_[0-9a-zA-Z]{10,}|ghp_[A-Za-z0-9]{36}|AKIA[0-9A-Z]{16}|xox[abprs]-[A-Za-z0-9-]{10,})",
+];
+
+/// Names of fields of examples whose values are credentials by default.
+const DEFAULT_FIELDS: &str = r"(?i)^(?:api[_-]?key|access[_-]?token|refresh[_-]?token|client[_-]?secret|secret|password|token)$";
+
+/// Replaces credentials in the `example` and `examples` values and security scheme descriptions
+/// of a spec, see [`with_redaction`](crate::DocService::with_redaction).
+///
+/// A pattern replaces the whole of each match, or only its `secret` group if it has one, e.g.
+/// `(?i)bearer\s+(?P<secret>\S+)` keeps the word `Bearer`. Fields of examples with names
+/// matching the field pattern have their value replaced regardless of it.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    fields: Option<Regex>,
+    replacement: String,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_PATTERNS
+                .iter()
+                .map(|pattern| Regex::new(pattern).expect("default patterns are valid"))
+                .collect(),
+            fields: Some(Regex::new(DEFAULT_FIELDS).expect("default field pattern is valid")),
+            replacement: "[REDACTED]".to_string(),
+        }
+    }
+}
+
+impl Redactor {
+    /// Create a redactor without any patterns, to configure from scratch.
+    pub fn empty() -> Self {
+        Self {
+            patterns: vec![],
+            fields: None,
+            ..Self::default()
+        }
+    }
+
+    /// Additionally replace matches of `pattern`.
+    pub fn pattern(mut self, pattern: Regex) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Replace the values of example fields whose names match `fields`, instead of the default
+    /// names like `password` and `apiKey`.
+    pub fn fields(mut self, fields: Regex) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Replace credentials with `replacement` instead of `[REDACTED]`.
+    pub fn replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Returns `spec` with the credentials replaced.
+    ///
+    /// Specs that aren't JSON are redacted as a whole, with the patterns only.
+    pub fn redact(&self, spec: &Spec) -> Spec {
+        let content = match serde_json::from_slice::<Value>(&spec.content) {
+            Ok(mut document) => {
+                self.redact_document(&mut document);
+                serde_json::to_vec(&document).expect("Could not serialize spec as JSON.")
+            }
+            Err(_) => self
+                .redact_text(&String::from_utf8_lossy(&spec.content))
+                .into_bytes(),
+        };
+        Spec {
+            name: spec.name.clone(),
+            content: Bytes::from(content),
+            content_type: spec.content_type.clone(),
+            precompressed: None,
+        }
+    }
+
+    fn redact_document(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object.iter_mut() {
+                    if key == "example" || key == "examples" {
+                        self.redact_example(value);
+                    } else {
+                        self.redact_document(value);
+                    }
+                }
+            }
+            Value::Array(values) => values
+                .iter_mut()
+                .for_each(|value| self.redact_document(value)),
+            _ => {}
+        }
+
+        for pointer in ["/components/securitySchemes", "/securityDefinitions"] {
+            let schemes = value.pointer_mut(pointer).and_then(Value::as_object_mut);
+            for scheme in schemes.into_iter().flat_map(|schemes| schemes.values_mut()) {
+                if let Some(Value::String(description)) = scheme.get_mut("description") {
+                    *description = self.redact_text(description);
+                }
+            }
+        }
+    }
+
+    fn redact_example(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.redact_text(text),
+            Value::Object(object) => {
+                for (key, value) in object.iter_mut() {
+                    let secret = self
+                        .fields
+                        .as_ref()
+                        .is_some_and(|fields| fields.is_match(key));
+                    match value {
+                        Value::String(_) | Value::Number(_) if secret => {
+                            *value = Value::String(self.replacement.clone())
+                        }
+                        value => self.redact_example(value),
+                    }
+                }
+            }
+            Value::Array(values) => values
+                .iter_mut()
+                .for_each(|value| self.redact_example(value)),
+            _ => {}
+        }
+    }
+
+    fn redact_text(&self, text: &str) -> String {
+        self.patterns
+            .iter()
+            .fold(text.to_string(), |text, pattern| {
+                pattern
+                    .replace_all(&text, |captures: &Captures| {
+                        let whole = captures.get(0).expect("a match has a whole group");
+                        match captures.name("secret") {
+                            Some(secret) => {
+                                let start = secret.start() - whole.start();
+                                let end = secret.end() - whole.start();
+                                let whole = whole.as_str();
+                                format!("{}{}{}", &whole[..start], self.replacement, &whole[end..])
+                            }
+                            None => self.replacement.clone(),
+                        }
+                    })
+                    .into_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Redactor;
+    use crate::Spec;
+    use bytes::Bytes;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn redacts_examples_and_scheme_descriptions() {
+        let spec = Spec::builder().json(Bytes::from(
+                serde_json::to_vec(&json!({
+                    "paths": {"/login": {"post": {
+                        "description": "Send Bearer abc.def to log in",
+                        "parameters": [{"name": "Authorization", "in": "header", "example": "Bearer abc.def"}],
+                        "requestBody": {"content": {"application/json": {"examples": {
+                            "admin": {"value": {"user": "admin", "password": "hunter22"}}
+                        }}}}
+                    }}},
+                    "components": {"securitySchemes": {"key": {
+                        "type": "apiKey",
+                        "description": "e.g. api_key=sk_live_0123456789abcdef"
+                    }}}
+                }))
+                .unwrap(),
+            ));
+        let redacted = Redactor::default().redact(&spec);
+        let document: Value = serde_json::from_slice(&redacted.content).unwrap();
+
+        let operation = &document["paths"]["/login"]["post"];
+        // descriptions of operations are prose, not examples
+        assert_eq!(operation["description"], "Send Bearer abc.def to log in");
+        assert_eq!(operation["parameters"][0]["example"], "Bearer [REDACTED]");
+        let example = &operation["requestBody"]["content"]["application/json"]["examples"]["admin"];
+        assert_eq!(
+            example["value"],
+            json!({"user": "admin", "password": "[REDACTED]"})
+        );
+        assert_eq!(
+            document["components"]["securitySchemes"]["key"]["description"],
+            "e.g. api_key=[REDACTED]"
+        );
+    }
+}
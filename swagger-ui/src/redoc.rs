@@ -0,0 +1,128 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the ReDoc bundle, with the spec url and options filled in.
+const INDEX: &str = include_str!("redoc.html");
+
+/// The standalone ReDoc bundle
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.viewers/redoc"]
+pub struct RedocAssets;
+
+/// Options of ReDoc, see <https://redocly.com/docs/redoc/config>. Options that are `None` keep
+/// the ReDoc defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedocConfig {
+    /// Disable the search in the side menu.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_search: Option<bool>,
+    /// Responses to expand by default, e.g. `"200,201"` or `"all"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expand_responses: Option<String>,
+    /// Hide the button to download the spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_download_button: Option<bool>,
+    /// Hide the protocol and hostname in the operation definitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_hostname: Option<bool>,
+    /// Levels of JSON samples to expand by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_sample_expand_level: Option<u32>,
+    /// Use the native scrollbar instead of the custom one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_scrollbars: Option<bool>,
+    /// Show the path of an operation in the middle panel instead of the right one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_in_middle_panel: Option<bool>,
+    /// Show the required properties of a schema first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_props_first: Option<bool>,
+    /// Sort the properties of a schema alphabetically.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_props_alphabetically: Option<bool>,
+    /// ReDoc theme, e.g. `{"colors": {"primary": {"main": "#32329f"}}}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<serde_json::Value>,
+}
+
+/// ReDoc, served from the embedded [`RedocAssets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Redoc;
+
+impl DocUi for Redoc {
+    type Config = RedocConfig;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        RedocAssets::get(path)
+    }
+
+    fn asset_paths() -> Vec<String> {
+        RedocAssets::iter().map(Cow::into_owned).collect()
+    }
+
+    fn index(spec_url: &str, config: &RedocConfig) -> Option<Bytes> {
+        let spec_url = serde_json::to_string(spec_url).expect("Could not serialize spec url.");
+        let options = serde_json::to_string(config).expect("Could not serialize config as JSON.");
+        let page = INDEX
+            .replace("{{spec_url}}", &html::script(&spec_url))
+            .replace("{{options}}", &html::script(&options));
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with ReDoc.
+///
+/// It is mounted like [`SwaggerUiService`](crate::SwaggerUiService), see
+/// [`MountPath`](crate::MountPath).
+pub type RedocService = DocService<Redoc>;
+
+#[cfg(test)]
+mod tests {
+    use super::{RedocConfig, RedocService};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn serves_redoc_below_the_mount() {
+        let service = RedocService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            RedocConfig {
+                hide_download_button: Some(true),
+                theme: Some(serde_json::json!({ "spacing": { "unit": 4 } })),
+                ..Default::default()
+            },
+        );
+        let get = |uri: &str| {
+            let mut req = Request::get(uri).body(()).unwrap();
+            req.extensions_mut().insert(MountPath("/redoc".to_string()));
+            service.handle(&req)
+        };
+
+        let response = get("/");
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/redoc/index.html");
+
+        let response = get("/index.html");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains(
+            r#"Redoc.init("/redoc/openapi.json", {"hideDownloadButton":true,"theme":{"spacing":{"unit":4}}}"#
+        ));
+
+        let response = get("/redoc.standalone.js");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .ends_with("javascript"));
+
+        let response = get("/openapi.json");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(get("/swagger-ui.css").status(), StatusCode::NOT_FOUND);
+    }
+}
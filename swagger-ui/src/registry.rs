@@ -0,0 +1,282 @@
+use crate::handlers::{content, head, method_not_allowed};
+use crate::{html, mime, Acl, Body, Spec, UrlObject};
+use bytes::Bytes;
+use http::{Method, Request, Response};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Landing page listing the registered APIs, with the title and entries filled in.
+const LANDING_PAGE: &str = include_str!("landing.html");
+
+/// An API listed on the landing page of a [`SpecRegistry`].
+#[derive(Debug, Clone)]
+pub struct ApiEntry {
+    /// The name of the API.
+    pub name: String,
+    /// The version of the API.
+    pub version: Option<String>,
+    /// What the API is about.
+    pub description: Option<String>,
+    /// Links to the docs of the API, e.g. `Swagger UI` to `/docs` and `ReDoc` to `/redoc`.
+    pub links: Vec<UrlObject>,
+    /// Who the API is listed to, or `None` for everyone.
+    pub acl: Option<Acl>,
+}
+
+/// The `info` object of a spec, as far as the landing page is concerned.
+#[derive(Deserialize)]
+struct Info {
+    title: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Document {
+    info: Option<Info>,
+}
+
+impl ApiEntry {
+    /// Create an entry for `spec`, named and described after its `info` object.
+    ///
+    /// Specs that aren't JSON or lack a title are listed under their file name.
+    pub fn from_spec(spec: &Spec, links: impl IntoIterator<Item = UrlObject>) -> Self {
+        let info = serde_json::from_slice::<Document>(&spec.content)
+            .ok()
+            .and_then(|document| document.info);
+        let (title, version, description) = match info {
+            Some(info) => (info.title, info.version, info.description),
+            None => (None, None, None),
+        };
+        Self {
+            name: title.unwrap_or_else(|| spec.name.to_string()),
+            version,
+            description,
+            links: links.into_iter().collect(),
+            acl: None,
+        }
+    }
+
+    /// Only list the API to the requests `acl` allows. Restrict its docs as well, e.g. with
+    /// [`DocService::with_guard`](crate::DocService::with_guard), the landing page only leaves
+    /// out the link to them.
+    pub fn visible_to(mut self, acl: Acl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    fn render(&self) -> String {
+        let version = match &self.version {
+            Some(version) => format!(
+                r#"<span class="version">{}</span>"#,
+                html::attribute(version)
+            ),
+            None => String::new(),
+        };
+        let description = match &self.description {
+            Some(description) => format!("<p>{}</p>", html::attribute(description)),
+            None => String::new(),
+        };
+        let links: String = self
+            .links
+            .iter()
+            .map(|link| {
+                format!(
+                    r#"<a href="{}">{}</a>"#,
+                    html::attribute(&link.url),
+                    html::attribute(&link.name)
+                )
+            })
+            .collect();
+        format!(
+            "<section><h2>{}{version}</h2>{description}<nav>{links}</nav></section>\n",
+            html::attribute(&self.name)
+        )
+    }
+}
+
+/// The APIs mounted on a server, for a landing page linking to all of their docs.
+#[derive(Debug, Clone)]
+pub struct SpecRegistry {
+    title: String,
+    entries: Vec<ApiEntry>,
+}
+
+impl Default for SpecRegistry {
+    fn default() -> Self {
+        Self::new("APIs")
+    }
+}
+
+impl SpecRegistry {
+    /// Create an empty registry, whose landing page is headed `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            entries: vec![],
+        }
+    }
+
+    /// Register `spec` with links to where its docs are mounted, see [`ApiEntry::from_spec`].
+    pub fn register(self, spec: &Spec, links: impl IntoIterator<Item = UrlObject>) -> Self {
+        self.register_entry(ApiEntry::from_spec(spec, links))
+    }
+
+    /// Register an entry, e.g. for an API whose spec is hosted elsewhere.
+    pub fn register_entry(mut self, entry: ApiEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Returns the registered entries, in the order they are listed.
+    pub fn entries(&self) -> &[ApiEntry] {
+        &self.entries
+    }
+
+    /// Returns a service answering every request with the landing page, listing the entries
+    /// visible to it.
+    pub fn landing_page(&self) -> LandingPageService {
+        let page = if self.entries.iter().any(|entry| entry.acl.is_some()) {
+            LandingPage::Restricted(Arc::new(self.clone()))
+        } else {
+            LandingPage::Static(Bytes::from(self.render(self.entries.iter())))
+        };
+        LandingPageService { page }
+    }
+
+    fn render<'a>(&self, entries: impl Iterator<Item = &'a ApiEntry>) -> String {
+        let entries: String = entries.map(ApiEntry::render).collect();
+        LANDING_PAGE
+            .replace("{{title}}", &html::attribute(&self.title))
+            .replace("{{entries}}", &entries)
+    }
+}
+
+/// A `tower::Service` answering every request with the landing page of a [`SpecRegistry`].
+#[derive(Debug, Clone)]
+pub struct LandingPageService {
+    page: LandingPage,
+}
+
+#[derive(Debug, Clone)]
+enum LandingPage {
+    /// The page listing every entry, when all of them are visible to all.
+    Static(Bytes),
+    /// The registry, to render the page per request since some entries aren't visible to all.
+    Restricted(Arc<SpecRegistry>),
+}
+
+impl LandingPageService {
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        let page = || {
+            let registry = match &self.page {
+                LandingPage::Static(page) => return content(mime::HTML, page.clone().into()),
+                LandingPage::Restricted(registry) => registry,
+            };
+            let visible = registry.entries.iter().filter(|entry| {
+                (entry.acl.as_ref()).is_none_or(|acl| acl.allows(req.headers(), req.extensions()))
+            });
+            content(mime::HTML, Bytes::from(registry.render(visible)).into())
+        };
+        match *req.method() {
+            Method::GET => page(),
+            Method::HEAD => head(page()),
+            _ => method_not_allowed(),
+        }
+    }
+}
+
+impl From<&SpecRegistry> for LandingPageService {
+    fn from(value: &SpecRegistry) -> Self {
+        value.landing_page()
+    }
+}
+
+impl<B> Service<Request<B>> for LandingPageService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiEntry, SpecRegistry};
+    use crate::{Acl, Bytes, Roles, Spec, UrlObject};
+    use http::header::CONTENT_TYPE;
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn lists_registered_apis() {
+        let payments = Spec::builder().name("payments.json").json(Bytes::from_static(
+                br#"{"info": {"title": "Payments <v2>", "version": "2.1.0", "description": "Charges & refunds"}}"#,
+            ));
+        let registry = SpecRegistry::new("Example APIs")
+            .register(
+                &payments,
+                [
+                    UrlObject::new("Swagger UI", "/payments/docs"),
+                    UrlObject::new("ReDoc", "/payments/redoc"),
+                ],
+            )
+            .register(
+                &Spec::builder()
+                    .name("legacy.yaml")
+                    .yaml(Bytes::from_static(b"info:\n  title: Legacy\n")),
+                [UrlObject::new("Swagger UI", "/legacy/docs")],
+            );
+        assert_eq!(registry.entries()[1].name, "legacy.yaml");
+
+        let service = registry.landing_page();
+        let response = service.handle(&Request::get("/").body(()).unwrap());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains("<h1>Example APIs</h1>"));
+        assert!(page.contains(concat!(
+            r#"<section><h2>Payments &lt;v2&gt;<span class="version">2.1.0</span></h2>"#,
+            r#"<p>Charges &amp; refunds</p><nav><a href="/payments/docs">Swagger UI</a>"#,
+            r#"<a href="/payments/redoc">ReDoc</a></nav></section>"#
+        )));
+
+        let entry = ApiEntry::from_spec(&crate::swagger_spec_file!("../examples/openapi.json"), []);
+        assert_eq!(entry.name, "Swagger Petstore");
+        assert_eq!(entry.version.as_deref(), Some("1.0.5"));
+    }
+
+    #[test]
+    fn lists_apis_only_to_those_entitled() {
+        let entry =
+            |name: &'static str| ApiEntry::from_spec(&Spec::builder().name(name).json("{}"), []);
+        let registry = SpecRegistry::new("Partner APIs")
+            .register_entry(entry("public.json"))
+            .register_entry(entry("billing.json").visible_to(Acl::roles(["finance"])));
+        let page = |roles: &[&str]| {
+            let mut req = Request::get("/").body(()).unwrap();
+            req.extensions_mut()
+                .insert(Roles::new(roles.iter().copied()));
+            let body = registry
+                .landing_page()
+                .handle(&req)
+                .into_body()
+                .into_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        };
+
+        assert!(page(&["finance"]).contains("billing.json"));
+        assert!(page(&["support"]).contains("public.json"));
+        assert!(!page(&["support"]).contains("billing.json"));
+    }
+}
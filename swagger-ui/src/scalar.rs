@@ -0,0 +1,145 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the Scalar bundle, with the spec url and configuration filled in.
+const INDEX: &str = include_str!("scalar.html");
+
+/// The standalone Scalar API reference bundle
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.viewers/scalar"]
+pub struct ScalarAssets;
+
+/// Used to control the overall layout of the reference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScalarLayout {
+    /// Three columns with the requests and responses on the right.
+    Modern,
+    /// Collapsible operations below each other.
+    Classic,
+}
+
+/// Configuration of the Scalar API reference, see
+/// <https://github.com/scalar/scalar/blob/main/documentation/configuration.md>. Options that are
+/// `None` keep the Scalar defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScalarConfig {
+    /// Name of a bundled theme, e.g. `purple`, `moon` or `none`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// The overall layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<ScalarLayout>,
+    /// Start in dark mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dark_mode: Option<bool>,
+    /// Hide the toggle between light and dark mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_dark_mode_toggle: Option<bool>,
+    /// Hide the models section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_models: Option<bool>,
+    /// Hide the button to download the spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_download_button: Option<bool>,
+    /// Show the sidebar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_sidebar: Option<bool>,
+    /// Key to focus the search with, together with `Ctrl` or `⌘`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_hot_key: Option<String>,
+    /// Additional CSS, e.g. to override the theme variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_css: Option<String>,
+}
+
+/// the Scalar API reference, served from the embedded [`ScalarAssets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Scalar;
+
+impl DocUi for Scalar {
+    type Config = ScalarConfig;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        ScalarAssets::get(path)
+    }
+
+    fn asset_paths() -> Vec<String> {
+        ScalarAssets::iter().map(Cow::into_owned).collect()
+    }
+
+    fn index(spec_url: &str, config: &ScalarConfig) -> Option<Bytes> {
+        let configuration =
+            serde_json::to_string(config).expect("Could not serialize config as JSON.");
+        let page = INDEX
+            .replace("{{spec_url}}", &html::attribute(spec_url))
+            .replace("{{configuration}}", &html::attribute(&configuration));
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with the Scalar API reference.
+///
+/// It is mounted like [`SwaggerUiService`](crate::SwaggerUiService), see
+/// [`MountPath`](crate::MountPath).
+pub type ScalarService = DocService<Scalar>;
+
+#[cfg(test)]
+mod tests {
+    use super::{ScalarConfig, ScalarLayout, ScalarService};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    #[test]
+    fn serves_scalar_below_the_mount() {
+        let service = ScalarService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            ScalarConfig {
+                theme: Some("moon".to_string()),
+                layout: Some(ScalarLayout::Classic),
+                ..Default::default()
+            },
+        );
+        let get = |uri: &str| {
+            let mut req = Request::get(uri).body(()).unwrap();
+            req.extensions_mut()
+                .insert(MountPath("/reference".to_string()));
+            service.handle(&req)
+        };
+
+        let response = get("/");
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/reference/index.html");
+
+        let response = get("/index.html");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains(concat!(
+            r#"data-url="/reference/openapi.json" "#,
+            r#"data-configuration="{&quot;theme&quot;:&quot;moon&quot;,&quot;layout&quot;:&quot;classic&quot;}""#
+        )));
+
+        assert_eq!(get("/standalone.js").status(), StatusCode::OK);
+        assert_eq!(get("/openapi.json").status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn links_external_spec_urls() {
+        let service = ScalarService::new("https://example.com/openapi.json", None);
+        let response = service.handle(&Request::get("/index.html").body(()).unwrap());
+        let page = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        assert!(page.contains(r#"data-url="https://example.com/openapi.json""#));
+        assert!(page.contains(r#"data-configuration="{}""#));
+        assert_eq!(
+            service
+                .handle(&Request::get("/openapi.json").body(()).unwrap())
+                .status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Exporting the schemas of a spec as a JSON Schema document.
+
+use crate::Spec;
+use serde_json::{json, Map, Value};
+
+/// Dialect of the generated document, which OpenAPI 3.1 schemas are written in as well.
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Converts the `components.schemas`, or Swagger 2.0 `definitions`, of `spec` into one JSON
+/// Schema document with every schema in its `$defs`, or returns `None` if the spec isn't JSON or
+/// has no schemas.
+///
+/// References between the schemas are rewritten to point into `$defs`, so a validator can load
+/// e.g. `schemas.json#/$defs/Pet` without the rest of the spec. The OpenAPI 3.0 keywords
+/// `nullable` and `example` are converted into their JSON Schema equivalents.
+pub fn json_schemas(spec: &Spec) -> Option<String> {
+    let document: Value = serde_json::from_slice(&spec.content).ok()?;
+    let (schemas, prefix) = match document["components"]["schemas"].as_object() {
+        Some(schemas) => (schemas, "#/components/schemas/"),
+        None => (document["definitions"].as_object()?, "#/definitions/"),
+    };
+    if schemas.is_empty() {
+        return None;
+    }
+
+    let defs: Map<String, Value> = schemas
+        .iter()
+        .map(|(name, schema)| {
+            let mut schema = schema.clone();
+            convert(&mut schema, prefix);
+            (name.clone(), schema)
+        })
+        .collect();
+    let mut export = json!({"$schema": DIALECT, "$defs": defs});
+    if let Some(title) = document["info"]["title"].as_str() {
+        export["title"] = json!(format!("Schemas of {title}"));
+    }
+    Some(serde_json::to_string(&export).expect("Could not serialize schemas as JSON."))
+}
+
+/// Rewrites the references starting with `prefix` below `value` and converts the OpenAPI 3.0
+/// keywords JSON Schema doesn't know.
+fn convert(value: &mut Value, prefix: &str) {
+    match value {
+        Value::Object(object) => {
+            let reference = object.get("$ref").and_then(Value::as_str);
+            if let Some(name) = reference.and_then(|reference| reference.strip_prefix(prefix)) {
+                let reference = format!("#/$defs/{name}");
+                object.insert("$ref".into(), reference.into());
+            }
+            if object.remove("nullable") == Some(Value::Bool(true)) {
+                match object.get_mut("type") {
+                    Some(Value::String(kind)) => {
+                        let kind = std::mem::take(kind);
+                        object.insert("type".into(), json!([kind, "null"]));
+                    }
+                    Some(Value::Array(kinds)) if !kinds.contains(&json!("null")) => {
+                        kinds.push(json!("null"));
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(example) = object.remove("example") {
+                object.entry("examples").or_insert_with(|| json!([example]));
+            }
+            for (key, value) in object.iter_mut() {
+                match (key.as_str(), value) {
+                    // values, not schemas
+                    ("enum" | "const" | "default" | "examples", _) => {}
+                    ("properties" | "patternProperties" | "$defs", Value::Object(named)) => {
+                        named.values_mut().for_each(|value| convert(value, prefix));
+                    }
+                    (_, value) => convert(value, prefix),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                convert(value, prefix);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_schemas;
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn exports_schemas_with_rewritten_references() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "openapi": "3.0.3",
+                "info": {"title": "Pets"},
+                "components": {"schemas": {
+                    "Pet": {"properties": {
+                        "owner": {"$ref": "#/components/schemas/Owner"},
+                        "name": {"type": "string", "nullable": true, "example": "Rex"},
+                        "example": {"type": "string", "default": {"nullable": true}}
+                    }},
+                    "Owner": {}
+                }}
+            }))
+            .unwrap(),
+        );
+        let export: Value = serde_json::from_str(&json_schemas(&spec).unwrap()).unwrap();
+
+        assert_eq!(export["title"], "Schemas of Pets");
+        let pet = &export["$defs"]["Pet"]["properties"];
+        assert_eq!(pet["owner"]["$ref"], "#/$defs/Owner");
+        assert_eq!(
+            pet["name"],
+            json!({"type": ["string", "null"], "examples": ["Rex"]})
+        );
+        // a property named like a keyword, with a default looking like a schema, is kept
+        assert_eq!(pet["example"]["default"], json!({"nullable": true}));
+        assert!(json_schemas(&Spec::builder().json(r#"{"paths": {}}"#)).is_none());
+    }
+}
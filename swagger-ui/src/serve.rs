@@ -0,0 +1,76 @@
+use crate::{Config, DocService, DocUi, SpecOrUrl, SwaggerUiService};
+use hyper::server::conn::http1;
+use hyper_util::rt::TokioIo;
+use std::io;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+/// Serves only swagger-ui displaying the given spec at the root of `addr`, until an error occurs
+/// accepting a connection.
+///
+/// Meant for quickly previewing a spec, e.g. from a test or a scratch binary:
+///
+/// ```rust,ignore
+/// swagger_ui::serve("127.0.0.1:3000", swagger_spec_file!("./openapi.json"), None).await
+/// ```
+pub async fn serve(
+    addr: impl ToSocketAddrs,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> io::Result<()> {
+    serve_service(addr, SwaggerUiService::new(spec, config)).await
+}
+
+/// Serves only the given service at the root of `addr`, like [`serve`] but with any viewer, e.g.
+/// a `RedocService` behind the `redoc` feature.
+pub async fn serve_service<U: DocUi>(
+    addr: impl ToSocketAddrs,
+    service: DocService<U>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    serve_listener(listener, service).await
+}
+
+async fn serve_listener<U: DocUi>(listener: TcpListener, service: DocService<U>) -> io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = service.clone();
+        tokio::spawn(async move {
+            // a failing connection only concerns its client
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serve_listener;
+    use crate::{Config, SwaggerUiService};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[tokio::test]
+    async fn serves_ui_at_the_root() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service =
+            SwaggerUiService::new(crate::swagger_spec_file!("../examples/openapi.json"), None);
+        tokio::spawn(serve_listener(listener, service));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /swagger-ui-config.json HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let config: Config = serde_json::from_str(body).unwrap();
+        assert_eq!(config.url, "/openapi.json");
+    }
+}
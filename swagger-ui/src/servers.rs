@@ -0,0 +1,119 @@
+//! Pointing the try-it-out requests of a spec at the servers of the environment it is served in.
+
+use crate::Spec;
+use serde_json::{json, Value};
+
+/// A server try-it-out requests can be sent to, see [`Config::servers`](crate::Config::servers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Server {
+    /// The url of the server, e.g. `https://staging.example.com/api`.
+    pub url: String,
+    /// Label shown next to the url, e.g. `Staging`.
+    pub description: Option<String>,
+}
+
+impl Server {
+    /// Create a server at `url`, without a label.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            description: None,
+        }
+    }
+
+    /// Label the server with `label`, e.g. the environment it belongs to.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.description = Some(label.into());
+        self
+    }
+}
+
+/// Returns `spec` with `servers` in place of its own, or followed by its own if `keep` is set,
+/// or `None` if the spec isn't an OpenAPI 3 JSON document.
+///
+/// The server labeled, or at the url, `default` is moved to the front, which swagger-ui selects
+/// for try-it-out requests. Swagger 2.0 specs describe their server with `host` and `basePath`
+/// instead, and are left out.
+pub(crate) fn set_servers(
+    spec: &Spec,
+    servers: &[Server],
+    keep: bool,
+    default: Option<&str>,
+) -> Option<Spec> {
+    let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+    document.get("openapi")?;
+    let mut list: Vec<Value> = servers
+        .iter()
+        .map(|server| match &server.description {
+            Some(description) => json!({"url": server.url, "description": description}),
+            None => json!({"url": server.url}),
+        })
+        .collect();
+    if keep {
+        let own = document["servers"].as_array().into_iter().flatten();
+        list.extend(
+            own.filter(|server| !servers.iter().any(|s| server["url"] == s.url.as_str()))
+                .cloned(),
+        );
+    }
+    let default = default.and_then(|default| {
+        list.iter()
+            .position(|server| server["description"] == default || server["url"] == default)
+    });
+    if let Some(index) = default {
+        let server = list.remove(index);
+        list.insert(0, server);
+    }
+    document["servers"] = Value::Array(list);
+
+    let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_servers, Server};
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn replaces_or_adds_to_the_servers_of_the_spec() {
+        let spec = Spec::builder().json(
+            r#"{"openapi": "3.0.3", "servers": [{"url": "https://example.com"}, {"url": "/api"}]}"#,
+        );
+        let servers = [Server::new("/api").label("Staging")];
+        let servers_of = |keep| {
+            let spec = set_servers(&spec, &servers, keep, None).unwrap();
+            serde_json::from_slice::<Value>(&spec.content).unwrap()["servers"].clone()
+        };
+
+        assert_eq!(
+            servers_of(false),
+            json!([{"url": "/api", "description": "Staging"}])
+        );
+        assert_eq!(
+            servers_of(true),
+            json!([{"url": "/api", "description": "Staging"}, {"url": "https://example.com"}])
+        );
+        let swagger = Spec::builder().json(r#"{"swagger": "2.0"}"#);
+        assert!(set_servers(&swagger, &servers, false, None).is_none());
+    }
+
+    #[test]
+    fn lists_the_default_server_first() {
+        let spec = Spec::builder().json(r#"{"openapi": "3.0.3"}"#);
+        let servers = [
+            Server::new("https://dev.example.com").label("Dev"),
+            Server::new("https://staging.example.com").label("Staging"),
+            Server::new("https://example.com").label("Prod"),
+        ];
+        let first = |default| {
+            let spec = set_servers(&spec, &servers, false, Some(default)).unwrap();
+            serde_json::from_slice::<Value>(&spec.content).unwrap()["servers"][0]["url"].clone()
+        };
+
+        assert_eq!(first("Staging"), "https://staging.example.com");
+        assert_eq!(first("https://example.com"), "https://example.com");
+        assert_eq!(first("Unknown"), "https://dev.example.com");
+    }
+}
@@ -0,0 +1,2804 @@
+use crate::audit::Audit;
+use crate::handlers::{content, head, method_not_allowed, redirect, status};
+use crate::lru::Lru;
+use crate::{
+    insomnia_export, json, mime, postman_collection, precompressed, query, serve_spec,
+    spec_markdown, AccessEvent, CachePolicy, Config, Cors, DocUi, QueryParam, Spec, SpecOrUrl,
+    SwaggerUi, UrlObject,
+};
+use bytes::{Bytes, BytesMut};
+use http::header::{CACHE_CONTROL, VARY};
+use http::{Extensions, HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode};
+use http_body::{Frame, SizeHint};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Request extension telling [`SwaggerUiService`] under which public path it is mounted.
+///
+/// Adapters that strip the mount prefix from the request uri before handing it to the service
+/// should insert this, so that redirects and the generated config point to the right place.
+#[derive(Debug, Clone, Default)]
+pub struct MountPath(pub String);
+
+impl MountPath {
+    /// Create a `MountPath` from the request path as seen before the prefix was stripped and the
+    /// remaining path the service receives.
+    pub fn from_original(original: &str, rest: &str) -> Self {
+        let base = original
+            .strip_suffix(rest)
+            .unwrap_or(original)
+            .trim_end_matches('/');
+        Self(base.to_string())
+    }
+}
+
+/// Response body produced by [`SwaggerUiService`].
+///
+/// The body is a sequence of `Bytes` chunks, which are all views into the original data, so
+/// large content can be streamed without copying it.
+#[derive(Debug, Default)]
+pub struct Body(VecDeque<Bytes>);
+
+impl Body {
+    /// Create an empty body.
+    pub fn empty() -> Self {
+        Self(VecDeque::new())
+    }
+
+    /// Create a body that yields `content` in chunks of at most `chunk_size` bytes.
+    pub fn chunked(content: Bytes, chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let chunks = (0..content.len())
+            .step_by(chunk_size)
+            .map(|start| content.slice(start..content.len().min(start + chunk_size)))
+            .collect();
+        Self(chunks)
+    }
+
+    /// Consume the body, returning its chunks in order.
+    pub fn into_chunks(self) -> impl Iterator<Item = Bytes> {
+        self.0.into_iter()
+    }
+
+    /// Consume the body, returning its content.
+    ///
+    /// This only copies when the body consists of more than one chunk.
+    pub fn into_bytes(mut self) -> Bytes {
+        match self.0.len() {
+            0 => Bytes::new(),
+            1 => self.0.pop_front().unwrap_or_default(),
+            _ => {
+                let mut content = BytesMut::with_capacity(self.0.iter().map(Bytes::len).sum());
+                self.0
+                    .iter()
+                    .for_each(|chunk| content.extend_from_slice(chunk));
+                content.freeze()
+            }
+        }
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(value: Bytes) -> Self {
+        Self::chunked(value, usize::MAX)
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(value: Vec<u8>) -> Self {
+        Bytes::from(value).into()
+    }
+}
+
+impl From<Cow<'static, [u8]>> for Body {
+    fn from(value: Cow<'static, [u8]>) -> Self {
+        match value {
+            Cow::Borrowed(slice) => Bytes::from_static(slice).into(),
+            Cow::Owned(vec) => vec.into(),
+        }
+    }
+}
+
+impl http_body::Body for Body {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        Poll::Ready(self.0.pop_front().map(|bytes| Ok(Frame::data(bytes))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.0.iter().map(|bytes| bytes.len() as u64).sum())
+    }
+}
+
+/// Maximum number of variants, e.g. mount paths, the serialized config and the served specs are
+/// each cached for.
+const CONFIG_CACHE_SIZE: usize = 64;
+
+/// Page served instead of `index.html` if the spec couldn't be loaded, see [`DocService::try_new`].
+const UNAVAILABLE_PAGE: &str = include_str!("unavailable.html");
+
+/// Path the spec is answered with `503 Service Unavailable` at if it couldn't be loaded.
+const UNAVAILABLE_SPEC_PATH: &str = "openapi.json";
+
+/// Path of the Markdown rendering of the spec, see [`DocService::with_markdown`].
+const MARKDOWN_PATH: &str = "openapi.md";
+
+/// Path of the Postman collection of the spec, see [`DocService::with_postman`].
+const POSTMAN_PATH: &str = "postman.json";
+
+/// Path of the Insomnia export of the spec, see [`DocService::with_insomnia`].
+const INSOMNIA_PATH: &str = "insomnia.json";
+
+/// Path of the spec as it was before minifying it, see [`DocService::with_minified_spec`].
+const FULL_SPEC_PATH: &str = "openapi.full.json";
+
+/// Path of the JSON Schema export of the schemas, see [`DocService::with_json_schemas`].
+const SCHEMAS_PATH: &str = "schemas.json";
+
+/// Path of the stylesheet of the theme, see [`DocService::with_theme`].
+const THEME_PATH: &str = "theme.css";
+
+/// Directory of the specs sliced by tag, see [`DocService::with_tag_specs`].
+const TAG_SPECS_DIR: &str = "specs";
+
+/// Header with the `info.version` of the served spec, see [`DocService::with_spec_headers`].
+const SPEC_VERSION: HeaderName = HeaderName::from_static("x-spec-version");
+
+/// Header with a digest of the served spec, see [`DocService::with_spec_headers`].
+const SPEC_DIGEST: HeaderName = HeaderName::from_static("x-spec-digest");
+
+/// Size up to which the stylesheets and scripts of `index.html` are inlined, see
+/// [`DocService::with_inlined_assets`].
+const INLINE_LIMIT: usize = 16 * 1024;
+
+/// A document generated from the spec, e.g. its Markdown rendering.
+#[derive(Clone)]
+struct Generated {
+    /// Path relative to the mount point.
+    path: Cow<'static, str>,
+    content_type: HeaderValue,
+    /// The document generated from the whole spec.
+    content: Bytes,
+    /// The spec it is generated from, filtered for requests that may only see part of it.
+    source: Spec,
+    render: Render,
+}
+
+/// Generates a document from the spec, or `None` if there is nothing to serve.
+type Render = Arc<dyn Fn(&Spec) -> Option<Bytes> + Send + Sync>;
+
+/// What the served config depends on besides the options.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ConfigVariant {
+    base: String,
+    /// The forwarded query parameters, see [`query::normalize`].
+    query: Vec<(QueryParam, String)>,
+    pretty: bool,
+    /// The specs left out of the listed `urls`, see [`DocService::with_spec_acl`].
+    hidden: BTreeSet<String>,
+}
+
+/// What a served spec depends on besides itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SpecVariant {
+    path: String,
+    /// The refresh of the remote spec it is from, see [`DocService::with_remote_spec`].
+    generation: u64,
+    /// The precompressed encoding it is served with, if the request accepts it.
+    encoding: Option<&'static str>,
+    /// The tags and scopes the spec is filtered down to, see [`DocService::with_spec_access`].
+    allowed: Option<BTreeSet<String>>,
+    /// The feature flags whose operations are left out, see [`DocService::with_feature_flags`].
+    disabled: BTreeSet<String>,
+    /// Whether the spec is pretty-printed, compacted or served as it is.
+    pretty: Option<bool>,
+}
+
+/// A spec as it is served, with its headers.
+#[derive(Clone)]
+struct CachedSpec {
+    spec: Spec,
+    headers: HeaderMap,
+}
+
+/// Returns the tags and scopes of the spec a request may see, or `None` for all of it.
+type Access = Arc<dyn Fn(&HeaderMap, &Extensions) -> Option<BTreeSet<String>> + Send + Sync>;
+
+/// Returns whether a request may see the docs, see [`DocService::with_guard`].
+type Guard = Arc<dyn Fn(&HeaderMap, &Extensions) -> bool + Send + Sync>;
+
+struct Inner<U: DocUi> {
+    spec: SpecOrUrl,
+    /// Further specs served next to `spec`, see [`DocService::multi`].
+    specs: Vec<Spec>,
+    /// The spec before it was minified, see [`DocService::with_minified_spec`].
+    full_spec: Option<Spec>,
+    /// Whether the spec couldn't be loaded, see [`DocService::try_new`].
+    unavailable: bool,
+    config: U::Config,
+    cors: Option<Cors>,
+    /// Query parameters forwarded into the config.
+    query_params: Vec<QueryParam>,
+    /// Applies the forwarded query parameters to the config, returning whether any was set.
+    apply_query: fn(&[(QueryParam, String)], &mut U::Config),
+    /// Serialized config per mount path, forwarded query and format, the `url` in it depends on
+    /// where the service is mounted.
+    config_cache: Lru<ConfigVariant, Bytes>,
+    /// `index.html` per mount path, or `None` if the asset is served as it is.
+    index_cache: Lru<String, Option<Bytes>>,
+    /// Documents generated from the spec, served next to it.
+    generated: Vec<Generated>,
+    /// The generated documents as served to requests that may only see part of the spec, per
+    /// path and variant, or `None` if there is nothing to serve.
+    generated_cache: Lru<SpecVariant, Option<Bytes>>,
+    /// Maps requests to what they may see of the spec, see [`DocService::with_spec_access`].
+    access: Option<Access>,
+    /// Decides which flagged operations are served, with the flags the specs name.
+    flags: Option<(Arc<dyn crate::FlagProvider>, BTreeSet<String>)>,
+    /// The specs as served, per path, set of allowed tags and scopes and format, or `None` if
+    /// they can't be filtered.
+    spec_cache: Lru<SpecVariant, Option<CachedSpec>>,
+    /// Identifies who requested which document, see [`DocService::with_access_audit`].
+    audit: Option<Audit>,
+    guard: Option<Guard>,
+    cache_policy: Option<CachePolicy>,
+    /// Whether the spec and config are pretty-printed unless a request asks otherwise.
+    pretty_json: bool,
+    /// Whether the spec is served with its version and digest, see
+    /// [`DocService::with_spec_headers`].
+    spec_headers: bool,
+    /// Whether `index.html` shows banners on deprecated operations.
+    sunset_banner: bool,
+    /// Whether `index.html` inlines the small stylesheets and scripts it loads.
+    inline_assets: bool,
+    /// The stylesheet of the theme, loaded by `index.html`.
+    theme: Option<Bytes>,
+    /// Terms shown in place of `index.html` until they are accepted.
+    terms: Option<crate::Terms>,
+    /// Snippet added to `index.html` for visitors who don't object to tracking.
+    analytics: Option<crate::Analytics>,
+    /// Who may see the spec served at a path, see [`DocService::with_spec_acl`].
+    acls: Vec<(String, crate::Acl)>,
+    /// Whether `index.html` routes try-it-out requests through the proxy next to it.
+    #[cfg(feature = "proxy")]
+    proxy: bool,
+    /// The remote spec refreshed in the background and the refresh `spec` is from, see
+    /// [`DocService::with_remote_spec`].
+    #[cfg(feature = "fetch")]
+    remote: Option<(crate::RemoteSpec, u64)>,
+    /// The later copy of the remote spec as served, with the refresh it is from and the feature
+    /// flags the specs name with it.
+    #[cfg(feature = "fetch")]
+    refreshed: std::sync::RwLock<Option<(u64, Spec, BTreeSet<String>)>>,
+    /// Whether `index.html` keeps the authorization in the session instead of `localStorage`.
+    #[cfg(feature = "sessions")]
+    session_authorization: bool,
+    /// SRI hashes of the scripts and stylesheets, added to the tags loading them.
+    #[cfg(feature = "integrity")]
+    integrity: Option<std::collections::HashMap<String, String>>,
+    /// Only allows requests with a link it signed, see [`DocService::with_signed_urls`].
+    #[cfg(feature = "signed-urls")]
+    signer: Option<crate::UrlSigner>,
+}
+
+impl<U: DocUi> Clone for Inner<U> {
+    fn clone(&self) -> Self {
+        Self {
+            spec: self.spec.clone(),
+            specs: self.specs.clone(),
+            full_spec: self.full_spec.clone(),
+            unavailable: self.unavailable,
+            config: self.config.clone(),
+            cors: self.cors.clone(),
+            query_params: self.query_params.clone(),
+            apply_query: self.apply_query,
+            config_cache: Lru::new(CONFIG_CACHE_SIZE),
+            index_cache: Lru::new(CONFIG_CACHE_SIZE),
+            generated: self.generated.clone(),
+            generated_cache: Lru::new(CONFIG_CACHE_SIZE),
+            access: self.access.clone(),
+            flags: self.flags.clone(),
+            spec_cache: Lru::new(CONFIG_CACHE_SIZE),
+            audit: self.audit.clone(),
+            guard: self.guard.clone(),
+            cache_policy: self.cache_policy.clone(),
+            pretty_json: self.pretty_json,
+            spec_headers: self.spec_headers,
+            sunset_banner: self.sunset_banner,
+            inline_assets: self.inline_assets,
+            theme: self.theme.clone(),
+            terms: self.terms.clone(),
+            analytics: self.analytics.clone(),
+            acls: self.acls.clone(),
+            #[cfg(feature = "proxy")]
+            proxy: self.proxy,
+            #[cfg(feature = "fetch")]
+            remote: self.remote.clone(),
+            #[cfg(feature = "fetch")]
+            refreshed: Default::default(),
+            #[cfg(feature = "sessions")]
+            session_authorization: self.session_authorization,
+            #[cfg(feature = "integrity")]
+            integrity: self.integrity.clone(),
+            #[cfg(feature = "signed-urls")]
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+impl<U: DocUi> Inner<U> {
+    /// Returns whether requests for `path` are audited: `index.html`, the spec and the documents
+    /// generated from it, but neither the assets nor the config, which are only loaded by
+    /// `index.html`.
+    fn is_audited(&self, path: &str) -> bool {
+        path == "index.html"
+            || self.spec_at(path).is_some()
+            || self
+                .generated
+                .iter()
+                .any(|generated| generated.path == path)
+    }
+
+    /// Returns whether `path` is the spec or the config, which tooling may fetch cross-origin.
+    fn is_document(&self, path: &str) -> bool {
+        U::CONFIG_PATH == Some(path) || self.spec_at(path).is_some()
+    }
+
+    /// Returns the spec served at `path`, if any.
+    fn spec_at(&self, path: &str) -> Option<&Spec> {
+        let primary = match &self.spec {
+            SpecOrUrl::Spec(spec) => Some(spec),
+            SpecOrUrl::Url(_) => None,
+        };
+        primary
+            .into_iter()
+            .chain(&self.specs)
+            .chain(&self.full_spec)
+            .find(|spec| spec.name.trim_start_matches('/') == path)
+    }
+
+    /// Returns the spec served at `path` like [`spec_at`](Self::spec_at), with the last copy of
+    /// a remote spec refreshed since the service was built in place of the spec.
+    fn current_spec_at(&self, path: &str) -> Option<(Cow<'_, Spec>, u64)> {
+        let spec = self.spec_at(path)?;
+        #[cfg(feature = "fetch")]
+        if let (Some((remote, initial)), SpecOrUrl::Spec(primary)) = (&self.remote, &self.spec) {
+            if std::ptr::eq(spec, primary) && remote.generation() != *initial {
+                let (generation, refreshed) = self.refreshed_spec(remote);
+                return Some((Cow::Owned(refreshed), generation));
+            }
+        }
+        Some((Cow::Borrowed(spec), 0))
+    }
+
+    /// Returns the last copy of `remote` as served, prepared once per refresh that changed it.
+    #[cfg(feature = "fetch")]
+    fn refreshed_spec(&self, remote: &crate::RemoteSpec) -> (u64, Spec) {
+        let generation = remote.generation();
+        if let Some((refreshed, spec, _)) = &*self.refreshed.read().unwrap() {
+            if *refreshed == generation {
+                return (generation, spec.clone());
+            }
+        }
+        let (generation, latest) = remote.latest();
+        let latest = U::prepare_spec(&latest, &self.config).unwrap_or(latest);
+        let names = match self.flags {
+            Some(_) => self.flag_names(Some(&latest)),
+            None => BTreeSet::new(),
+        };
+        *self.refreshed.write().unwrap() = Some((generation, latest.clone(), names));
+        // the cached copies are of a previous one, and keyed by it in case one is still inserted
+        self.spec_cache.clear();
+        (generation, latest)
+    }
+
+    /// Returns the feature flags `primary`, the further specs and the full spec name, see
+    /// [`DocService::with_feature_flags`].
+    fn flag_names(&self, primary: Option<&Spec>) -> BTreeSet<String> {
+        primary
+            .into_iter()
+            .chain(&self.specs)
+            .chain(&self.full_spec)
+            .flat_map(crate::filter::flag_names)
+            .collect()
+    }
+
+    /// Returns the feature flags of the specs the flag provider doesn't enable now, with those
+    /// the copy of the remote spec from refresh `generation` names, `0` for the spec itself.
+    fn disabled_flags(&self, generation: u64) -> BTreeSet<String> {
+        let Some((flags, names)) = &self.flags else {
+            return BTreeSet::new();
+        };
+        #[cfg(feature = "fetch")]
+        let refreshed = self.refreshed.read().unwrap();
+        #[cfg(feature = "fetch")]
+        let names = match &*refreshed {
+            Some((refreshed, _, refreshed_names))
+                if generation != 0 && *refreshed == generation =>
+            {
+                refreshed_names
+            }
+            _ => names,
+        };
+        #[cfg(not(feature = "fetch"))]
+        let _ = generation;
+        names
+            .iter()
+            .filter(|name| !flags.is_enabled(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the url of the spec for a service mounted at `base`.
+    fn spec_url(&self, base: &str) -> String {
+        match &self.spec {
+            SpecOrUrl::Spec(spec) => format!("{base}/{}", spec.name.trim_start_matches('/')),
+            SpecOrUrl::Url(url) => crate::handlers::resolve(base, url),
+        }
+    }
+
+    /// Returns the spec at `path` as it is served for `variant`, filtered and formatted once per
+    /// variant, or `None` if it can't be filtered.
+    fn served_spec(&self, spec: &Spec, variant: SpecVariant) -> Option<CachedSpec> {
+        if let Some(served) = self.spec_cache.get(&variant) {
+            return served;
+        }
+
+        let flagged = match variant.disabled.is_empty() {
+            true => None,
+            false => crate::flag_spec(spec, &|flag: &str| !variant.disabled.contains(flag)),
+        };
+        let spec = flagged.as_ref().unwrap_or(spec);
+        let filtered = match &variant.allowed {
+            Some(allowed) => crate::filter_spec(spec, allowed),
+            None => Some(spec.clone()),
+        };
+        let served = filtered.map(|filtered| {
+            let mut headers = HeaderMap::new();
+            if self.spec_headers {
+                add_spec_headers(&filtered, &mut headers);
+            }
+            if filtered.precompressed.is_some() {
+                headers.append(VARY, HeaderValue::from_static("Accept-Encoding"));
+            }
+            let formatted = variant
+                .pretty
+                .and_then(|pretty| json::reformat(&filtered.content, pretty));
+            let spec = match formatted {
+                Some(content) => Spec {
+                    content,
+                    precompressed: None,
+                    ..filtered
+                },
+                None => Spec {
+                    precompressed: filtered
+                        .precompressed
+                        .filter(|precompressed| Some(precompressed.encoding) == variant.encoding),
+                    ..filtered
+                },
+            };
+            CachedSpec { spec, headers }
+        });
+
+        self.spec_cache.insert(variant, served.clone());
+        served
+    }
+
+    /// Returns the tags and scopes of the spec the request with `headers` and `extensions` may
+    /// see, or `None` for all of it, see [`DocService::with_spec_access`].
+    fn allowed(&self, headers: &HeaderMap, extensions: &Extensions) -> Option<BTreeSet<String>> {
+        self.access
+            .as_ref()
+            .and_then(|access| access(headers, extensions))
+    }
+
+    /// Serves `generated` to a request that may only see the `allowed` tags and scopes of the
+    /// spec, and not the operations behind the `disabled` feature flags, generated from the
+    /// filtered spec once per variant.
+    fn serve_generated(
+        &self,
+        generated: &Generated,
+        allowed: Option<BTreeSet<String>>,
+        disabled: BTreeSet<String>,
+    ) -> Response<Body> {
+        if allowed.is_none() && disabled.is_empty() {
+            return content(
+                generated.content_type.clone(),
+                generated.content.clone().into(),
+            );
+        }
+        let variant = SpecVariant {
+            path: generated.path.to_string(),
+            generation: 0,
+            encoding: None,
+            allowed,
+            disabled,
+            pretty: None,
+        };
+        let document = match self.generated_cache.get(&variant) {
+            Some(document) => document,
+            None => {
+                let Some(filtered) = self.served_spec(&generated.source, variant.clone()) else {
+                    return status(StatusCode::FORBIDDEN);
+                };
+                let document = (generated.render)(&filtered.spec);
+                self.generated_cache.insert(variant, document.clone());
+                document
+            }
+        };
+        match document {
+            Some(document) => content(generated.content_type.clone(), document.into()),
+            None => status(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// Returns the paths of the specs the request with `headers` and `extensions` may not see.
+    fn hidden_specs(&self, headers: &HeaderMap, extensions: &Extensions) -> BTreeSet<String> {
+        self.acls
+            .iter()
+            .filter(|(_, acl)| !acl.allows(headers, extensions))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns whether `path` is one of the `hidden` specs, or a document generated from the
+    /// spec, e.g. `openapi.md` or a spec per tag, while it is hidden.
+    fn is_hidden(&self, path: &str, hidden: &BTreeSet<String>) -> bool {
+        if hidden.is_empty() {
+            return false;
+        }
+        let primary_hidden = match &self.spec {
+            SpecOrUrl::Spec(spec) => hidden.contains(spec.name.trim_start_matches('/')),
+            SpecOrUrl::Url(_) => false,
+        };
+        let full_spec = self.full_spec.is_some() && path == FULL_SPEC_PATH;
+        hidden.contains(path)
+            || primary_hidden
+                && (full_spec
+                    || self
+                        .generated
+                        .iter()
+                        .any(|generated| generated.path == path))
+    }
+
+    /// Drops the cached configs and specs, after the spec or the options they are generated with
+    /// changed.
+    fn invalidate(&mut self) {
+        self.config_cache.clear();
+        self.index_cache.clear();
+        self.spec_cache.clear();
+        self.generated_cache.clear();
+        #[cfg(feature = "fetch")]
+        {
+            self.refreshed = Default::default();
+        }
+    }
+
+    /// Returns the serialized config for a service mounted at `base`, with the allowed
+    /// parameters of `query` applied.
+    fn config_json(
+        &self,
+        base: &str,
+        query: Option<&str>,
+        pretty: bool,
+        hidden: BTreeSet<String>,
+    ) -> Bytes {
+        let key = ConfigVariant {
+            base: base.to_string(),
+            query: query::normalize(&self.query_params, query),
+            pretty,
+            hidden,
+        };
+        if let Some(json) = self.config_cache.get(&key) {
+            return json;
+        }
+
+        let mut config = self.config.clone();
+        (self.apply_query)(&key.query, &mut config);
+        let mut json = U::serialize_config(&self.spec_url(base), config);
+        if !key.hidden.is_empty() {
+            json = hide_urls(&json, base, &key.hidden).unwrap_or(json);
+        }
+        let json = match pretty {
+            true => json::reformat(&json, true).unwrap_or(json),
+            false => json,
+        };
+
+        self.config_cache.insert(key, json.clone());
+        json
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec and config with the viewer
+/// `U`, see [`DocUi`].
+///
+/// Request paths are interpreted relative to the mount point, see [`MountPath`].
+pub struct DocService<U: DocUi> {
+    inner: Arc<Inner<U>>,
+}
+
+/// A framework-agnostic `tower::Service` serving swagger-ui with the specified spec and config.
+pub type SwaggerUiService = DocService<SwaggerUi>;
+
+impl<U: DocUi> Clone for DocService<U> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<U: DocUi> DocService<U> {
+    /// Create a service serving the specified spec and config with the viewer.
+    ///
+    /// With the `verify-assets` feature, the first service created checks the embedded
+    /// swagger-ui assets against the hashes recorded at build time, and panics if any differ.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<U::Config>>) -> Self {
+        #[cfg(feature = "verify-assets")]
+        {
+            static VERIFIED: std::sync::Once = std::sync::Once::new();
+            VERIFIED.call_once(|| {
+                if let Err(mismatch) = swagger_ui_assets::verify() {
+                    panic!("Refusing to serve corrupted assets: {mismatch}");
+                }
+            });
+        }
+        let config = config.into().unwrap_or_default();
+        let spec = match spec.into() {
+            SpecOrUrl::Spec(spec) => {
+                SpecOrUrl::Spec(U::prepare_spec(&spec, &config).unwrap_or(spec))
+            }
+            url => url,
+        };
+        Self {
+            inner: Arc::new(Inner {
+                spec,
+                specs: vec![],
+                full_spec: None,
+                unavailable: false,
+                config,
+                cors: None,
+                query_params: vec![],
+                apply_query: |_, _| {},
+                config_cache: Lru::new(CONFIG_CACHE_SIZE),
+                index_cache: Lru::new(CONFIG_CACHE_SIZE),
+                generated: vec![],
+                generated_cache: Lru::new(CONFIG_CACHE_SIZE),
+                access: None,
+                flags: None,
+                spec_cache: Lru::new(CONFIG_CACHE_SIZE),
+                audit: None,
+                guard: None,
+                cache_policy: None,
+                pretty_json: false,
+                spec_headers: false,
+                sunset_banner: false,
+                inline_assets: false,
+                theme: None,
+                terms: None,
+                analytics: None,
+                acls: vec![],
+                #[cfg(feature = "proxy")]
+                proxy: false,
+                #[cfg(feature = "fetch")]
+                remote: None,
+                #[cfg(feature = "fetch")]
+                refreshed: Default::default(),
+                #[cfg(feature = "sessions")]
+                session_authorization: false,
+                #[cfg(feature = "integrity")]
+                integrity: None,
+                #[cfg(feature = "signed-urls")]
+                signer: None,
+            }),
+        }
+    }
+
+    /// Create a service serving the spec `spec` loaded, e.g. by
+    /// [`try_swagger_spec_file!`](crate::try_swagger_spec_file), or a page saying the docs are
+    /// temporarily unavailable instead of `index.html` if it failed to.
+    ///
+    /// The error is logged, and the spec is answered with `503 Service Unavailable` at
+    /// `openapi.json`, where the viewer looks for it.
+    pub fn try_new<S, E>(spec: Result<S, E>, config: impl Into<Option<U::Config>>) -> Self
+    where
+        S: Into<SpecOrUrl>,
+        E: std::fmt::Display,
+    {
+        match spec {
+            Ok(spec) => Self::new(spec, config),
+            Err(err) => {
+                log::error!("Could not load the spec, the docs are unavailable: {err}");
+                let mut service = Self::new(SpecOrUrl::Url(UNAVAILABLE_SPEC_PATH.into()), config);
+                Arc::make_mut(&mut service.inner).unavailable = true;
+                service
+            }
+        }
+    }
+
+    /// Serve the last copy of `remote`, which is downloaded again in the background, in place of
+    /// the spec, see [`RemoteSpec::start`](crate::RemoteSpec::start).
+    ///
+    /// The copy downloaded by now goes through the `with_*` methods called after this one, like
+    /// any spec. Later copies are only prepared for the config, e.g. with its
+    /// [`servers`](crate::Config::servers), and the documents generated from the spec, e.g. by
+    /// [`with_markdown`](Self::with_markdown), aren't refreshed.
+    #[cfg(feature = "fetch")]
+    pub fn with_remote_spec(mut self, remote: crate::RemoteSpec) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        let (generation, spec) = remote.latest();
+        inner.spec = SpecOrUrl::Spec(U::prepare_spec(&spec, &inner.config).unwrap_or(spec));
+        inner.remote = Some((remote, generation));
+        inner.invalidate();
+        self
+    }
+
+    /// Emit the specified CORS headers on the spec and config responses.
+    pub fn with_cors(mut self, cors: Cors) -> Self {
+        Arc::make_mut(&mut self.inner).cors = Some(cors);
+        self
+    }
+
+    /// Additionally serve the spec rendered into Markdown at `openapi.md`, see
+    /// [`spec_markdown`](crate::spec_markdown).
+    ///
+    /// The spec is rendered once, here. Nothing is served for specs that aren't JSON or that
+    /// are only linked by url.
+    pub fn with_markdown(self) -> Self {
+        self.with_generated(MARKDOWN_PATH, mime::MARKDOWN, spec_markdown)
+    }
+
+    /// Additionally serve the spec converted into a Postman v2.1 collection at `postman.json`,
+    /// see [`postman_collection`](crate::postman_collection).
+    ///
+    /// Like with [`with_markdown`](Self::with_markdown), the collection is generated once,
+    /// here, and only for JSON specs.
+    pub fn with_postman(self) -> Self {
+        self.with_generated(POSTMAN_PATH, mime::JSON, postman_collection)
+    }
+
+    /// Additionally serve the spec converted into an Insomnia export at `insomnia.json`, see
+    /// [`insomnia_export`](crate::insomnia_export).
+    ///
+    /// The export is generated once, here, and only for JSON specs.
+    pub fn with_insomnia(self) -> Self {
+        self.with_generated(INSOMNIA_PATH, mime::JSON, insomnia_export)
+    }
+
+    /// Serve the spec without descriptions, examples and vendor extensions, see
+    /// [`minify_spec`](crate::minify_spec), and the spec as it is at `openapi.full.json`.
+    ///
+    /// The spec is minified once, here, and only if it is JSON. Call this after the transforms
+    /// whose output the full spec should have, like [`with_info`](Self::with_info). The full spec
+    /// is served like the spec, e.g. filtered by [`with_spec_access`](Self::with_spec_access) and
+    /// pretty-printed with `?pretty=1`.
+    pub fn with_minified_spec(mut self) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        let SpecOrUrl::Spec(spec) = &inner.spec else {
+            return self;
+        };
+        let Some(minified) = crate::minify_spec(spec) else {
+            return self;
+        };
+        inner.full_spec = Some(Spec {
+            name: FULL_SPEC_PATH.into(),
+            ..spec.clone()
+        });
+        inner.spec = SpecOrUrl::Spec(minified);
+        inner.invalidate();
+        self
+    }
+
+    /// Serve the spec and config pretty-printed, for people opening them in a browser, unless a
+    /// request asks for compact JSON with `?pretty=0`.
+    ///
+    /// By default the config is compact and the spec is served as it is, either can be asked for
+    /// pretty-printed with `?pretty=1`. Reformatting keeps the order of the keys, and specs that
+    /// aren't JSON are always served as they are.
+    pub fn with_pretty_json(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).pretty_json = true;
+        self
+    }
+
+    /// Send the `info.version` of the spec in an `X-Spec-Version` header and a digest of its
+    /// content in an `X-Spec-Digest` header, so gateways and SDK pipelines can tell whether the
+    /// spec changed without parsing it.
+    ///
+    /// The digest is a 64-bit FNV-1a hash in hex, of the spec as it is stored, so it doesn't
+    /// change with `?pretty`. It detects changes, but doesn't protect against tampering. Specs
+    /// without a version, e.g. YAML ones, get the digest only.
+    pub fn with_spec_headers(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).spec_headers = true;
+        self
+    }
+
+    /// Additionally serve the schemas of the spec as a JSON Schema document at `schemas.json`,
+    /// for validators and code generators, see [`json_schemas`](crate::json_schemas).
+    ///
+    /// Like with [`with_markdown`](Self::with_markdown), the document is generated once, here,
+    /// and only for JSON specs with schemas.
+    pub fn with_json_schemas(self) -> Self {
+        self.with_generated(SCHEMAS_PATH, mime::JSON, crate::json_schemas)
+    }
+
+    /// Additionally serve a slice of the spec per tag at `specs/{tag}.json`, with only the
+    /// operations tagged with it and the components they refer to, see
+    /// [`tag_spec`](crate::tag_spec).
+    ///
+    /// Tags are lowercased in the file names, and characters other than letters, digits, `-`,
+    /// `_` and `.` replaced with `-`, e.g. `specs/pet-store.json` for `Pet store`. Tags that end
+    /// up with the name of an earlier one get a number, e.g. `specs/pet-store-2.json` for
+    /// `pet-store`. The slices are generated once, here, and only for JSON specs. Like the other
+    /// generated documents, they are filtered by [`with_spec_access`](Self::with_spec_access)
+    /// and [`with_feature_flags`](Self::with_feature_flags), and requests get `404 Not Found`
+    /// for the tags without operations they may see.
+    pub fn with_tag_specs(mut self) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        inner
+            .generated
+            .retain(|generated| !generated.path.starts_with(&format!("{TAG_SPECS_DIR}/")));
+        let SpecOrUrl::Spec(spec) = &inner.spec else {
+            return self;
+        };
+        let mut names = std::collections::HashSet::new();
+        for tag in crate::filter::tag_names(spec) {
+            let Some(slice) = crate::tag_spec(spec, &tag) else {
+                continue;
+            };
+            let slug: String = tag
+                .chars()
+                .map(|c| match c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                    true => c.to_ascii_lowercase(),
+                    false => '-',
+                })
+                .collect();
+            let mut name = slug.clone();
+            for n in 2.. {
+                if names.insert(name.clone()) {
+                    break;
+                }
+                name = format!("{slug}-{n}");
+            }
+            inner.generated.push(Generated {
+                path: format!("{TAG_SPECS_DIR}/{name}.json").into(),
+                content_type: mime::JSON,
+                content: slice.content,
+                source: spec.clone(),
+                render: Arc::new(move |spec| {
+                    // a tag the filtered spec has no operations for doesn't exist for the request
+                    crate::filter::tag_names(spec)
+                        .contains(&tag)
+                        .then(|| crate::tag_spec(spec, &tag))?
+                        .map(|slice| slice.content)
+                }),
+            });
+        }
+        self
+    }
+
+    /// Serves what `render` generates from the spec at `path`, if it generates anything.
+    fn with_generated(
+        mut self,
+        path: &'static str,
+        content_type: HeaderValue,
+        render: fn(&Spec) -> Option<String>,
+    ) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        inner.generated.retain(|generated| generated.path != path);
+        let SpecOrUrl::Spec(spec) = &inner.spec else {
+            return self;
+        };
+        if let Some(content) = render(spec) {
+            inner.generated.push(Generated {
+                path: path.into(),
+                content_type,
+                content: Bytes::from(content),
+                source: spec.clone(),
+                render: Arc::new(move |spec| render(spec).map(Bytes::from)),
+            });
+        }
+        self
+    }
+
+    /// Add Subresource Integrity hashes to the tags of `index.html` loading scripts and
+    /// stylesheets, with `crossorigin="anonymous"`, so browsers refuse assets that were tampered
+    /// with, e.g. by a caching proxy or CDN in front of the service.
+    ///
+    /// The assets are hashed once, here.
+    #[cfg(feature = "integrity")]
+    pub fn with_integrity(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).integrity = Some(crate::integrity::hashes::<U>());
+        self
+    }
+
+    /// Serve the spec with the credentials in its examples replaced by `redactor`, see
+    /// [`Redactor`](crate::Redactor).
+    ///
+    /// The spec is redacted once, here. Call this before adding generated documents like
+    /// [`with_markdown`](Self::with_markdown), which are rendered from the spec as it is then.
+    #[cfg(feature = "redact")]
+    pub fn with_redaction(mut self, redactor: &crate::Redactor) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        if let SpecOrUrl::Spec(spec) = &inner.spec {
+            inner.spec = SpecOrUrl::Spec(redactor.redact(spec));
+        }
+        inner.invalidate();
+        self
+    }
+
+    /// Serve the spec with the version, contact, license and external docs of `info` set, see
+    /// [`SpecInfo`](crate::SpecInfo).
+    ///
+    /// Like with [`with_redaction`](Self::with_redaction), the spec is changed once, here, and
+    /// specs that aren't JSON are served as they are.
+    pub fn with_info(mut self, info: &crate::SpecInfo) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        if let SpecOrUrl::Spec(spec) = &inner.spec {
+            if let Some(spec) = info.apply(spec) {
+                inner.spec = SpecOrUrl::Spec(spec);
+            }
+        }
+        inner.invalidate();
+        self
+    }
+
+    /// Serve the spec with the keys of its objects sorted, so every deployment of the same
+    /// spec serves the same bytes, see [`sort_spec`](crate::sort_spec).
+    ///
+    /// Like with [`with_redaction`](Self::with_redaction), the spec is changed once, here, and
+    /// specs that aren't JSON are served as they are.
+    pub fn with_sorted_keys(mut self) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        if let SpecOrUrl::Spec(spec) = &inner.spec {
+            if let Some(spec) = crate::sort_spec(spec) {
+                inner.spec = SpecOrUrl::Spec(spec);
+            }
+        }
+        inner.invalidate();
+        self
+    }
+
+    /// Serve the spec with its OpenAPI 3.1 webhooks copied into its paths under `tag`, for
+    /// viewers that don't show the `webhooks` section, see
+    /// [`webhooks_as_tag`](crate::webhooks_as_tag).
+    ///
+    /// Like with [`with_redaction`](Self::with_redaction), the spec is changed once, here, and
+    /// specs that aren't JSON are served as they are.
+    pub fn with_webhooks_tag(mut self, tag: &str) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        if let SpecOrUrl::Spec(spec) = &inner.spec {
+            if let Some(spec) = crate::webhooks_as_tag(spec, tag) {
+                inner.spec = SpecOrUrl::Spec(spec);
+            }
+        }
+        inner.invalidate();
+        self
+    }
+
+    /// Serve every request the spec filtered down to the tags and scopes `allowed` maps it to,
+    /// from its headers and extensions, e.g. the roles of the authenticated partner. See
+    /// [`filter_spec`](crate::filter_spec) for which operations are kept.
+    ///
+    /// `allowed` returning `None` serves the whole spec. The spec is filtered once per set of
+    /// tags and scopes. Specs that can't be filtered because they aren't JSON are answered with
+    /// `403 Forbidden`. The documents generated from the spec, e.g. by
+    /// [`with_markdown`](Self::with_markdown), are generated again from the filtered spec, once
+    /// per set of tags and scopes as well.
+    pub fn with_spec_access(
+        mut self,
+        allowed: impl Fn(&HeaderMap, &Extensions) -> Option<BTreeSet<String>> + Send + Sync + 'static,
+    ) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        inner.access = Some(Arc::new(allowed));
+        inner.invalidate();
+        self
+    }
+
+    /// Serve the spec without the operations behind the feature flags `flags` doesn't enable,
+    /// see [`flag_spec`](crate::flag_spec), so unreleased endpoints don't show in the docs.
+    ///
+    /// `flags` is asked on every request for the spec, so released features show up without a
+    /// restart, and the spec is filtered once per set of disabled flags. The flags asked about
+    /// are those the specs name, with those of the latest copy of a remote spec in place of the
+    /// spec's. Like with [`with_spec_access`](Self::with_spec_access), the documents generated
+    /// from the spec are generated again without the operations left out.
+    pub fn with_feature_flags(mut self, flags: impl crate::FlagProvider + 'static) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        let primary = match &inner.spec {
+            SpecOrUrl::Spec(spec) => Some(spec),
+            SpecOrUrl::Url(_) => None,
+        };
+        let names = inner.flag_names(primary);
+        inner.flags = Some((Arc::new(flags), names));
+        inner.invalidate();
+        self
+    }
+
+    /// Call `hook` with an [`AccessEvent`] for every request for `index.html`, the spec or a
+    /// document generated from it, with the identity `identify` extracts from the headers and
+    /// extensions of the request, e.g. to record which employees viewed which docs.
+    ///
+    /// Requests for the assets and the config aren't passed, as every viewer of `index.html`
+    /// loads them.
+    pub fn with_access_audit(
+        mut self,
+        identify: impl Fn(&HeaderMap, &Extensions) -> Option<String> + Send + Sync + 'static,
+        hook: impl Fn(&AccessEvent) + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.inner).audit = Some(Audit {
+            identify: Arc::new(identify),
+            hook: Arc::new(hook),
+        });
+        self
+    }
+
+    /// Only allow requests `guard` accepts from their headers and extensions, answering others
+    /// with `401 Unauthorized`, e.g. to check an API key or the user set by an authentication
+    /// middleware.
+    ///
+    /// CORS preflight requests aren't passed, as browsers send them without credentials.
+    pub fn with_guard(
+        mut self,
+        guard: impl Fn(&HeaderMap, &Extensions) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.inner).guard = Some(Arc::new(guard));
+        self
+    }
+
+    /// Send successful responses with the `Cache-Control` of `policy`, unless they set their
+    /// own.
+    pub fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        Arc::make_mut(&mut self.inner).cache_policy = Some(policy);
+        self
+    }
+
+    /// Only allow requests by a link `signer` created, answering others with `403 Forbidden`,
+    /// e.g. to hand partners a link to the docs that expires.
+    ///
+    /// The first request with a valid link sets a cookie remembering it until it expires, for the
+    /// assets and the spec `index.html` loads. CORS preflight requests aren't checked, as browsers
+    /// send them without the cookie.
+    #[cfg(feature = "signed-urls")]
+    pub fn with_signed_urls(mut self, signer: crate::UrlSigner) -> Self {
+        Arc::make_mut(&mut self.inner).signer = Some(signer);
+        self
+    }
+
+    /// Only list and serve the spec named `name`, e.g. one of [`multi`](Self::multi), to the
+    /// requests `acl` allows, so every requester only sees the APIs they are entitled to.
+    ///
+    /// Other requests get the config without the spec in its `urls`, and `404 Not Found` for
+    /// the spec itself, as if it didn't exist.
+    pub fn with_spec_acl(mut self, name: &str, acl: crate::Acl) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        let name = name.trim_start_matches('/').to_string();
+        inner.acls.retain(|(other, _)| *other != name);
+        inner.acls.push((name, acl));
+        inner.invalidate();
+        self
+    }
+
+    /// Add the snippet of `analytics` to `index.html`, except for visitors sending `DNT: 1`,
+    /// see [`Analytics`](crate::Analytics).
+    ///
+    /// With [`with_terms`](Self::with_terms), the snippet is only added once the terms are
+    /// accepted, unless it is added [before consent](crate::Analytics::before_consent) too.
+    pub fn with_analytics(mut self, analytics: crate::Analytics) -> Self {
+        Arc::make_mut(&mut self.inner).analytics = Some(analytics);
+        self
+    }
+
+    /// Show `terms` in place of `index.html` until they are accepted, once per browser session,
+    /// e.g. the usage terms of partner-facing docs, see [`Terms`](crate::Terms).
+    ///
+    /// Accepting them posts to `terms` next to `index.html`, which records the consent in a
+    /// cookie and redirects to `index.html`. The spec and the other documents are served as
+    /// before, e.g. to code generators.
+    pub fn with_terms(mut self, terms: crate::Terms) -> Self {
+        Arc::make_mut(&mut self.inner).terms = Some(terms);
+        self
+    }
+
+    /// Returns the paths, relative to the mount point and without a leading `/`, this service
+    /// responds to. The empty path is the mount point itself, which redirects to `index.html`.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = vec![String::new(), "index.html".to_string()];
+        paths.extend(U::CONFIG_PATH.map(str::to_string));
+        paths.extend(U::asset_paths());
+        if let SpecOrUrl::Spec(spec) = &self.inner.spec {
+            paths.push(spec.name.trim_start_matches('/').to_string());
+        }
+        paths.extend(
+            self.inner
+                .specs
+                .iter()
+                .map(|spec| spec.name.trim_start_matches('/').to_string()),
+        );
+        if self.inner.full_spec.is_some() {
+            paths.push(FULL_SPEC_PATH.to_string());
+        }
+        if self.inner.theme.is_some() {
+            paths.push(THEME_PATH.to_string());
+        }
+        if self.inner.unavailable {
+            paths.push(UNAVAILABLE_SPEC_PATH.to_string());
+        }
+        paths.extend(
+            self.inner
+                .generated
+                .iter()
+                .map(|generated| generated.path.to_string()),
+        );
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Returns whether this service responds to `path`, relative to the mount point.
+    pub fn serves(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        path.is_empty()
+            || path == "index.html"
+            || U::asset(path).is_some()
+            || self.inner.is_document(path)
+            || self.inner.theme.is_some() && path == THEME_PATH
+            || self
+                .inner
+                .generated
+                .iter()
+                .any(|generated| generated.path == path)
+    }
+
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    ///
+    /// Only `GET` and `HEAD` are supported, besides accepting the terms set with
+    /// [`with_terms`](Self::with_terms), other methods are answered with
+    /// `405 Method Not Allowed` for the paths this service responds to.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        let base = req
+            .extensions()
+            .get::<MountPath>()
+            .map_or("", |mount| mount.0.as_str());
+        self.respond(req, base, req.uri().path().trim_start_matches('/'))
+    }
+
+    /// Returns a service serving only the spec of this service, on whatever path it is mounted.
+    pub fn spec_service(&self) -> SpecService<U> {
+        SpecService { ui: self.clone() }
+    }
+
+    /// Responds to `req` as if it was made for `path`.
+    fn respond<B>(&self, req: &Request<B>, base: &str, path: &str) -> Response<Body> {
+        let cors = self
+            .inner
+            .cors
+            .as_ref()
+            .filter(|_| self.inner.is_document(path));
+        let preflight = req.method() == Method::OPTIONS && cors.is_some();
+
+        if let Some(guard) = self.inner.guard.as_ref().filter(|_| !preflight) {
+            if !guard(req.headers(), req.extensions()) {
+                return status(StatusCode::UNAUTHORIZED);
+            }
+        }
+
+        #[cfg(feature = "signed-urls")]
+        let mut cookie = None;
+        #[cfg(feature = "signed-urls")]
+        if let Some(signer) = self.inner.signer.as_ref().filter(|_| !preflight) {
+            match signer.validate(base, req.uri(), req.headers()) {
+                Some(set) => cookie = set,
+                None => return status(StatusCode::FORBIDDEN),
+            }
+        }
+
+        let mut response = match *req.method() {
+            Method::GET => self.get(req, base, path),
+            Method::HEAD => head(self.get(req, base, path)),
+            Method::POST if path == crate::terms::ACCEPT_PATH && self.inner.terms.is_some() => {
+                let terms = self.inner.terms.as_ref().unwrap();
+                terms.accept(req.headers(), req.extensions(), base)
+            }
+            Method::OPTIONS if cors.is_some() => {
+                return cors.unwrap().preflight(req.headers());
+            }
+            _ if self.serves(path) => method_not_allowed(),
+            _ => status(StatusCode::NOT_FOUND),
+        };
+        if let Some(cors) = cors {
+            cors.apply(req.headers(), response.headers_mut());
+        }
+        if let Some(policy) = &self.inner.cache_policy {
+            if response.status().is_success() && !response.headers().contains_key(CACHE_CONTROL) {
+                let value = match U::asset(path).is_some() && path != "index.html" {
+                    true => policy.assets.clone(),
+                    false => policy.documents.clone(),
+                };
+                response.headers_mut().insert(CACHE_CONTROL, value);
+            }
+        }
+        #[cfg(feature = "signed-urls")]
+        if let Some(cookie) = cookie {
+            response
+                .headers_mut()
+                .append(http::header::SET_COOKIE, cookie);
+        }
+        if let Some(audit) = self.inner.audit.as_ref() {
+            if self.inner.is_audited(path) {
+                (audit.hook)(&AccessEvent {
+                    method: req.method().clone(),
+                    path: format!("{base}/{path}"),
+                    status: response.status(),
+                    identity: (audit.identify)(req.headers(), req.extensions()),
+                });
+            }
+        }
+        response
+    }
+
+    /// Returns `index.html` for a service mounted at `base`, or `None` if the asset is served as
+    /// it is, built once per mount path.
+    fn index(&self, base: &str) -> Option<Bytes> {
+        if let Some(index) = self.inner.index_cache.get(base) {
+            return index;
+        }
+        let index = self.build_index(base);
+        self.inner
+            .index_cache
+            .insert(base.to_string(), index.clone());
+        index
+    }
+
+    /// Builds `index.html` for a service mounted at `base`, see [`index`](Self::index).
+    fn build_index(&self, base: &str) -> Option<Bytes> {
+        let mut index = U::index(&self.inner.spec_url(base), &self.inner.config);
+        if self.inner.sunset_banner {
+            let page = Self::page(index)?;
+            index = Some(crate::html::inject_script(&page, crate::sunset::BANNER));
+        }
+        #[cfg(feature = "proxy")]
+        if self.inner.proxy {
+            let page = Self::page(index)?;
+            index = Some(crate::html::inject_script(&page, crate::proxy::INTERCEPTOR));
+        }
+        #[cfg(feature = "sessions")]
+        if self.inner.session_authorization {
+            let page = Self::page(index)?;
+            index = Some(crate::html::inject_script(
+                &page,
+                crate::session::PERSISTENCE,
+            ));
+        }
+        if self.inner.theme.is_some() {
+            let page = Self::page(index)?;
+            let link = format!(r#"<link rel="stylesheet" href="./{THEME_PATH}" />"#);
+            index = Some(crate::html::inject_script(&page, &link));
+        }
+        if self.inner.inline_assets {
+            let page = Self::page(index)?;
+            index = Some(crate::html::inline_assets(&page, U::asset, INLINE_LIMIT));
+        }
+        #[cfg(feature = "integrity")]
+        if let Some(hashes) = &self.inner.integrity {
+            let page = Self::page(index)?;
+            index = Some(crate::integrity::add_attributes(&page, hashes));
+        }
+        index
+    }
+
+    /// Returns `index`, or the `index.html` asset if it is served as it is.
+    fn page(index: Option<Bytes>) -> Option<Bytes> {
+        index.or_else(|| Some(Bytes::from(U::asset("index.html")?.into_owned())))
+    }
+
+    fn get<B>(&self, req: &Request<B>, base: &str, path: &str) -> Response<Body> {
+        let query = req.uri().query();
+        if path.is_empty() {
+            let forwarded = query::index_query(&self.inner.query_params, query, base);
+            let location = match forwarded.as_deref().or(query) {
+                Some(query) => format!("{base}/index.html?{query}"),
+                None => format!("{base}/index.html"),
+            };
+            return redirect(&location);
+        }
+        // the one place access control lists apply, before any document is looked up
+        let hidden = self.inner.hidden_specs(req.headers(), req.extensions());
+        if self.inner.is_hidden(path, &hidden) {
+            return status(StatusCode::NOT_FOUND);
+        }
+        if self.inner.unavailable && (path == "index.html" || path == UNAVAILABLE_SPEC_PATH) {
+            let mut response = match path {
+                "index.html" => content(
+                    mime::HTML,
+                    Bytes::from_static(UNAVAILABLE_PAGE.as_bytes()).into(),
+                ),
+                _ => status(StatusCode::SERVICE_UNAVAILABLE),
+            };
+            *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+            return response;
+        }
+        if path == "index.html" {
+            let analytics = self.inner.analytics.as_ref();
+            let terms = self.inner.terms.as_ref();
+            if let Some(terms) = terms.filter(|terms| !terms.accepted(req.headers())) {
+                let mut page = terms.page();
+                if let Some(analytics) =
+                    analytics.filter(|analytics| analytics.tracks_before_consent())
+                {
+                    analytics.apply(req.headers(), &mut page);
+                }
+                return page;
+            }
+            let mut index = self.index(base);
+            if let Some(analytics) = analytics {
+                if let Some(page) = Self::page(index.take()) {
+                    let mut response = content(mime::HTML, page.into());
+                    analytics.apply(req.headers(), &mut response);
+                    return response;
+                }
+            }
+            if let Some(index) = index {
+                return content(mime::HTML, index.into());
+            }
+        }
+        if let Some(asset) = U::asset(path) {
+            return content(mime::content_type(path), asset.into());
+        }
+        let pretty = json::pretty_param(query).or(self.inner.pretty_json.then_some(true));
+        if U::CONFIG_PATH == Some(path) {
+            let config = self
+                .inner
+                .config_json(base, query, pretty == Some(true), hidden);
+            return content(mime::JSON, config.into());
+        }
+        if let Some((spec, generation)) = self.inner.current_spec_at(path) {
+            let allowed = self.inner.allowed(req.headers(), req.extensions());
+            let disabled = self.inner.disabled_flags(generation);
+            let encoding = spec
+                .precompressed
+                .filter(|precompressed| {
+                    precompressed::accepts(req.headers(), precompressed.encoding)
+                })
+                .map(|precompressed| precompressed.encoding);
+            let variant = SpecVariant {
+                path: path.to_string(),
+                generation,
+                encoding,
+                allowed,
+                disabled,
+                pretty,
+            };
+            let Some(served) = self.inner.served_spec(&spec, variant) else {
+                return status(StatusCode::FORBIDDEN);
+            };
+            let mut response = match &served.spec.precompressed {
+                Some(precompressed) => precompressed::serve(&served.spec, precompressed),
+                None => serve_spec(&served.spec),
+            };
+            response.headers_mut().extend(served.headers);
+            return response;
+        }
+        if let (THEME_PATH, Some(css)) = (path, &self.inner.theme) {
+            return content(mime::content_type(path), css.clone().into());
+        }
+        if let Some(generated) = self
+            .inner
+            .generated
+            .iter()
+            .find(|generated| generated.path == path)
+        {
+            let allowed = self.inner.allowed(req.headers(), req.extensions());
+            let disabled = self.inner.disabled_flags(0);
+            return self.inner.serve_generated(generated, allowed, disabled);
+        }
+        status(StatusCode::NOT_FOUND)
+    }
+}
+
+/// Returns the serialized config `json` of a service mounted at `base` without its `url`, the
+/// entries of its `urls` and the `urls.primaryName` pointing at the `hidden` specs, or `None`
+/// if it isn't a JSON object.
+fn hide_urls(json: &[u8], base: &str, hidden: &BTreeSet<String>) -> Option<Bytes> {
+    let mut config: serde_json::Value = serde_json::from_slice(json).ok()?;
+    let config_object = config.as_object_mut()?;
+    let is_hidden = |url: &str| {
+        let path = url.strip_prefix(base).unwrap_or(url);
+        hidden.contains(path.trim_start_matches('/'))
+    };
+    if config_object
+        .get("url")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(is_hidden)
+    {
+        config_object.remove("url");
+    }
+    let mut hidden_names = vec![];
+    if let Some(urls) = config_object
+        .get_mut("urls")
+        .and_then(serde_json::Value::as_array_mut)
+    {
+        urls.retain(|url| {
+            let keep = url["url"].as_str().is_none_or(|url| !is_hidden(url));
+            if !keep {
+                hidden_names.extend(url["name"].as_str().map(str::to_string));
+            }
+            keep
+        });
+    }
+    let primary = config_object
+        .get("urls.primaryName")
+        .and_then(serde_json::Value::as_str);
+    if primary.is_some_and(|primary| hidden_names.iter().any(|name| name == primary)) {
+        config_object.remove("urls.primaryName");
+    }
+    let json = serde_json::to_vec(&config).expect("Could not serialize config as JSON.");
+    Some(Bytes::from(json))
+}
+
+/// Adds the `X-Spec-Version` and `X-Spec-Digest` headers of `spec` to `headers`, see
+/// [`DocService::with_spec_headers`].
+fn add_spec_headers(spec: &Spec, headers: &mut HeaderMap) {
+    if let Some(version) = spec.version().and_then(|v| HeaderValue::from_str(&v).ok()) {
+        headers.insert(SPEC_VERSION, version);
+    }
+    // FNV-1a, which is stable across builds unlike the hasher of the standard library, and
+    // computed by `swagger_spec_file!` already for precompressed specs
+    let digest = match &spec.precompressed {
+        Some(precompressed) => precompressed.digest,
+        None => spec
+            .content
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+            }),
+    };
+    let digest = HeaderValue::from_str(&format!("{digest:016x}")).expect("hex is a valid header");
+    headers.insert(SPEC_DIGEST, digest);
+}
+
+impl DocService<SwaggerUi> {
+    /// Create a service serving every spec of `specs` in one swagger-ui, with a dropdown to pick
+    /// them.
+    ///
+    /// Specs are served next to `index.html` under their file names and listed by their titles,
+    /// urls are listed as they are. swagger-ui applies one config to every spec, so its display
+    /// options are those of the first config given.
+    ///
+    /// # Panics
+    ///
+    /// If `specs` is empty or two specs have the same name.
+    pub fn multi(specs: impl IntoIterator<Item = (SpecOrUrl, Option<Config>)>) -> Self {
+        let (specs, configs): (Vec<SpecOrUrl>, Vec<Option<Config>>) = specs.into_iter().unzip();
+        let Some(primary) = specs.first().cloned() else {
+            panic!("swagger-ui needs at least one spec to serve");
+        };
+
+        let mut served: Vec<Spec> = vec![];
+        let mut urls = vec![];
+        for spec in specs {
+            match spec {
+                SpecOrUrl::Spec(spec) => {
+                    let name = spec.name.trim_start_matches('/').to_string();
+                    assert!(
+                        !served
+                            .iter()
+                            .any(|other| other.name.trim_start_matches('/') == name),
+                        "Specs served by one swagger-ui need unique names, {name} is served twice"
+                    );
+                    urls.push(UrlObject::new(&spec.title().unwrap_or(name.clone()), &name));
+                    served.push(spec);
+                }
+                SpecOrUrl::Url(url) => urls.push(UrlObject::new(&url, &url)),
+            }
+        }
+        if matches!(primary, SpecOrUrl::Spec(_)) {
+            served.remove(0);
+        }
+
+        let mut config = configs.into_iter().flatten().next().unwrap_or_default();
+        config.urls = urls;
+        let served = served
+            .into_iter()
+            .map(|spec| SwaggerUi::prepare_spec(&spec, &config).unwrap_or(spec))
+            .collect();
+        let mut service = Self::new(primary, config);
+        Arc::make_mut(&mut service.inner).specs = served;
+        service
+    }
+
+    /// Create a service serving every version of a spec in one swagger-ui, with a dropdown to
+    /// switch between them, e.g. `v1`, `v2` and `beta`.
+    ///
+    /// Versions are given by name, oldest first, and served as e.g. `v2.json`. The dropdown
+    /// opens at the latest stable version, the last one named like `v2` or `2.1`, or at the last
+    /// version if none is. Otherwise this is [`multi`](Self::multi) with one config.
+    ///
+    /// # Panics
+    ///
+    /// If `versions` is empty or two versions have the same name.
+    pub fn versioned<N: Into<String>>(
+        versions: impl IntoIterator<Item = (N, Spec)>,
+        config: impl Into<Option<Config>>,
+    ) -> Self {
+        let versions: Vec<(String, Spec)> = versions
+            .into_iter()
+            .map(|(version, spec)| {
+                let version = version.into();
+                let extension = spec.name.rsplit_once('.').map_or("json", |(_, ext)| ext);
+                let name = format!("{version}.{extension}");
+                let spec = Spec {
+                    name: name.into(),
+                    ..spec
+                };
+                (version, spec)
+            })
+            .collect();
+        let stable = |version: &str| {
+            let number = version.strip_prefix('v').unwrap_or(version);
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+        };
+        let default = versions
+            .iter()
+            .rev()
+            .find(|(version, _)| stable(version))
+            .or(versions.last())
+            .map(|(version, _)| version.clone());
+
+        let config = config.into();
+        let names: Vec<String> = versions
+            .iter()
+            .map(|(version, _)| version.clone())
+            .collect();
+        let mut service = Self::multi(
+            versions
+                .into_iter()
+                .map(|(_, spec)| (spec.into(), config.clone())),
+        );
+        let inner = Arc::make_mut(&mut service.inner);
+        for (url, version) in inner.config.urls.iter_mut().zip(names) {
+            url.name = version;
+        }
+        inner.config.urls_primary_name = default;
+        inner.invalidate();
+        service
+    }
+
+    /// Forward the specified query parameters into the generated config.
+    ///
+    /// The config applies them from its own query string. Requests to the mount point carrying
+    /// any of them are redirected to `index.html` with a `configUrl` that forwards them, so e.g.
+    /// `/docs?expand=full` opens swagger-ui with every operation expanded.
+    pub fn with_query_params(mut self, params: impl IntoIterator<Item = QueryParam>) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        inner.query_params = params.into_iter().collect();
+        inner.apply_query = query::apply;
+        self
+    }
+
+    /// Inline the stylesheets and scripts of at most 16 KiB `index.html` loads, e.g. the styles
+    /// and the initializer of the page, into it, so that only the bundles are loaded separately.
+    ///
+    /// This saves a round trip per file for users far from the server, at the cost of sending
+    /// the inlined files with every page instead of caching them.
+    pub fn with_inlined_assets(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).inline_assets = true;
+        self
+    }
+
+    /// Restyle swagger-ui with `theme`, served as a stylesheet at `theme.css` which
+    /// `index.html` loads after swagger-ui's own.
+    pub fn with_theme(mut self, theme: &crate::Theme) -> Self {
+        Arc::make_mut(&mut self.inner).theme = Some(Bytes::from(theme.css()));
+        self
+    }
+
+    /// Show a banner below the summary of every deprecated operation, with the date from its
+    /// `x-sunset` extension if it has one, and add the same notice to the start of its
+    /// description, see [`sunset_notices`](crate::sunset_notices).
+    ///
+    /// The spec is changed once, here, and only if it is JSON.
+    pub fn with_sunset_banners(mut self) -> Self {
+        let inner = Arc::make_mut(&mut self.inner);
+        if let SpecOrUrl::Spec(spec) = &inner.spec {
+            if let Some(spec) = crate::sunset_notices(spec) {
+                inner.spec = SpecOrUrl::Spec(spec);
+            }
+        }
+        inner.sunset_banner = true;
+        inner.invalidate();
+        self
+    }
+
+    /// Send try-it-out requests to other origins through a [`ProxyService`](crate::ProxyService)
+    /// mounted at `proxy` next to this service, e.g. at `/docs/proxy` for docs at `/docs`.
+    ///
+    /// `index.html` is served with a request interceptor rewriting them to `proxy?url=...`.
+    #[cfg(feature = "proxy")]
+    pub fn with_proxy(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).proxy = true;
+        self
+    }
+
+    /// Keep the authorization entered into swagger-ui in the session, through an
+    /// [`AuthorizationService`](crate::AuthorizationService) mounted at `authorization` next to
+    /// this service, instead of in the browser's `localStorage`.
+    ///
+    /// `index.html` is served with a plugin restoring the authorization from there once loaded
+    /// and saving it whenever it changes, and with `persistAuthorization` turned off. Don't turn
+    /// it on again in the config.
+    #[cfg(feature = "sessions")]
+    pub fn with_session_authorization(mut self) -> Self {
+        Arc::make_mut(&mut self.inner).session_authorization = true;
+        self
+    }
+}
+
+/// A `tower::Service` serving only the spec of a [`DocService`], e.g. to additionally expose it
+/// at `/.well-known/openapi.json`. Every request is answered with the spec, sharing its content
+/// and CORS configuration with the UI service.
+pub struct SpecService<U: DocUi = SwaggerUi> {
+    ui: DocService<U>,
+}
+
+impl<U: DocUi> Clone for SpecService<U> {
+    fn clone(&self) -> Self {
+        Self {
+            ui: self.ui.clone(),
+        }
+    }
+}
+
+impl<U: DocUi> SpecService<U> {
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        match &self.ui.inner.spec {
+            SpecOrUrl::Spec(spec) => self.ui.respond(req, "", spec.name.trim_start_matches('/')),
+            SpecOrUrl::Url(_) => status(StatusCode::NOT_FOUND),
+        }
+    }
+}
+
+impl<U: DocUi> From<&DocService<U>> for SpecService<U> {
+    fn from(value: &DocService<U>) -> Self {
+        value.spec_service()
+    }
+}
+
+impl From<Spec> for SpecService {
+    fn from(value: Spec) -> Self {
+        SwaggerUiService::new(value, None).spec_service()
+    }
+}
+
+impl<B, U: DocUi> Service<Request<B>> for SpecService<U> {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+impl<B, U: DocUi> Service<Request<B>> for DocService<U> {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+/// Lets plain hyper servers use the service without going through `tower`.
+#[cfg(feature = "hyper")]
+impl<B, U: DocUi> hyper::service::Service<Request<B>> for DocService<U> {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn call(&self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl<B, U: DocUi> hyper::service::Service<Request<B>> for SpecService<U> {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn call(&self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Body, MountPath, SwaggerUiService};
+    use crate::{Bytes, Config, Cors, DocExpansion, QueryParam};
+    use http::header::{
+        ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+        ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ALLOW, CONTENT_LENGTH,
+        CONTENT_TYPE, LOCATION, ORIGIN, VARY,
+    };
+    use http::{HeaderName, HeaderValue, Method, Request, StatusCode};
+    use http_body::Body as _;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    fn service() -> SwaggerUiService {
+        SwaggerUiService::new(crate::swagger_spec_file!("../examples/openapi.json"), None)
+    }
+
+    fn get(uri: &str, mount: &str) -> Request<()> {
+        let mut req = Request::get(uri).body(()).unwrap();
+        req.extensions_mut().insert(MountPath(mount.to_string()));
+        req
+    }
+
+    #[test]
+    fn mount_path_from_original() {
+        assert_eq!(MountPath::from_original("/docs", "/").0, "/docs");
+        assert_eq!(MountPath::from_original("/docs/", "/").0, "/docs");
+        assert_eq!(
+            MountPath::from_original("/docs/index.html", "/index.html").0,
+            "/docs"
+        );
+        assert_eq!(MountPath::from_original("/index.html", "/index.html").0, "");
+    }
+
+    #[test]
+    fn chunked_body_streams_slices() {
+        let content = Bytes::from_static(b"0123456789");
+        let mut body = Body::chunked(content.clone(), 4);
+        assert_eq!(body.size_hint().exact(), Some(10));
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut chunks = vec![];
+        while let Poll::Ready(Some(frame)) = Pin::new(&mut body).poll_frame(&mut cx) {
+            chunks.push(frame.unwrap().into_data().unwrap());
+        }
+        assert_eq!(chunks, ["0123", "4567", "89"]);
+        assert!(body.is_end_stream());
+
+        assert_eq!(Body::chunked(content.clone(), 4).into_bytes(), content);
+        assert_eq!(Body::from(Bytes::new()).into_chunks().count(), 0);
+    }
+
+    #[test]
+    fn spec_is_served_without_copying() {
+        let spec = crate::swagger_spec_file!("../examples/openapi.json");
+        let content = spec.content.clone();
+        let response = SwaggerUiService::new(spec, None).handle(&get("/openapi.json", ""));
+        let first = response.into_body().into_chunks().next().unwrap();
+        assert_eq!(first.as_ptr(), content.as_ptr());
+    }
+
+    #[test]
+    fn config_is_cached_per_mount() {
+        let service = service();
+        let first = service
+            .inner
+            .config_json("/docs", None, false, Default::default());
+        assert_eq!(
+            first.as_ptr(),
+            service
+                .inner
+                .config_json("/docs", None, false, Default::default())
+                .as_ptr()
+        );
+
+        let other: Config = serde_json::from_slice(&service.inner.config_json(
+            "/v2",
+            None,
+            false,
+            Default::default(),
+        ))
+        .unwrap();
+        assert_eq!(other.url, "/v2/openapi.json");
+        assert_eq!(service.inner.config_cache.len(), 2);
+    }
+
+    #[test]
+    fn lists_served_paths() {
+        let service = service();
+        let paths = service.paths();
+        assert!(paths.contains(&String::new()));
+        assert!(paths.contains(&"index.html".to_string()));
+        assert!(paths.contains(&"swagger-ui-config.json".to_string()));
+        assert!(paths.contains(&"openapi.json".to_string()));
+        assert!(paths.iter().all(|path| service.serves(path)));
+        assert!(service.serves("/index.html"));
+        assert!(!service.serves("/hello"));
+    }
+
+    #[test]
+    fn answers_other_methods_with_405_on_known_paths() {
+        let service = service();
+        let request = |method: Method, uri: &str| {
+            Request::builder().method(method).uri(uri).body(()).unwrap()
+        };
+
+        let response = service.handle(&request(Method::POST, "/index.html"));
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "GET, HEAD");
+
+        let response = service.handle(&request(Method::PUT, "/"));
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let response = service.handle(&request(Method::POST, "/missing"));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = service.handle(&request(Method::HEAD, "/openapi.json"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[CONTENT_LENGTH],
+            include_bytes!("../examples/openapi.json").len().to_string()
+        );
+        assert!(response.into_body().into_bytes().is_empty());
+
+        let themed = service.with_theme(&crate::Theme::new().primary_color("#0b5fff"));
+        let response = themed.handle(&request(Method::POST, "/theme.css"));
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn emits_cors_headers_on_documents() {
+        let service = service().with_cors(Cors {
+            max_age: Some(Duration::from_secs(600)),
+            ..Cors::origins(["https://portal.example.com"])
+        });
+        let request = |method: Method, uri: &str, origin: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(ORIGIN, origin)
+                .header(ACCESS_CONTROL_REQUEST_HEADERS, "x-api-key")
+                .body(())
+                .unwrap()
+        };
+
+        let response = service.handle(&request(
+            Method::GET,
+            "/openapi.json",
+            "https://portal.example.com",
+        ));
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://portal.example.com"
+        );
+        assert_eq!(response.headers()[VARY], "Origin");
+
+        let response = service.handle(&request(
+            Method::GET,
+            "/swagger-ui-config.json",
+            "https://evil.example.com",
+        ));
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let response = service.handle(&request(
+            Method::GET,
+            "/index.html",
+            "https://portal.example.com",
+        ));
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let response = service.handle(&request(
+            Method::OPTIONS,
+            "/openapi.json",
+            "https://portal.example.com",
+        ));
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_METHODS],
+            "GET, HEAD"
+        );
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_HEADERS],
+            "x-api-key"
+        );
+        assert_eq!(response.headers()[ACCESS_CONTROL_MAX_AGE], "600");
+
+        let response = service.handle(&request(
+            Method::OPTIONS,
+            "/index.html",
+            "https://portal.example.com",
+        ));
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn spec_service_serves_spec_anywhere() {
+        let ui = service().with_cors(Cors::any());
+        let spec = ui.spec_service();
+
+        let response = spec.handle(&get("/.well-known/openapi.json", ""));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        let served = response.into_body().into_chunks().next().unwrap();
+
+        let response = ui.handle(&get("/openapi.json", ""));
+        let shared = response.into_body().into_chunks().next().unwrap();
+        assert_eq!(served.as_ptr(), shared.as_ptr());
+
+        let spec = SwaggerUiService::new("https://example.com/openapi.json", None).spec_service();
+        let response = spec.handle(&get("/openapi.json", ""));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn redirects_to_index() {
+        let response = service().handle(&get("/?configUrl=x", "/docs"));
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html?configUrl=x");
+    }
+
+    #[test]
+    fn forwards_allowed_query_params() {
+        let service = service().with_query_params([QueryParam::Expand]);
+
+        let response = service.handle(&get("/?expand=full", "/docs"));
+        assert_eq!(
+            response.headers()[LOCATION],
+            "/docs/index.html?expand=full&configUrl=%2Fdocs%2Fswagger-ui-config.json%3Fexpand%3Dfull"
+        );
+
+        let response = service.handle(&get("/swagger-ui-config.json?expand=full&api=x", "/docs"));
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert!(matches!(config.doc_expansion, DocExpansion::Full));
+        assert_eq!(config.urls_primary_name, None);
+        // cached per forwarded parameter, however the query spells it
+        service.handle(&get(
+            "/swagger-ui-config.json?junk=1&api=x&expand=full",
+            "/docs",
+        ));
+        service.handle(&get(
+            "/swagger-ui-config.json?expand=none&expand=full&api=x",
+            "/docs",
+        ));
+        assert_eq!(service.inner.config_cache.len(), 1);
+    }
+
+    #[test]
+    fn serves_assets_config_and_spec() {
+        let service = service();
+
+        let response = service.handle(&get("/index.html", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+        let response = service.handle(&get("/swagger-ui-config.json", "/docs"));
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let response = service.handle(&get("/openapi.json", "/docs"));
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(
+            response.into_body().into_bytes(),
+            include_bytes!("../examples/openapi.json").as_ref()
+        );
+
+        let response = service.handle(&get("/missing.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn index_is_built_once_per_mount() {
+        let theme = crate::Theme::new().primary_color("#0b5fff");
+        let service = service().with_theme(&theme);
+        let index = |mount: &str| {
+            let response = service.handle(&get("/index.html", mount));
+            response.into_body().into_bytes()
+        };
+
+        let first = index("/docs");
+        assert!(String::from_utf8_lossy(&first).contains("theme.css"));
+        assert_eq!(first.as_ptr(), index("/docs").as_ptr());
+        index("/v2");
+        assert_eq!(service.inner.index_cache.len(), 2);
+    }
+
+    #[test]
+    fn caches_served_specs_per_path_and_variant() {
+        let v2 = crate::Spec::builder()
+            .name("v2.json")
+            .json(r#"{"paths": {"/v2": {"get": {"tags": ["pet"]}}}}"#);
+        let service = SwaggerUiService::multi([
+            (
+                crate::swagger_spec_file!("../examples/openapi.json").into(),
+                None,
+            ),
+            (v2.into(), None),
+        ])
+        .with_spec_access(|_, _| Some(std::collections::BTreeSet::from(["pet".to_string()])));
+
+        let spec = |uri: &str| {
+            let body = service.handle(&get(uri, "/docs")).into_body().into_bytes();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        assert!(spec("/openapi.json")["paths"]["/pet"].is_object());
+        assert!(spec("/v2.json")["paths"]["/v2"].is_object());
+        assert!(spec("/v2.json?pretty=1")["paths"]["/v2"].is_object());
+        assert_eq!(service.inner.spec_cache.len(), 3);
+
+        let first = service
+            .handle(&get("/v2.json", "/docs"))
+            .into_body()
+            .into_bytes();
+        let second = service
+            .handle(&get("/v2.json", "/docs"))
+            .into_body()
+            .into_bytes();
+        assert_eq!(first.as_ptr(), second.as_ptr());
+    }
+
+    #[test]
+    fn serves_multiple_specs_with_a_dropdown() {
+        let v2 = crate::Spec::builder()
+            .name("v2.json")
+            .json(r#"{"info": {"title": "Petstore v2"}}"#);
+        let service = SwaggerUiService::multi([
+            (
+                crate::swagger_spec_file!("../examples/openapi.json").into(),
+                None,
+            ),
+            (v2.into(), None),
+            ("https://example.com/v3.json".into(), None),
+        ]);
+
+        let response = service.handle(&get("/swagger-ui-config.json", "/docs"));
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        let urls: Vec<_> = config
+            .urls
+            .iter()
+            .map(|url| (&*url.name, &*url.url))
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                ("Swagger Petstore", "openapi.json"),
+                ("Petstore v2", "v2.json"),
+                ("https://example.com/v3.json", "https://example.com/v3.json")
+            ]
+        );
+
+        let response = service.handle(&get("/v2.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(service.paths().contains(&"v2.json".to_string()));
+        assert!(service.serves("openapi.json"));
+    }
+
+    #[test]
+    fn switches_between_versions_starting_at_the_latest_stable() {
+        let version = |title: &str| {
+            crate::Spec::builder().json(format!(r#"{{"info": {{"title": "{title}"}}}}"#))
+        };
+        let service = SwaggerUiService::versioned(
+            [
+                ("v1", version("Pets v1")),
+                ("v2", version("Pets v2")),
+                ("beta", version("Pets beta")),
+            ],
+            None,
+        );
+
+        let response = service.handle(&get("/swagger-ui-config.json", "/docs"));
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        let urls: Vec<_> = config
+            .urls
+            .iter()
+            .map(|url| (&*url.name, &*url.url))
+            .collect();
+        assert_eq!(
+            urls,
+            [("v1", "v1.json"), ("v2", "v2.json"), ("beta", "beta.json")]
+        );
+        assert_eq!(config.urls_primary_name.as_deref(), Some("v2"));
+
+        let response = service.handle(&get("/beta.json", "/docs"));
+        let spec: serde_json::Value =
+            serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(spec["info"]["title"], "Pets beta");
+    }
+
+    #[test]
+    fn hides_operations_behind_disabled_flags() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let spec = crate::Spec::builder()
+            .json(r#"{"paths": {"/pets": {"get": {}, "post": {"x-feature-flag": "adoption"}}}}"#);
+        let released = Arc::new(AtomicBool::new(false));
+        let flag = released.clone();
+        let service = SwaggerUiService::new(spec, None)
+            .with_postman()
+            .with_feature_flags(move |_: &str| flag.load(Ordering::Relaxed));
+        let pets = || {
+            let body = service.handle(&get("/openapi.json", "/docs")).into_body();
+            serde_json::from_slice::<serde_json::Value>(&body.into_bytes()).unwrap()["paths"]
+                ["/pets"]
+                .clone()
+        };
+        let collected = || {
+            let body = service.handle(&get("/postman.json", "/docs")).into_body();
+            String::from_utf8(body.into_bytes().to_vec()).unwrap()
+        };
+
+        assert!(pets().get("post").is_none());
+        assert!(!collected().contains("POST"));
+        released.store(true, Ordering::Relaxed);
+        assert!(pets()["post"].is_object());
+        assert!(collected().contains("POST"));
+    }
+
+    #[test]
+    fn serves_the_stylesheet_of_the_theme() {
+        let theme = crate::Theme::new().primary_color("#0b5fff");
+        let service = service().with_theme(&theme);
+
+        let response = service.handle(&get("/theme.css", "/docs"));
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/css");
+        assert_eq!(response.into_body().into_bytes(), theme.css());
+        let index = service.handle(&get("/index.html", "/docs")).into_body();
+        let index = String::from_utf8(index.into_bytes().to_vec()).unwrap();
+        assert!(index.contains(r#"<link rel="stylesheet" href="./theme.css" />"#));
+    }
+
+    #[test]
+    fn shows_the_terms_until_they_are_accepted() {
+        use http::header::{COOKIE, LOCATION, SET_COOKIE};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = accepted.clone();
+        let terms = crate::Terms::markdown("Use the API **fairly**.")
+            .version("2")
+            .on_accept(move |_, _| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        let service = service().with_terms(terms);
+        let index = |cookie: Option<&str>| {
+            let mut req = get("/index.html", "/docs");
+            if let Some(cookie) = cookie {
+                req.headers_mut().insert(COOKIE, cookie.parse().unwrap());
+            }
+            let body = service.handle(&req).into_body().into_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        };
+
+        assert!(index(None).contains("<strong>fairly</strong>"));
+        assert!(index(Some("swagger_ui_terms=1")).contains("Accept and continue"));
+        let mut req = get("/terms", "/docs");
+        *req.method_mut() = Method::POST;
+        let response = service.handle(&req);
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+        let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+        assert!(cookie.starts_with("swagger_ui_terms=2; Path=/docs;"));
+        assert_eq!(accepted.load(Ordering::Relaxed), 1);
+        assert!(index(Some("swagger_ui_terms=2")).contains("swagger-ui"));
+        assert!(!index(Some("swagger_ui_terms=2")).contains("Accept and continue"));
+
+        let spec = service.handle(&get("/openapi.json", "/docs"));
+        assert_eq!(spec.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn adds_analytics_unless_the_visitor_objects() {
+        use http::header::{COOKIE, VARY};
+
+        let snippet = r#"<script src="https://analytics.example.com/a.js"></script>"#;
+        let service = service().with_analytics(crate::Analytics::new(snippet));
+        let index = |service: &SwaggerUiService, headers: &[(&str, &str)]| {
+            let mut req = get("/index.html", "/docs");
+            for (name, value) in headers {
+                req.headers_mut().insert(
+                    HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    value.parse().unwrap(),
+                );
+            }
+            let body = service.handle(&req).into_body().into_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        };
+
+        let response = service.handle(&get("/index.html", "/docs"));
+        assert_eq!(response.headers()[VARY], "DNT");
+        assert!(index(&service, &[]).contains(snippet));
+        assert!(!index(&service, &[("dnt", "1")]).contains(snippet));
+
+        let service = service.with_terms(crate::Terms::html("<p>Terms</p>"));
+        let terms = index(&service, &[]);
+        assert!(terms.contains("<p>Terms</p>") && !terms.contains(snippet));
+        assert!(index(&service, &[(COOKIE.as_str(), "swagger_ui_terms=1")]).contains(snippet));
+
+        let service = service.with_analytics(crate::Analytics::new(snippet).before_consent());
+        assert!(index(&service, &[]).contains(snippet));
+    }
+
+    #[test]
+    fn lists_and_serves_specs_only_to_those_entitled() {
+        let billing = crate::Spec::builder()
+            .name("billing.json")
+            .json(r#"{"info": {"title": "Billing"}}"#);
+        let service = SwaggerUiService::multi([
+            (
+                crate::swagger_spec_file!("../examples/openapi.json").into(),
+                None,
+            ),
+            (billing.into(), None),
+        ])
+        .with_spec_acl("billing.json", crate::Acl::roles(["finance"]));
+        let request = |uri: &str, role: &str| {
+            let mut req = get(uri, "/docs");
+            req.extensions_mut().insert(crate::Roles::new([role]));
+            service.handle(&req)
+        };
+        let listed = |role: &str| {
+            let body = request("/swagger-ui-config.json", role).into_body();
+            let config: Config = serde_json::from_slice(&body.into_bytes()).unwrap();
+            config
+                .urls
+                .into_iter()
+                .map(|url| url.url)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(listed("finance"), ["openapi.json", "billing.json"]);
+        assert_eq!(listed("support"), ["openapi.json"]);
+        assert_eq!(request("/billing.json", "finance").status(), StatusCode::OK);
+        assert_eq!(
+            request("/billing.json", "support").status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn hides_the_primary_spec_and_its_documents_from_those_not_entitled() {
+        let service = service()
+            .with_markdown()
+            .with_postman()
+            .with_insomnia()
+            .with_json_schemas()
+            .with_tag_specs()
+            .with_minified_spec()
+            .with_spec_acl("openapi.json", crate::Acl::roles(["partner"]));
+        let mut derived: Vec<String> = [
+            "openapi.json",
+            "openapi.md",
+            "postman.json",
+            "insomnia.json",
+            "schemas.json",
+            "openapi.full.json",
+        ]
+        .map(String::from)
+        .into();
+        derived.extend(
+            service
+                .paths()
+                .into_iter()
+                .filter(|path| path.starts_with("specs/") && path.ends_with(".json")),
+        );
+        assert!(derived.len() > 6);
+        let request = |uri: &str, role: &str| {
+            let mut req = get(uri, "/docs");
+            req.extensions_mut().insert(crate::Roles::new([role]));
+            service.handle(&req)
+        };
+
+        for path in &derived {
+            let uri = format!("/{path}");
+            assert_eq!(request(&uri, "partner").status(), StatusCode::OK, "{path}");
+            assert_eq!(
+                request(&uri, "visitor").status(),
+                StatusCode::NOT_FOUND,
+                "{path}"
+            );
+        }
+        let config = |role: &str| {
+            let body = request("/swagger-ui-config.json", role)
+                .into_body()
+                .into_bytes();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        assert_eq!(config("partner")["url"], "/docs/openapi.json");
+        assert!(config("visitor").get("url").is_none());
+        assert_eq!(request("/index.html", "visitor").status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn guards_and_caches_responses() {
+        use crate::CachePolicy;
+        use http::header::{AUTHORIZATION, CACHE_CONTROL};
+
+        let service = service()
+            .with_guard(|headers, _| headers.contains_key(AUTHORIZATION))
+            .with_cache_policy(CachePolicy::max_age(Duration::from_secs(3600)));
+        let response = service.handle(&get("/openapi.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let authorized = |uri: &str| {
+            let mut req = get(uri, "/docs");
+            req.headers_mut()
+                .insert(AUTHORIZATION, HeaderValue::from_static("Bearer abc"));
+            service.handle(&req)
+        };
+        let response = authorized("/openapi.json");
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-cache");
+        let response = authorized("/swagger-ui.css");
+        assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=3600");
+        let response = authorized("/index.html");
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-cache");
+    }
+
+    #[test]
+    fn serves_an_unavailable_page_if_the_spec_failed_to_load() {
+        let spec = crate::try_swagger_spec_file!("./missing/openapi.json");
+        let service = SwaggerUiService::try_new(spec, None);
+
+        let response = service.handle(&get("/index.html", "/docs"));
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let page = response.into_body().into_bytes();
+        assert!(String::from_utf8_lossy(&page).contains("Documentation temporarily unavailable"));
+        let response = service.handle(&get("/openapi.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let response = service.handle(&get("/swagger-ui.css", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn keeps_an_explicit_config_url() {
+        let config = Config {
+            url: "https://gateway.example.com/openapi.json".to_string(),
+            ..Config::default()
+        };
+        let service = SwaggerUiService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            config,
+        );
+
+        let response = service.handle(&get("/swagger-ui-config.json", "/docs"));
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(config.url, "https://gateway.example.com/openapi.json");
+    }
+
+    #[test]
+    fn serves_markdown_when_enabled() {
+        assert_eq!(
+            service().handle(&get("/openapi.md", "/docs")).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let service = service().with_markdown();
+        assert!(service.serves("/openapi.md"));
+        assert!(service.paths().contains(&"openapi.md".to_string()));
+        let response = service.handle(&get("/openapi.md", "/docs"));
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/markdown; charset=utf-8"
+        );
+        let markdown = response.into_body().into_bytes();
+        assert!(markdown.starts_with(b"# "));
+
+        let linked =
+            SwaggerUiService::new("https://example.com/openapi.json", None).with_markdown();
+        assert!(!linked.serves("/openapi.md"));
+    }
+
+    #[test]
+    fn serves_postman_collection_when_enabled() {
+        let service = service()
+            .with_markdown()
+            .with_postman()
+            .with_insomnia()
+            .with_json_schemas();
+        assert!(service.serves("/openapi.md"));
+        assert!(service.serves("/insomnia.json"));
+        assert!(service.serves("/schemas.json"));
+        let response = service.handle(&get("/postman.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let collection: serde_json::Value =
+            serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(collection["info"]["name"], "Swagger Petstore");
+        assert_eq!(
+            collection["variable"][0]["value"],
+            "https://petstore.swagger.io/v2"
+        );
+    }
+
+    #[test]
+    fn pretty_prints_json_on_request() {
+        let service = service();
+        let spec = |service: &SwaggerUiService, uri: &str| {
+            let body = service.handle(&get(uri, "/docs")).into_body().into_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        };
+        let compact = spec(&service, "/openapi.json?pretty=0");
+        assert!(!compact.contains('\n'));
+        let pretty = spec(&service, "/openapi.json?pretty=1");
+        assert!(pretty.starts_with("{\n  \""));
+        // keys keep the order of the spec
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap()
+        );
+        assert!(pretty.find("\"swagger\"").unwrap() < pretty.find("\"info\"").unwrap());
+
+        let service = service.with_pretty_json();
+        assert!(spec(&service, "/swagger-ui-config.json").contains("\n  "));
+        assert!(!spec(&service, "/swagger-ui-config.json?pretty=0").contains('\n'));
+    }
+
+    #[test]
+    fn sends_the_version_and_digest_of_the_spec() {
+        let service = service().with_spec_headers();
+        let response = service.handle(&get("/openapi.json", "/docs"));
+        assert_eq!(response.headers()["x-spec-version"], "1.0.5");
+        let digest = response.headers()["x-spec-digest"].clone();
+        assert_eq!(digest.len(), 16);
+
+        let pretty = service.handle(&get("/openapi.json?pretty=1", "/docs"));
+        assert_eq!(pretty.headers()["x-spec-digest"], digest);
+        let changed = service
+            .with_sorted_keys()
+            .handle(&get("/openapi.json", "/docs"));
+        assert_ne!(changed.headers()["x-spec-digest"], digest);
+    }
+
+    #[test]
+    fn serves_embedded_assets_without_copying_them() {
+        let embedded = swagger_ui_assets::get("swagger-ui-bundle.js")
+            .unwrap()
+            .content;
+        let response = service().handle(&get("/swagger-ui-bundle.js", "/docs"));
+        let body = response.into_body().into_bytes();
+        assert_eq!(body.as_ptr(), embedded.as_ptr());
+        let body = crate::serve_asset("swagger-ui-bundle.js")
+            .into_body()
+            .into_bytes();
+        assert_eq!(body.as_ptr(), embedded.as_ptr());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn serves_precompressed_specs_to_clients_accepting_them() {
+        use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+
+        let brotli = crate::swagger_spec_file!("../examples/openapi.json", brotli);
+        assert_eq!(brotli.precompressed.unwrap().encoding, "br");
+        let spec = crate::swagger_spec_file!("../examples/openapi.json", gzip);
+        let raw = include_bytes!("../examples/openapi.json");
+        assert_eq!(spec.content, raw.as_slice());
+        assert_eq!(brotli.content, spec.content);
+        let precompressed = spec.precompressed.unwrap();
+        assert_eq!(precompressed.raw_len, raw.len());
+        assert!(precompressed.content.len() < raw.len());
+
+        let service = SwaggerUiService::new(spec, None).with_spec_headers();
+        let mut req = get("/openapi.json", "/docs");
+        req.headers_mut()
+            .insert(ACCEPT_ENCODING, HeaderValue::from_static("br, gzip"));
+        let response = service.handle(&req);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[VARY], "Accept-Encoding");
+        let digest = response.headers()["x-spec-digest"].clone();
+        assert_eq!(response.into_body().into_bytes(), precompressed.content);
+
+        let response = service.handle(&get("/openapi.json", "/docs"));
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(response.headers()["x-spec-digest"], digest);
+        assert_eq!(response.into_body().into_bytes(), raw.as_slice());
+        // cached per encoding
+        assert_eq!(service.handle(&req).headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(service.inner.spec_cache.len(), 2);
+        // the digest is the one of the raw content
+        let uncompressed =
+            SwaggerUiService::new(crate::swagger_spec_file!("../examples/openapi.json"), None)
+                .with_spec_headers()
+                .handle(&get("/openapi.json", "/docs"));
+        assert_eq!(uncompressed.headers()["x-spec-digest"], digest);
+    }
+
+    #[test]
+    fn serves_a_spec_per_tag() {
+        let service = service().with_tag_specs();
+        assert!(service.paths().contains(&"specs/pet.json".to_string()));
+        let response = service.handle(&get("/specs/store.json", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        let slice: serde_json::Value =
+            serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert!(slice["paths"]["/store/inventory"].is_object());
+        assert!(slice["paths"].get("/pet").is_none());
+
+        let spec = crate::Spec::builder().json(
+            serde_json::to_vec(&serde_json::json!({
+                "openapi": "3.0.3",
+                "paths": {
+                    "/a": {"get": {"tags": ["Pet store"]}},
+                    "/b": {"get": {"tags": ["pet-store"]}},
+                },
+            }))
+            .unwrap(),
+        );
+        let service = SwaggerUiService::new(spec, None).with_tag_specs();
+        let response = service.handle(&get("/specs/pet-store-2.json", "/docs"));
+        let slice: serde_json::Value =
+            serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert!(slice["paths"]["/b"].is_object());
+        assert!(slice["paths"].get("/a").is_none());
+    }
+
+    #[test]
+    fn serves_the_full_spec_next_to_the_minified_one() {
+        let service = service().with_minified_spec();
+        let full = service.handle(&get("/openapi.full.json", "/docs"));
+        assert_eq!(full.status(), StatusCode::OK);
+        let full = full.into_body().into_bytes();
+        let minified = service
+            .handle(&get("/openapi.json", "/docs"))
+            .into_body()
+            .into_bytes();
+        assert!(minified.len() < full.len());
+        let document: serde_json::Value = serde_json::from_slice(&minified).unwrap();
+        assert!(document["info"].get("description").is_none());
+        let pretty = service
+            .handle(&get("/openapi.full.json?pretty=1", "/docs"))
+            .into_body()
+            .into_bytes();
+        assert!(pretty.starts_with(b"{\n"));
+    }
+
+    #[cfg(feature = "hyper")]
+    #[test]
+    fn serves_as_hyper_service() {
+        use hyper::service::Service as _;
+
+        let response = service()
+            .call(get("/openapi.json", "/docs"))
+            .into_inner()
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    }
+
+    #[test]
+    fn injects_the_sunset_banner() {
+        let response = service()
+            .with_sunset_banners()
+            .handle(&get("/index.html", "/docs"));
+        let index = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        let banner = index.find("sunset-banner").unwrap();
+        assert!(banner < index.rfind("</body>").unwrap());
+    }
+
+    #[cfg(feature = "proxy")]
+    #[test]
+    fn injects_the_proxy_interceptor() {
+        let response = service().with_proxy().handle(&get("/index.html", "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        let index = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        let interceptor = index.find("requestInterceptor").unwrap();
+        assert!(interceptor < index.rfind("</body>").unwrap());
+
+        let response = service().handle(&get("/index.html", "/docs"));
+        let index = response.into_body().into_bytes();
+        assert!(!String::from_utf8_lossy(&index).contains("requestInterceptor"));
+    }
+
+    #[cfg(feature = "sessions")]
+    #[test]
+    fn injects_the_authorization_persistence() {
+        let response = service()
+            .with_session_authorization()
+            .handle(&get("/index.html", "/docs"));
+        let index = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        let persistence = index.find(r#"new URL("./authorization""#).unwrap();
+        assert!(persistence < index.rfind("</body>").unwrap());
+    }
+
+    #[cfg(feature = "integrity")]
+    #[test]
+    fn adds_integrity_to_the_index() {
+        let response = service()
+            .with_integrity()
+            .handle(&get("/index.html", "/docs"));
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        let index = String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap();
+        let bundle = crate::integrity::hash(&crate::Assets::get("swagger-ui-bundle.js").unwrap());
+        assert!(index.contains(&format!(
+            r#"src="./swagger-ui-bundle.js" integrity="{bundle}" crossorigin="anonymous""#
+        )));
+    }
+
+    #[test]
+    fn audits_document_requests() {
+        use crate::AccessEvent;
+        use http::HeaderValue;
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let service = service().with_access_audit(
+            |headers, _| Some(headers.get("x-user")?.to_str().ok()?.to_string()),
+            move |event| recorded.lock().unwrap().push(event.clone()),
+        );
+
+        let mut req = get("/index.html", "/docs");
+        req.headers_mut()
+            .insert("x-user", HeaderValue::from_static("alice"));
+        service.handle(&req);
+        service.handle(&get("/swagger-ui.css", "/docs"));
+        service.handle(&get("/openapi.json", "/docs"));
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                AccessEvent {
+                    method: Method::GET,
+                    path: "/docs/index.html".to_string(),
+                    status: StatusCode::OK,
+                    identity: Some("alice".to_string()),
+                },
+                AccessEvent {
+                    method: Method::GET,
+                    path: "/docs/openapi.json".to_string(),
+                    status: StatusCode::OK,
+                    identity: None,
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "signed-urls")]
+    #[test]
+    fn only_allows_signed_links() {
+        use crate::UrlSigner;
+        use http::header::{COOKIE, SET_COOKIE};
+        use std::time::Duration;
+
+        let signer = UrlSigner::new("secret");
+        let service = service().with_signed_urls(signer.clone());
+        let unsigned = service.handle(&get("/index.html", "/docs"));
+        assert_eq!(unsigned.status(), StatusCode::FORBIDDEN);
+
+        let link = signer.link("/docs", Duration::from_secs(60));
+        let response = service.handle(&get(link.strip_prefix("/docs").unwrap(), "/docs"));
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+        assert!(cookie.contains("; Path=/docs; Max-Age="));
+        assert!(cookie.ends_with("; HttpOnly; SameSite=Lax"));
+
+        let mut req = get("/openapi.json", "/docs");
+        let value = cookie.split(';').next().unwrap().parse().unwrap();
+        req.headers_mut().insert(COOKIE, value);
+        assert_eq!(service.handle(&req).status(), StatusCode::OK);
+
+        // preflight requests come without the cookie
+        let service = service.with_cors(Cors::any());
+        let mut preflight = get("/openapi.json", "/docs");
+        *preflight.method_mut() = Method::OPTIONS;
+        preflight.headers_mut().insert(
+            ORIGIN,
+            http::HeaderValue::from_static("https://portal.example.com"),
+        );
+        assert_eq!(service.handle(&preflight).status(), StatusCode::NO_CONTENT);
+    }
+
+    #[test]
+    fn filters_the_spec_per_request() {
+        use std::collections::BTreeSet;
+
+        let service = service().with_spec_access(|headers, _| {
+            let role = headers.get("x-role")?.to_str().ok()?;
+            Some(BTreeSet::from([role.to_string()]))
+        });
+        let paths = |role: Option<&'static str>| {
+            let mut req = get("/openapi.json", "/docs");
+            if let Some(role) = role {
+                req.headers_mut()
+                    .insert("x-role", http::HeaderValue::from_static(role));
+            }
+            let body = service.handle(&req).into_body().into_bytes();
+            let document: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            document["paths"].as_object().unwrap().len()
+        };
+
+        let all = paths(None);
+        let store = paths(Some("store"));
+        assert!(0 < store && store < all);
+        assert_eq!(paths(Some("store")), store);
+        assert_eq!(paths(Some("unknown")), 0);
+    }
+
+    #[test]
+    fn filters_the_generated_documents_per_request() {
+        use std::collections::BTreeSet;
+
+        let service = service()
+            .with_markdown()
+            .with_postman()
+            .with_insomnia()
+            .with_json_schemas()
+            .with_tag_specs()
+            .with_minified_spec()
+            .with_spec_access(|headers, _| {
+                let role = headers.get("x-role")?.to_str().ok()?;
+                Some(BTreeSet::from([role.to_string()]))
+            });
+        let document = |path: &str, role: Option<&'static str>| {
+            let mut req = get(&format!("/{path}"), "/docs");
+            if let Some(role) = role {
+                req.headers_mut()
+                    .insert("x-role", http::HeaderValue::from_static(role));
+            }
+            let response = service.handle(&req);
+            assert_eq!(response.status(), StatusCode::OK, "{path}");
+            String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap()
+        };
+
+        for (path, left_out) in [
+            ("openapi.md", "findByStatus"),
+            ("postman.json", "findByStatus"),
+            ("insomnia.json", "findByStatus"),
+            ("schemas.json", "Category"),
+            ("openapi.full.json", "findByStatus"),
+        ] {
+            let store = document(path, Some("store"));
+            assert!(document(path, None).contains(left_out), "{path}");
+            assert!(!store.contains(left_out), "{path}");
+            assert!(store.to_lowercase().contains("order"), "{path}");
+            assert_eq!(document(path, Some("store")), store);
+        }
+        assert_eq!(
+            document("specs/store.json", Some("store")),
+            document("specs/store.json", None)
+        );
+        let mut req = get("/specs/pet.json", "/docs");
+        req.headers_mut()
+            .insert("x-role", http::HeaderValue::from_static("store"));
+        assert_eq!(service.handle(&req).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "redact")]
+    #[test]
+    fn serves_redacted_spec() {
+        let spec = crate::Spec::builder().json(Bytes::from_static(
+            br#"{"components":{"examples":{"token":{"value":"Bearer abc"}}}}"#,
+        ));
+        let service = SwaggerUiService::new(spec, None).with_redaction(&crate::Redactor::default());
+        let body = service
+            .handle(&get("/openapi.json", "/docs"))
+            .into_body()
+            .into_bytes();
+        assert_eq!(
+            body,
+            r#"{"components":{"examples":{"token":{"value":"Bearer [REDACTED]"}}}}"#
+        );
+    }
+}
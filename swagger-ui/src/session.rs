@@ -0,0 +1,433 @@
+//! Keeping the authorization entered into swagger-ui server-side, per session, instead of in
+//! the browser's `localStorage`.
+
+use crate::handlers::{content, method_not_allowed, status};
+use crate::lru::Lru;
+use crate::{mime, Body};
+use bytes::Bytes;
+use http::header::CACHE_CONTROL;
+use http::{Extensions, HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use serde_json::Value;
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Restores the authorization from `./authorization` once swagger-ui is loaded, and saves it
+/// there whenever it changes, instead of persisting it in `localStorage`.
+pub(crate) const PERSISTENCE: &str = r#"<script>
+(function () {
+  var endpoint = new URL("./authorization", document.baseURI).href;
+  function save(system) {
+    var authorized = system.authSelectors.authorized();
+    fetch(endpoint, {
+      method: "PUT",
+      credentials: "same-origin",
+      headers: {"Content-Type": "application/json"},
+      body: JSON.stringify(authorized ? authorized.toJS() : {})
+    });
+  }
+  function saving(action, system) {
+    return function (payload) {
+      var result = action(payload);
+      save(system);
+      return result;
+    };
+  }
+  function persistence() {
+    return {statePlugins: {auth: {wrapActions: {authorize: saving, logout: saving}}}};
+  }
+  var bundle = window.SwaggerUIBundle;
+  window.SwaggerUIBundle = Object.assign(function (options) {
+    var ui;
+    var complete = options.onComplete;
+    options.persistAuthorization = false;
+    options.plugins = (options.plugins || []).concat([persistence]);
+    options.onComplete = function () {
+      fetch(endpoint, {credentials: "same-origin"})
+        .then(function (response) { return response.ok ? response.json() : null; })
+        .then(function (authorized) {
+          if (authorized && Object.keys(authorized).length) {
+            ui.authActions.authorize(authorized);
+          }
+        });
+      if (complete) {
+        complete();
+      }
+    };
+    ui = bundle(options);
+    return ui;
+  }, bundle);
+})();
+</script>
+"#;
+
+/// Largest authorization stored, larger ones are answered with `413 Payload Too Large`.
+const MAX_AUTHORIZATION_SIZE: usize = 64 * 1024;
+
+/// Sessions a [`MemoryAuthorizationStore`] keeps the authorization of by default.
+const MEMORY_STORE_CAPACITY: usize = 10_000;
+
+/// Key of the authorization in a `tower-sessions` session.
+#[cfg(feature = "tower-sessions")]
+const SESSION_KEY: &str = "swagger_ui.authorization";
+
+/// Where an [`AuthorizationService`] keeps the authorization of each session, as the JSON
+/// object swagger-ui holds it in.
+///
+/// Implement it to keep them with the rest of the session data, or see
+/// [`AuthorizationService::tower_sessions`] for sessions of `tower-sessions`.
+pub trait AuthorizationStore: Send + Sync {
+    /// Returns the authorization stored for `session`.
+    fn load(&self, session: &str) -> Option<Bytes>;
+    /// Stores `authorization` for `session`, replacing any earlier one.
+    fn save(&self, session: &str, authorization: Bytes);
+    /// Forgets the authorization of `session`, e.g. on logout.
+    fn remove(&self, session: &str);
+}
+
+/// Keeps authorizations in memory, lost when the process exits.
+///
+/// Keeps those of up to 10 000 sessions by default, forgetting the least recently used one to
+/// make room for another.
+pub struct MemoryAuthorizationStore {
+    authorizations: Lru<String, Bytes>,
+}
+
+impl MemoryAuthorizationStore {
+    /// Create a store keeping the authorizations of up to `capacity` sessions.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            authorizations: Lru::new(capacity),
+        }
+    }
+}
+
+impl Default for MemoryAuthorizationStore {
+    fn default() -> Self {
+        Self::with_capacity(MEMORY_STORE_CAPACITY)
+    }
+}
+
+impl fmt::Debug for MemoryAuthorizationStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryAuthorizationStore")
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuthorizationStore for MemoryAuthorizationStore {
+    fn load(&self, session: &str) -> Option<Bytes> {
+        self.authorizations.get(session)
+    }
+
+    fn save(&self, session: &str, authorization: Bytes) {
+        self.authorizations
+            .insert(session.to_string(), authorization);
+    }
+
+    fn remove(&self, session: &str) {
+        self.authorizations.remove(session);
+    }
+}
+
+/// Returns the id of the session a request belongs to.
+type SessionId = Arc<dyn Fn(&HeaderMap, &Extensions) -> Option<String> + Send + Sync>;
+
+/// Where an [`AuthorizationService`] keeps the authorizations.
+#[derive(Clone)]
+enum Backend {
+    Store {
+        store: Arc<dyn AuthorizationStore>,
+        session: SessionId,
+    },
+    #[cfg(feature = "tower-sessions")]
+    TowerSessions,
+}
+
+/// The session of a request, to load and save its authorization.
+enum Session<'a> {
+    Store(&'a dyn AuthorizationStore, String),
+    #[cfg(feature = "tower-sessions")]
+    TowerSessions(&'a tower_sessions::Session),
+}
+
+/// Why a session store failed.
+#[cfg(feature = "tower-sessions")]
+type StoreError = tower_sessions::session::Error;
+#[cfg(not(feature = "tower-sessions"))]
+type StoreError = Infallible;
+
+impl Session<'_> {
+    async fn load(&self) -> Result<Option<Bytes>, StoreError> {
+        match self {
+            Self::Store(store, session) => Ok(store.load(session)),
+            #[cfg(feature = "tower-sessions")]
+            Self::TowerSessions(session) => Ok(session.get_value(SESSION_KEY).await?.map(|json| {
+                serde_json::to_vec(&json)
+                    .expect("Could not serialize authorization as JSON.")
+                    .into()
+            })),
+        }
+    }
+
+    async fn save(&self, authorization: Bytes) -> Result<(), StoreError> {
+        match self {
+            Self::Store(store, session) => store.save(session, authorization),
+            #[cfg(feature = "tower-sessions")]
+            Self::TowerSessions(session) => {
+                let json = serde_json::from_slice(&authorization)?;
+                session.insert_value(SESSION_KEY, json).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self) -> Result<(), StoreError> {
+        match self {
+            Self::Store(store, session) => store.remove(session),
+            #[cfg(feature = "tower-sessions")]
+            Self::TowerSessions(session) => {
+                session.remove_value(SESSION_KEY).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `tower::Service` storing the authorization swagger-ui was given per session, for docs
+/// served with [`with_session_authorization`](crate::DocService::with_session_authorization).
+///
+/// Mount it at `authorization` next to the docs, e.g. at `/docs/authorization`. `GET` returns
+/// the stored authorization, `PUT` replaces it and `DELETE` removes it. Requests without a
+/// session are answered with `401 Unauthorized`.
+#[derive(Clone)]
+pub struct AuthorizationService {
+    backend: Backend,
+}
+
+impl fmt::Debug for AuthorizationService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthorizationService")
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuthorizationService {
+    /// Create a service keeping authorizations in `store`, by the session id `session` extracts
+    /// from the headers and extensions of a request, e.g. the id of a `tower-sessions` session
+    /// or the subject of the authenticated user.
+    pub fn new(
+        store: impl AuthorizationStore + 'static,
+        session: impl Fn(&HeaderMap, &Extensions) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            backend: Backend::Store {
+                store: Arc::new(store),
+                session: Arc::new(session),
+            },
+        }
+    }
+
+    /// Create a service keeping authorizations in the `tower_sessions::Session` of each
+    /// request, which the `SessionManagerLayer` of `tower-sessions` inserts, and so in its
+    /// session store.
+    #[cfg(feature = "tower-sessions")]
+    pub fn tower_sessions() -> Self {
+        Self {
+            backend: Backend::TowerSessions,
+        }
+    }
+
+    fn session<'a>(&'a self, req: &'a Request<Bytes>) -> Option<Session<'a>> {
+        match &self.backend {
+            Backend::Store { store, session } => Some(Session::Store(
+                store.as_ref(),
+                session(req.headers(), req.extensions())?,
+            )),
+            #[cfg(feature = "tower-sessions")]
+            Backend::TowerSessions => req
+                .extensions()
+                .get::<tower_sessions::Session>()
+                .map(Session::TowerSessions),
+        }
+    }
+
+    /// Returns the largest request body accepted, for adapters to limit how much they read.
+    pub fn max_request_size(&self) -> usize {
+        MAX_AUTHORIZATION_SIZE
+    }
+
+    /// Handle a request, for adapters that don't speak `tower`. Answers
+    /// `500 Internal Server Error` if the session store fails.
+    pub async fn handle(&self, req: &Request<Bytes>) -> Response<Body> {
+        let Some(session) = self.session(req) else {
+            return status(StatusCode::UNAUTHORIZED);
+        };
+        let stored = match *req.method() {
+            Method::GET => match session.load().await {
+                Ok(authorization) => {
+                    let authorization = authorization.unwrap_or_else(|| Bytes::from_static(b"{}"));
+                    let mut response = content(mime::JSON, authorization.into());
+                    response
+                        .headers_mut()
+                        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+                    return response;
+                }
+                Err(err) => Err(err),
+            },
+            Method::PUT => {
+                if req.body().len() > MAX_AUTHORIZATION_SIZE {
+                    return status(StatusCode::PAYLOAD_TOO_LARGE);
+                }
+                match serde_json::from_slice::<Value>(req.body()) {
+                    Ok(Value::Object(authorized)) if authorized.is_empty() => {
+                        session.remove().await
+                    }
+                    Ok(Value::Object(_)) => session.save(req.body().clone()).await,
+                    _ => return status(StatusCode::BAD_REQUEST),
+                }
+            }
+            Method::DELETE => session.remove().await,
+            _ => return method_not_allowed(),
+        };
+        match stored {
+            Ok(()) => status(StatusCode::NO_CONTENT),
+            Err(err) => {
+                log::error!("Could not store the authorization: {err}");
+                status(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
+
+impl Service<Request<Bytes>> for AuthorizationService {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Bytes>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { Ok(service.handle(&req).await) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorizationService, AuthorizationStore, MemoryAuthorizationStore};
+    use bytes::Bytes;
+    use http::{Method, Request, Response, StatusCode};
+
+    async fn send(
+        service: &AuthorizationService,
+        method: Method,
+        session: Option<&str>,
+        body: &'static str,
+    ) -> Response<crate::Body> {
+        let mut req = Request::builder().method(method).uri("/docs/authorization");
+        if let Some(session) = session {
+            req = req.header("x-session", session);
+        }
+        service
+            .handle(&req.body(Bytes::from_static(body.as_bytes())).unwrap())
+            .await
+    }
+
+    #[tokio::test]
+    async fn stores_authorization_per_session() {
+        let service =
+            AuthorizationService::new(MemoryAuthorizationStore::default(), |headers, _| {
+                Some(headers.get("x-session")?.to_str().ok()?.to_string())
+            });
+        let send = |method, session, body| send(&service, method, session, body);
+        let authorized = r#"{"api_key":{"name":"api_key","value":"secret"}}"#;
+
+        let put = send(Method::PUT, Some("a"), authorized).await;
+        assert_eq!(put.status(), StatusCode::NO_CONTENT);
+        let get = send(Method::GET, Some("a"), "").await;
+        assert_eq!(get.headers()["cache-control"], "no-store");
+        assert_eq!(get.into_body().into_bytes(), authorized);
+        assert_eq!(
+            send(Method::GET, Some("b"), "")
+                .await
+                .into_body()
+                .into_bytes(),
+            "{}"
+        );
+
+        assert_eq!(
+            send(Method::PUT, Some("a"), "[]").await.status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            send(Method::GET, None, "").await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        send(Method::DELETE, Some("a"), "").await;
+        assert_eq!(
+            send(Method::GET, Some("a"), "")
+                .await
+                .into_body()
+                .into_bytes(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn memory_store_forgets_the_least_recently_used_session() {
+        let store = MemoryAuthorizationStore::with_capacity(2);
+        store.save("a", Bytes::from_static(b"{}"));
+        store.save("b", Bytes::from_static(b"{}"));
+        store.load("a");
+        store.save("c", Bytes::from_static(b"{}"));
+        assert!(store.load("a").is_some());
+        assert!(store.load("b").is_none());
+        assert!(store.load("c").is_some());
+    }
+
+    #[cfg(feature = "tower-sessions")]
+    #[tokio::test]
+    async fn stores_authorization_in_tower_sessions() {
+        use std::sync::Arc;
+        use tower_sessions::{MemoryStore, Session};
+
+        let service = AuthorizationService::tower_sessions();
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        let send = |method, body: &'static str| {
+            let mut req = Request::builder()
+                .method(method)
+                .body(Bytes::from_static(body.as_bytes()))
+                .unwrap();
+            req.extensions_mut().insert(session.clone());
+            let service = service.clone();
+            async move { service.handle(&req).await }
+        };
+        let authorized = r#"{"api_key":{"value":"secret"}}"#;
+
+        assert_eq!(
+            send(Method::PUT, authorized).await.status(),
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            session.get_value("swagger_ui.authorization").await.unwrap(),
+            Some(serde_json::from_str(authorized).unwrap())
+        );
+        let get = send(Method::GET, "").await;
+        assert_eq!(get.into_body().into_bytes(), authorized);
+        send(Method::PUT, "{}").await;
+        assert_eq!(send(Method::GET, "").await.into_body().into_bytes(), "{}");
+
+        let without_layer = Request::new(Bytes::new());
+        assert_eq!(
+            service.handle(&without_layer).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
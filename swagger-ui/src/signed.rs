@@ -0,0 +1,203 @@
+//! Time-limited links to the docs, signed with HMAC-SHA256.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use http::header::{COOKIE, FORWARDED};
+use http::{HeaderMap, HeaderName, HeaderValue, Uri};
+use sha2::Sha256;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Cookie a valid link is remembered in, so the assets and the spec `index.html` loads are
+/// allowed as well.
+const COOKIE_NAME: &str = "swagger_ui_token";
+
+/// Header with the scheme of the request behind a reverse proxy.
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+/// Signs and validates links to docs that expire, see
+/// [`with_signed_urls`](crate::DocService::with_signed_urls).
+///
+/// A link carries its expiry as `exp`, in seconds since the Unix epoch, and the signature of the
+/// mount path and expiry as `token`, e.g. `/docs/index.html?exp=1700000000&token=...`. Keep the
+/// key secret, anyone with it can create links.
+#[derive(Clone)]
+pub struct UrlSigner {
+    key: Arc<[u8]>,
+}
+
+impl fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlSigner").finish_non_exhaustive()
+    }
+}
+
+impl UrlSigner {
+    /// Create a signer with the secret `key`.
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().into(),
+        }
+    }
+
+    /// Returns a link to `index.html` of the docs mounted at `base`, e.g. `/docs`, that is valid
+    /// for `valid_for` from now.
+    pub fn link(&self, base: &str, valid_for: Duration) -> String {
+        let expires = (SystemTime::now() + valid_for)
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |expires| expires.as_secs());
+        format!(
+            "{base}/index.html?exp={expires}&token={}",
+            self.token(base, expires)
+        )
+    }
+
+    /// Returns the signature of `base` and `expires`.
+    fn token(&self, base: &str, expires: u64) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(base, expires).finalize().into_bytes())
+    }
+
+    fn mac(&self, base: &str, expires: u64) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC takes keys of any size");
+        mac.update(format!("{base}\n{expires}").as_bytes());
+        mac
+    }
+
+    /// Returns the time left until `expires` if `token` signs `base` and `expires`, and
+    /// `expires` hasn't passed.
+    fn remaining(&self, base: &str, expires: &str, token: &str) -> Option<Duration> {
+        let expires = expires.parse::<u64>().ok()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs());
+        let token = URL_SAFE_NO_PAD.decode(token).ok()?;
+        let valid = expires > now && self.mac(base, expires).verify_slice(&token).is_ok();
+        valid.then(|| Duration::from_secs(expires - now))
+    }
+
+    /// Validates a request for `uri` to the docs mounted at `base` by the link in its query, or
+    /// else by the cookie set for an earlier one.
+    ///
+    /// Returns `None` for requests that aren't allowed, and for ones allowed by their query the
+    /// `Set-Cookie` header remembering the link until it expires, only sent back over `https`
+    /// if the request was made over it.
+    pub(crate) fn validate(
+        &self,
+        base: &str,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<Option<HeaderValue>> {
+        let (mut expires, mut token) = (None, None);
+        for (key, value) in form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+            match key.as_ref() {
+                "exp" => expires = Some(value.into_owned()),
+                "token" => token = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        if let (Some(expires), Some(token)) = (&expires, &token) {
+            if let Some(remaining) = self.remaining(base, expires, token) {
+                let path = if base.is_empty() { "/" } else { base };
+                let secure = match is_https(uri, headers) {
+                    true => "; Secure",
+                    false => "",
+                };
+                let cookie = format!(
+                    "{COOKIE_NAME}={expires}.{token}; Path={path}; Max-Age={}; HttpOnly; SameSite=Lax{secure}",
+                    remaining.as_secs()
+                );
+                return Some(HeaderValue::from_str(&cookie).ok());
+            }
+        }
+
+        let cookie = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(|header| header.split(';'))
+            .find_map(|cookie| cookie.trim().strip_prefix(COOKIE_NAME)?.strip_prefix('='))?;
+        let (expires, token) = cookie.split_once('.')?;
+        self.remaining(base, expires, token).map(|_| None)
+    }
+}
+
+/// Returns whether the request for `uri` was made over `https`, to the service itself or to the
+/// reverse proxy in front of it that sets `X-Forwarded-Proto` or `Forwarded`.
+fn is_https(uri: &Uri, headers: &HeaderMap) -> bool {
+    let forwarded = headers
+        .get(FORWARDED)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.split(',').next())
+        .and_then(|forwarded| {
+            forwarded
+                .split(';')
+                .find_map(|pair| pair.trim().strip_prefix("proto="))
+        });
+    let proto = headers
+        .get(X_FORWARDED_PROTO)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.split(',').next())
+        .or(forwarded)
+        .or(uri.scheme_str());
+    proto.is_some_and(|proto| proto.trim().trim_matches('"').eq_ignore_ascii_case("https"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UrlSigner;
+    use http::header::COOKIE;
+    use http::{HeaderMap, Uri};
+    use std::time::Duration;
+
+    #[test]
+    fn validates_links_and_their_cookie() {
+        let signer = UrlSigner::new("secret");
+        let link = signer.link("/docs", Duration::from_secs(60));
+        let uri: Uri = link.parse().unwrap();
+
+        let cookie = signer
+            .validate("/docs", &uri, &HeaderMap::new())
+            .unwrap()
+            .unwrap();
+        let mut headers = HeaderMap::new();
+        let value = cookie.to_str().unwrap().split(';').next().unwrap();
+        headers.insert(COOKIE, value.parse().unwrap());
+        assert_eq!(
+            signer.validate("/docs", &"/docs/openapi.json".parse().unwrap(), &headers),
+            Some(None)
+        );
+
+        // other mount paths, keys and expired links
+        assert_eq!(signer.validate("/admin", &uri, &HeaderMap::new()), None);
+        let other = UrlSigner::new("other");
+        assert_eq!(other.validate("/docs", &uri, &HeaderMap::new()), None);
+        let expired = signer.link("/docs", Duration::ZERO);
+        let uri: Uri = expired.parse().unwrap();
+        assert_eq!(signer.validate("/docs", &uri, &HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn remembers_links_as_long_as_they_are_valid_and_over_https_only() {
+        let signer = UrlSigner::new("secret");
+        let link = signer.link("/docs", Duration::from_secs(60));
+        let cookie = |uri: &str, headers: &[(&'static str, &'static str)]| {
+            let mut map = HeaderMap::new();
+            for (name, value) in headers {
+                map.insert(*name, value.parse().unwrap());
+            }
+            let cookie = signer.validate("/docs", &uri.parse().unwrap(), &map);
+            cookie.unwrap().unwrap().to_str().unwrap().to_string()
+        };
+
+        let plain = cookie(&link, &[]);
+        assert!(plain.contains("; Max-Age=60;") || plain.contains("; Max-Age=59;"));
+        assert!(!plain.contains("Secure"));
+        let https = format!("https://docs.example.com{link}");
+        assert!(cookie(&https, &[]).ends_with("; Secure"));
+        assert!(cookie(&link, &[("x-forwarded-proto", "https")]).ends_with("; Secure"));
+        assert!(cookie(&link, &[("forwarded", "for=1.2.3.4;proto=https")]).ends_with("; Secure"));
+    }
+}
@@ -0,0 +1,55 @@
+//! Canonical ordering of the keys of a spec.
+
+use crate::Spec;
+use serde_json::{Map, Value};
+
+/// Returns `spec` with the keys of every object sorted, so the paths, their methods and the
+/// components come in the same order however the spec was generated, or `None` if the spec isn't
+/// JSON.
+///
+/// Serializing the same spec twice gives the same bytes, which keeps diffs between deployments
+/// and cached copies down to actual changes. Arrays keep their order, which is meaningful, e.g.
+/// for parameters.
+pub fn sort_spec(spec: &Spec) -> Option<Spec> {
+    let document: Value = serde_json::from_slice(&spec.content).ok()?;
+    let content = serde_json::to_vec(&sort(document)).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+/// Sorts the keys of `value` and the objects in it, regardless of whether `serde_json` keeps
+/// the order of insertion.
+fn sort(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let object: Map<String, Value> = entries
+                .into_iter()
+                .map(|(key, value)| (key, sort(value)))
+                .collect();
+            Value::Object(object)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sort_spec;
+    use crate::Spec;
+
+    #[test]
+    fn sorts_keys_but_not_arrays() {
+        let spec = Spec::builder().json(
+            r#"{"paths": {"/b": {"post": {}, "get": {"parameters": [{"name": "z"}, {"name": "a"}]}},
+                "/a": {}}, "components": {"schemas": {"Pet": {}, "Owner": {}}}}"#,
+        );
+        let sorted = sort_spec(&spec).unwrap();
+        assert_eq!(
+            sorted.content,
+            r#"{"components":{"schemas":{"Owner":{},"Pet":{}}},"paths":{"/a":{},"/b":{"get":{"parameters":[{"name":"z"},{"name":"a"}]},"post":{}}}}"#
+        );
+        assert_eq!(sort_spec(&sorted).unwrap().content, sorted.content);
+    }
+}
@@ -0,0 +1,116 @@
+//! Making deprecated operations and their sunset dates hard to miss.
+
+use crate::markdown::METHODS;
+use crate::Spec;
+use serde_json::Value;
+
+/// Plugin and styles for `index.html` showing a banner below the summary of every deprecated
+/// operation, with its `x-sunset` date if it has one.
+pub(crate) const BANNER: &str = r#"<style>
+.sunset-banner {
+  margin: 0 10px 10px;
+  padding: 6px 10px;
+  border-left: 4px solid #f93e3e;
+  background: #fdecea;
+  color: #3b4151;
+  font-family: sans-serif;
+  font-size: 13px;
+}
+</style>
+<script>
+(function () {
+  function banner(React, op) {
+    var sunset = op && op.get("x-sunset");
+    if (!op || !(op.get("deprecated") || sunset)) {
+      return null;
+    }
+    var text = sunset ? "Deprecated, removed on " + sunset + "." : "Deprecated.";
+    return React.createElement("div", {className: "sunset-banner"}, text);
+  }
+  function sunset(system) {
+    return {
+      wrapComponents: {
+        OperationSummary: function (Original) {
+          return function (props) {
+            var op = props.operationProps && props.operationProps.get("op");
+            return system.React.createElement("div", null,
+              system.React.createElement(Original, props),
+              banner(system.React, op));
+          };
+        }
+      }
+    };
+  }
+  var bundle = window.SwaggerUIBundle;
+  window.SwaggerUIBundle = Object.assign(function (options) {
+    options.plugins = (options.plugins || []).concat([sunset]);
+    return bundle(options);
+  }, bundle);
+})();
+</script>
+"#;
+
+/// Returns `spec` with a notice at the start of the description of every operation that is
+/// `deprecated` or has an `x-sunset` date, e.g. `2025-06-30`, or `None` if the spec isn't JSON.
+///
+/// Viewers render the notice as a quote above the description, where readers look first.
+/// Operations marked with `x-sunset` only are marked `deprecated` as well.
+pub fn sunset_notices(spec: &Spec) -> Option<Spec> {
+    let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+    for section in ["paths", "webhooks"] {
+        let Some(items) = document[section].as_object_mut() else {
+            continue;
+        };
+        for item in items.values_mut() {
+            for method in METHODS {
+                if let Some(operation) = item.get_mut(method).and_then(Value::as_object_mut) {
+                    let sunset = operation.get("x-sunset").and_then(Value::as_str);
+                    let notice = match sunset {
+                        Some(sunset) => format!("> **Deprecated**, removed on {sunset}."),
+                        None if operation.get("deprecated") == Some(&Value::Bool(true)) => {
+                            "> **Deprecated**.".to_string()
+                        }
+                        None => continue,
+                    };
+                    let description = match operation.get("description").and_then(Value::as_str) {
+                        Some(description) => format!("{notice}\n\n{description}"),
+                        None => notice,
+                    };
+                    operation.insert("description".into(), description.into());
+                    operation.insert("deprecated".into(), true.into());
+                }
+            }
+        }
+    }
+    let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sunset_notices;
+    use crate::Spec;
+    use serde_json::Value;
+
+    #[test]
+    fn adds_notices_to_deprecated_operations() {
+        let spec = Spec::builder().json(
+            r#"{"paths": {"/pets": {
+                "get": {"x-sunset": "2025-06-30", "description": "Lists pets."},
+                "post": {"deprecated": true},
+                "put": {"deprecated": false}
+            }}}"#,
+        );
+        let spec = sunset_notices(&spec).unwrap();
+        let document: Value = serde_json::from_slice(&spec.content).unwrap();
+        let pets = &document["paths"]["/pets"];
+
+        assert_eq!(
+            pets["get"]["description"],
+            "> **Deprecated**, removed on 2025-06-30.\n\nLists pets."
+        );
+        assert_eq!(pets["get"]["deprecated"], true);
+        assert_eq!(pets["post"]["description"], "> **Deprecated**.");
+        assert!(pets["put"].get("description").is_none());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{html, DocService, DocUi};
+use bytes::Bytes;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Page loading the swagger-editor bundles, with the spec url and mode filled in.
+const INDEX: &str = include_str!("swagger_editor.html");
+
+/// The swagger-editor bundles from swagger-editor-dist
+#[derive(RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/.viewers/swagger-editor"]
+pub struct SwaggerEditorAssets;
+
+/// Used to pick whether the spec can be edited in [`SwaggerEditorService`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EditorMode {
+    /// Always show the mounted spec, edits in the editor pane are dropped.
+    #[default]
+    ReadOnly,
+    /// Allow editing, the edited spec is kept in the local storage of the browser and shown
+    /// instead of the mounted one until it is cleared.
+    LocalStorage,
+}
+
+/// swagger-editor, served from the embedded [`SwaggerEditorAssets`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwaggerEditor;
+
+impl DocUi for SwaggerEditor {
+    type Config = EditorMode;
+
+    fn asset(path: &str) -> Option<Cow<'static, [u8]>> {
+        SwaggerEditorAssets::get(path)
+    }
+
+    fn asset_paths() -> Vec<String> {
+        SwaggerEditorAssets::iter().map(Cow::into_owned).collect()
+    }
+
+    fn index(spec_url: &str, config: &EditorMode) -> Option<Bytes> {
+        let spec_url = serde_json::to_string(spec_url).expect("Could not serialize spec url.");
+        let read_only = *config == EditorMode::ReadOnly;
+        let page = INDEX
+            .replace("{{spec_url}}", &html::script(&spec_url))
+            .replace("{{read_only}}", &read_only.to_string());
+        Some(Bytes::from(page))
+    }
+}
+
+/// A framework-agnostic `tower::Service` serving the specified spec with swagger-editor instead
+/// of swagger-ui, so spec authors can iterate on it next to the running service.
+///
+/// This is meant for development rather than for publishing the docs. It is mounted like
+/// [`SwaggerUiService`](crate::SwaggerUiService), see [`MountPath`](crate::MountPath).
+pub type SwaggerEditorService = DocService<SwaggerEditor>;
+
+#[cfg(test)]
+mod tests {
+    use super::{EditorMode, SwaggerEditorService};
+    use crate::MountPath;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::{Request, StatusCode};
+
+    fn page(service: &SwaggerEditorService) -> String {
+        let mut req = Request::get("/index.html").body(()).unwrap();
+        req.extensions_mut()
+            .insert(MountPath("/editor".to_string()));
+        let response = service.handle(&req);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        String::from_utf8(response.into_body().into_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serves_the_editor_read_only_by_default() {
+        let service =
+            SwaggerEditorService::new(crate::swagger_spec_file!("../examples/openapi.json"), None);
+
+        let response = service.handle(&Request::get("/").body(()).unwrap());
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/index.html");
+
+        let page = page(&service);
+        assert!(page.contains(r#"const specUrl = "/editor/openapi.json";"#));
+        assert!(page.contains("const readOnly = true;"));
+
+        for asset in [
+            "/swagger-editor-bundle.js",
+            "/swagger-editor.css",
+            "/openapi.json",
+        ] {
+            let response = service.handle(&Request::get(asset).body(()).unwrap());
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+
+    #[test]
+    fn allows_editing_in_local_storage_mode() {
+        let service = SwaggerEditorService::new(
+            crate::swagger_spec_file!("../examples/openapi.json"),
+            EditorMode::LocalStorage,
+        );
+        assert!(page(&service).contains("const readOnly = false;"));
+    }
+}
@@ -0,0 +1,277 @@
+//! Serving the docs of many tenants from one deployment, each with their own spec and config.
+
+use crate::handlers::status;
+use crate::lru::Lru;
+use crate::{Body, DocService, DocUi, Spec, SwaggerUi};
+use http::header::HOST;
+use http::{HeaderMap, HeaderName, Request, Response, StatusCode};
+use std::convert::Infallible;
+use std::fmt;
+use std::future::{ready, Ready};
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Header naming the tenant of a request, see [`tenant_from_header`].
+pub const TENANT_HEADER: HeaderName = HeaderName::from_static("x-tenant");
+
+/// Number of tenants whose service is kept, the one whose docs were requested longest ago is
+/// created again on its next request.
+const TENANT_CACHE_SIZE: usize = 256;
+
+type Load<U> = Arc<dyn Fn(&str) -> Option<(Spec, <U as DocUi>::Config)> + Send + Sync>;
+type Resolve = Arc<dyn Fn(&HeaderMap) -> Option<String> + Send + Sync>;
+type Customize<U> = Arc<dyn Fn(&str, DocService<U>) -> DocService<U> + Send + Sync>;
+
+/// Returns whether `tenant` is made of 1 to 63 ASCII letters, digits, `-` and `_`.
+fn is_valid(tenant: &str) -> bool {
+    !tenant.is_empty()
+        && tenant.len() <= 63
+        && tenant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the tenant of a request, the lowercased subdomain of its `Host`, e.g. `acme` for
+/// `acme.docs.example.com`.
+///
+/// Hosts with fewer than three labels, e.g. `example.com`, and IP addresses have no tenant.
+pub fn tenant_of(headers: &HeaderMap) -> Option<String> {
+    let host = headers.get(HOST)?.to_str().ok()?;
+    if host.starts_with('[') {
+        return None;
+    }
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    if host.parse::<Ipv4Addr>().is_ok() {
+        return None;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let tenant = labels[0].to_ascii_lowercase();
+    (labels.len() >= 3 && is_valid(&tenant)).then_some(tenant)
+}
+
+/// Returns the tenant named by the `X-Tenant` header of a request, for
+/// [`TenantService::with_resolver`].
+///
+/// Clients can set the header to any tenant, so it may only be trusted if a proxy in front of
+/// the service sets it, replacing the one of the client.
+pub fn tenant_from_header(headers: &HeaderMap) -> Option<String> {
+    let tenant = headers.get(TENANT_HEADER)?.to_str().ok()?.trim();
+    is_valid(tenant).then(|| tenant.to_string())
+}
+
+/// A `tower::Service` serving every tenant the docs of their own spec and config, e.g. branded
+/// and scoped for each customer.
+///
+/// The tenant of a request is found by [`tenant_of`], or the resolver given to
+/// [`with_resolver`](Self::with_resolver), and its spec and config are loaded once, on its
+/// first request. Requests without a tenant, or for one that isn't loaded, are answered with
+/// `404 Not Found`.
+pub struct TenantService<U: DocUi = SwaggerUi> {
+    load: Load<U>,
+    resolve: Resolve,
+    customize: Option<Customize<U>>,
+    services: Arc<Lru<String, DocService<U>>>,
+}
+
+impl<U: DocUi> Clone for TenantService<U> {
+    fn clone(&self) -> Self {
+        Self {
+            load: self.load.clone(),
+            resolve: self.resolve.clone(),
+            customize: self.customize.clone(),
+            services: self.services.clone(),
+        }
+    }
+}
+
+impl<U: DocUi> fmt::Debug for TenantService<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantService")
+            .field("tenants", &self.services.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<U: DocUi> TenantService<U> {
+    /// Create a service with the spec and config `load` returns for a tenant, or `None` for
+    /// tenants that don't exist.
+    pub fn new(load: impl Fn(&str) -> Option<(Spec, U::Config)> + Send + Sync + 'static) -> Self {
+        Self {
+            load: Arc::new(load),
+            resolve: Arc::new(tenant_of),
+            customize: None,
+            services: Arc::new(Lru::new(TENANT_CACHE_SIZE)),
+        }
+    }
+
+    /// Find the tenant of a request with `resolve` instead of [`tenant_of`], e.g. from a claim
+    /// of the user, or with [`tenant_from_header`] behind a proxy setting `X-Tenant`.
+    pub fn with_resolver(
+        mut self,
+        resolve: impl Fn(&HeaderMap) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.resolve = Arc::new(resolve);
+        self
+    }
+
+    /// Configure the service of every tenant further once it is created, e.g.
+    /// `|_, service| service.with_cors(cors.clone())`.
+    pub fn with_service(
+        mut self,
+        customize: impl Fn(&str, DocService<U>) -> DocService<U> + Send + Sync + 'static,
+    ) -> Self {
+        self.customize = Some(Arc::new(customize));
+        self
+    }
+
+    /// Drop the service of `tenant`, so that its spec and config are loaded again on its next
+    /// request, e.g. after they changed.
+    pub fn forget(&self, tenant: &str) {
+        self.services.remove(tenant);
+    }
+
+    /// Handle a request synchronously, for adapters that don't speak `tower`.
+    pub fn handle<B>(&self, req: &Request<B>) -> Response<Body> {
+        let Some(tenant) = (self.resolve)(req.headers()) else {
+            return status(StatusCode::NOT_FOUND);
+        };
+        match self.service(&tenant) {
+            Some(service) => service.handle(req),
+            None => status(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// Returns the service of `tenant`, created on its first request.
+    fn service(&self, tenant: &str) -> Option<DocService<U>> {
+        if let Some(service) = self.services.get(tenant) {
+            return Some(service);
+        }
+        // unknown tenants aren't cached, they are as many as there are made-up names
+        let (spec, config) = (self.load)(tenant)?;
+        let mut service = DocService::new(spec, config);
+        if let Some(customize) = &self.customize {
+            service = customize(tenant, service);
+        }
+        self.services.insert(tenant.to_string(), service.clone());
+        Some(service)
+    }
+}
+
+impl<U: DocUi, B> Service<Request<B>> for TenantService<U> {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        ready(Ok(self.handle(&req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tenant_from_header, tenant_of, TenantService};
+    use crate::{Config, Spec, SwaggerUi};
+    use http::header::HOST;
+    use http::{HeaderMap, Request, StatusCode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn finds_the_tenant_in_the_subdomain_or_the_header() {
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in pairs {
+                headers.insert(*name, value.parse().unwrap());
+            }
+            headers
+        };
+        assert_eq!(
+            tenant_of(&headers(&[("host", "Acme.docs.example.com:8080")])).unwrap(),
+            "acme"
+        );
+        let forwarded = headers(&[("host", "acme.docs.example.com"), ("x-tenant", "globex")]);
+        assert_eq!(tenant_of(&forwarded).unwrap(), "acme");
+        assert_eq!(tenant_from_header(&forwarded).unwrap(), "globex");
+        assert_eq!(tenant_of(&headers(&[("host", "example.com")])), None);
+        assert_eq!(tenant_of(&headers(&[("host", "10.0.0.1:8080")])), None);
+        assert_eq!(tenant_of(&headers(&[("host", "[fd00::1]:8080")])), None);
+        assert_eq!(
+            tenant_from_header(&headers(&[("x-tenant", "../etc")])),
+            None
+        );
+    }
+
+    #[test]
+    fn serves_and_caches_the_docs_of_every_tenant() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = loads.clone();
+        let service = TenantService::<SwaggerUi>::new(move |tenant| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            let title = match tenant {
+                "acme" => "Acme API",
+                "globex" => "Globex API",
+                _ => return None,
+            };
+            let spec = format!(r#"{{"openapi": "3.0.0", "info": {{"title": "{title}"}}}}"#);
+            Some((Spec::builder().json(spec), Config::default()))
+        });
+        let title = |host: &str| {
+            let req = Request::get("/openapi.json")
+                .header(HOST, host)
+                .body(())
+                .unwrap();
+            let response = service.handle(&req);
+            if response.status() != StatusCode::OK {
+                return None;
+            }
+            let spec: serde_json::Value =
+                serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+            Some(spec["info"]["title"].as_str().unwrap().to_string())
+        };
+
+        assert_eq!(title("acme.docs.example.com").unwrap(), "Acme API");
+        assert_eq!(title("globex.docs.example.com").unwrap(), "Globex API");
+        assert_eq!(title("acme.docs.example.com").unwrap(), "Acme API");
+        assert_eq!(loads.load(Ordering::Relaxed), 2);
+        assert_eq!(title("initech.docs.example.com"), None);
+        assert_eq!(title("example.com"), None);
+
+        service.forget("acme");
+        title("acme.docs.example.com");
+        assert_eq!(loads.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn keeps_the_services_of_the_tenants_requested_last() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = loads.clone();
+        let service = TenantService::<SwaggerUi>::new(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Some((
+                Spec::builder().json(r#"{"openapi": "3.0.0"}"#),
+                Config::default(),
+            ))
+        })
+        .with_resolver(tenant_from_header);
+        let request = |tenant: &str| {
+            let req = Request::get("/openapi.json")
+                .header("x-tenant", tenant)
+                .body(())
+                .unwrap();
+            assert_eq!(service.handle(&req).status(), StatusCode::OK);
+        };
+
+        for tenant in 0..=super::TENANT_CACHE_SIZE {
+            request(&format!("tenant-{tenant}"));
+        }
+        request(&format!("tenant-{}", super::TENANT_CACHE_SIZE));
+        assert_eq!(loads.load(Ordering::Relaxed), super::TENANT_CACHE_SIZE + 1);
+        assert_eq!(service.services.len(), super::TENANT_CACHE_SIZE);
+    }
+}
@@ -0,0 +1,223 @@
+//! Showing the terms of use of the docs before the API reference.
+
+use crate::handlers::content;
+use crate::html::attribute;
+use crate::{mime, Body};
+use bytes::Bytes;
+use http::header::{CACHE_CONTROL, COOKIE, LOCATION, SET_COOKIE};
+use http::{Extensions, HeaderMap, HeaderValue, Response, StatusCode};
+use std::fmt;
+use std::sync::Arc;
+
+/// Page showing the terms, with a button accepting them.
+const PAGE: &str = include_str!("terms.html");
+
+/// Cookie recording the version of the terms accepted in the session.
+const COOKIE_NAME: &str = "swagger_ui_terms";
+
+/// Path, relative to the mount point, the terms are accepted at.
+pub(crate) const ACCEPT_PATH: &str = "terms";
+
+/// Called with the headers and extensions of every request accepting the terms.
+type OnAccept = Arc<dyn Fn(&HeaderMap, &Extensions) + Send + Sync>;
+
+/// Terms of use shown in place of `index.html` until they are accepted, once per browser
+/// session, see [`DocService::with_terms`](crate::DocService::with_terms).
+#[derive(Clone)]
+pub struct Terms {
+    page: Bytes,
+    version: String,
+    on_accept: Option<OnAccept>,
+}
+
+impl fmt::Debug for Terms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Terms")
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Terms {
+    /// Create terms shown as the HTML `html`, which is trusted and inserted as it is.
+    pub fn html(html: impl AsRef<str>) -> Self {
+        Self {
+            page: Bytes::from(PAGE.replace("{terms}", html.as_ref())),
+            version: "1".to_string(),
+            on_accept: None,
+        }
+    }
+
+    /// Create terms shown as the Markdown `markdown`, of which headings, paragraphs, lists,
+    /// `**bold**` text and links are rendered, and anything else is shown as text.
+    pub fn markdown(markdown: &str) -> Self {
+        Self::html(render(markdown))
+    }
+
+    /// Ask for the terms to be accepted again in sessions that accepted another `version`, e.g.
+    /// after they changed. Versions are made of ASCII letters, digits, `.`, `-` and `_`.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        let version: String = version.into();
+        self.version = version
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            .collect();
+        self
+    }
+
+    /// Call `record` with the headers and extensions of every request accepting the terms,
+    /// e.g. to record who accepted them when.
+    pub fn on_accept(
+        mut self,
+        record: impl Fn(&HeaderMap, &Extensions) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_accept = Some(Arc::new(record));
+        self
+    }
+
+    /// Returns whether the terms were accepted in the session `headers` are from.
+    pub(crate) fn accepted(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(|header| header.split(';'))
+            .filter_map(|cookie| cookie.trim().strip_prefix(COOKIE_NAME)?.strip_prefix('='))
+            .any(|version| version == self.version)
+    }
+
+    /// Returns the page showing the terms.
+    pub(crate) fn page(&self) -> Response<Body> {
+        let mut response = content(mime::HTML, self.page.clone().into());
+        let no_store = HeaderValue::from_static("no-store");
+        response.headers_mut().insert(CACHE_CONTROL, no_store);
+        response
+    }
+
+    /// Accepts the terms for the session, and sends the browser on to `index.html` of the
+    /// service mounted at `base`.
+    pub(crate) fn accept(
+        &self,
+        headers: &HeaderMap,
+        extensions: &Extensions,
+        base: &str,
+    ) -> Response<Body> {
+        if let Some(record) = &self.on_accept {
+            record(headers, extensions);
+        }
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::SEE_OTHER;
+        let location = format!("{base}/index.html");
+        let path = match base {
+            "" => "/",
+            base => base,
+        };
+        // no Max-Age, a new browser session asks again
+        let cookie = format!(
+            "{COOKIE_NAME}={}; Path={}; HttpOnly; SameSite=Lax",
+            self.version,
+            path.replace(';', "")
+        );
+        let headers = response.headers_mut();
+        headers.insert(
+            LOCATION,
+            HeaderValue::from_str(&location).expect("redirect location is a valid header value"),
+        );
+        headers.insert(
+            SET_COOKIE,
+            HeaderValue::from_str(&cookie).expect("cookie is a valid header value"),
+        );
+        response
+    }
+}
+
+/// Renders the Markdown `markdown` into HTML, see [`Terms::markdown`].
+fn render(markdown: &str) -> String {
+    let mut html = String::new();
+    for block in markdown
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+    {
+        let lines: Vec<&str> = block.lines().map(str::trim).collect();
+        let level = block.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && block[level..].starts_with(' ') {
+            let heading = inline(block[level..].trim());
+            html.push_str(&format!("<h{level}>{heading}</h{level}>\n"));
+        } else if lines
+            .iter()
+            .all(|line| line.starts_with("- ") || line.starts_with("* "))
+        {
+            html.push_str("<ul>\n");
+            for line in lines {
+                html.push_str(&format!("<li>{}</li>\n", inline(&line[2..])));
+            }
+            html.push_str("</ul>\n");
+        } else {
+            html.push_str(&format!("<p>{}</p>\n", inline(&lines.join(" "))));
+        }
+    }
+    html
+}
+
+/// Renders the `**bold**` text and links of `text`, escaping the rest.
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    let mut bold = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("**") {
+            html.push_str(if bold { "</strong>" } else { "<strong>" });
+            bold = !bold;
+            rest = after;
+            continue;
+        }
+        if let Some((label, url, after)) = link(rest) {
+            html.push_str(&format!(
+                r#"<a href="{}">{}</a>"#,
+                attribute(url),
+                attribute(label)
+            ));
+            rest = after;
+            continue;
+        }
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let next = rest[first..]
+            .find(['*', '['])
+            .map_or(rest.len(), |at| at + first);
+        html.push_str(&attribute(&rest[..next]));
+        rest = &rest[next..];
+    }
+    if bold {
+        html.push_str("</strong>");
+    }
+    html
+}
+
+/// Splits a link to an http(s), `mailto:` or relative url off the start of `text`, returning
+/// its label, its url and the text after it.
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let (label, rest) = text.strip_prefix('[')?.split_once("](")?;
+    let (url, after) = rest.split_once(')')?;
+    let safe = ["https://", "http://", "mailto:", "/", "#", "./"];
+    safe.iter()
+        .any(|prefix| url.starts_with(prefix))
+        .then_some((label, url, after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    #[test]
+    fn renders_the_markdown_of_the_terms() {
+        let markdown = "# Terms of use\n\nBy using the API you accept the\n**partner agreement**, see [the terms](https://example.com/terms).\n\n- No scraping\n- No <script> tags\n\n[bad](javascript:alert(1))";
+        assert_eq!(
+            render(markdown),
+            "<h1>Terms of use</h1>\n\
+             <p>By using the API you accept the <strong>partner agreement</strong>, see <a href=\"https://example.com/terms\">the terms</a>.</p>\n\
+             <ul>\n<li>No scraping</li>\n<li>No &lt;script&gt; tags</li>\n</ul>\n\
+             <p>[bad](javascript:alert(1))</p>\n"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Restyling swagger-ui for a brand without writing CSS by hand.
+
+/// The look of swagger-ui for a brand, served as a stylesheet overriding its own, see
+/// [`DocService::with_theme`](crate::DocService::with_theme).
+///
+/// Every option left unset keeps the look of swagger-ui.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Theme {
+    /// Color of the top bar, the buttons and the links, e.g. `#0b5fff`.
+    pub primary_color: Option<String>,
+    /// Font stack of all text but code, e.g. `Inter, sans-serif`.
+    pub font_family: Option<String>,
+    /// Url of the logo shown in the top bar in place of swagger-ui's.
+    pub logo_url: Option<String>,
+    /// Radius of the corners of buttons, inputs and operations, e.g. `8px`.
+    pub border_radius: Option<String>,
+}
+
+impl Theme {
+    /// Create a theme keeping the look of swagger-ui.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Color the top bar, the buttons and the links with `color`.
+    pub fn primary_color(mut self, color: impl Into<String>) -> Self {
+        self.primary_color = Some(color.into());
+        self
+    }
+
+    /// Set all text but code in `fonts`.
+    pub fn font_family(mut self, fonts: impl Into<String>) -> Self {
+        self.font_family = Some(fonts.into());
+        self
+    }
+
+    /// Show the logo at `url` in the top bar.
+    pub fn logo_url(mut self, url: impl Into<String>) -> Self {
+        self.logo_url = Some(url.into());
+        self
+    }
+
+    /// Round the corners of buttons, inputs and operations by `radius`.
+    pub fn border_radius(mut self, radius: impl Into<String>) -> Self {
+        self.border_radius = Some(radius.into());
+        self
+    }
+
+    /// Returns the stylesheet overriding the styles of swagger-ui with the theme.
+    ///
+    /// Values that would end their declaration, e.g. with `;` or `}`, are cut off there, so a
+    /// theme read from a tenant's settings can't add rules of its own.
+    pub fn css(&self) -> String {
+        let mut css = String::new();
+        if let Some(color) = self.primary_color.as_deref().map(value) {
+            css.push_str(&format!(
+                ".swagger-ui .topbar {{ background-color: {color}; }}\n\
+                 .swagger-ui .btn.execute, .swagger-ui .btn.authorize {{ \
+                 background-color: {color}; border-color: {color}; color: #fff; }}\n\
+                 .swagger-ui .btn.authorize svg {{ fill: #fff; }}\n\
+                 .swagger-ui a, .swagger-ui .info a {{ color: {color}; }}\n"
+            ));
+        }
+        if let Some(fonts) = self.font_family.as_deref().map(value) {
+            css.push_str(&format!(
+                ".swagger-ui, .swagger-ui .info .title, .swagger-ui .opblock-tag, \
+                 .swagger-ui .opblock .opblock-summary-description, .swagger-ui .btn, \
+                 .swagger-ui label, .swagger-ui table {{ font-family: {fonts}; }}\n"
+            ));
+        }
+        if let Some(url) = &self.logo_url {
+            let url = url.replace(['"', '\\', '\n', '\r'], "");
+            css.push_str(&format!(
+                ".swagger-ui .topbar-wrapper .link svg, .swagger-ui .topbar-wrapper .link img \
+                 {{ display: none; }}\n\
+                 .swagger-ui .topbar-wrapper .link::before {{ content: \"\"; display: block; \
+                 width: 160px; height: 40px; \
+                 background: url(\"{url}\") no-repeat left center / contain; }}\n"
+            ));
+        }
+        if let Some(radius) = self.border_radius.as_deref().map(value) {
+            css.push_str(&format!(
+                ".swagger-ui .btn, .swagger-ui .opblock, .swagger-ui .opblock .opblock-summary-method, \
+                 .swagger-ui input, .swagger-ui select, .swagger-ui textarea, \
+                 .swagger-ui .model-box {{ border-radius: {radius}; }}\n"
+            ));
+        }
+        css
+    }
+}
+
+/// Returns `value` up to the first character that would end its declaration or rule.
+fn value(value: &str) -> &str {
+    let end = value
+        .find([';', '{', '}', '<', '>', '\\', '\n', '\r'])
+        .unwrap_or(value.len());
+    value[..end].trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Theme;
+
+    #[test]
+    fn generates_overrides_for_the_options_set() {
+        let css = Theme::new()
+            .primary_color("#0b5fff; } body { display: none")
+            .logo_url("https://example.com/logo.svg\"); }")
+            .css();
+
+        assert!(css.contains(".swagger-ui .topbar { background-color: #0b5fff; }"));
+        assert!(css.contains(r#"url("https://example.com/logo.svg); }")"#));
+        assert!(!css.contains("display: none\n") && !css.contains("body {"));
+        assert!(!css.contains("font-family"));
+        assert_eq!(Theme::new().css(), "");
+    }
+}
@@ -0,0 +1,402 @@
+//! Checks of a spec, for catching mistakes before it is served.
+
+use crate::markdown::METHODS;
+use crate::Spec;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The spec is usable, but could be clearer.
+    Warning,
+    /// The spec is invalid, viewers are likely to show it incompletely.
+    Error,
+}
+
+/// A problem found in a spec by [`validate`] or [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// JSON pointer to where in the spec the problem is, e.g. `/paths/~1pets/get`.
+    pub pointer: String,
+    /// What the problem is.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self.pointer.as_str() {
+            "" => write!(f, "{severity}: {}", self.message),
+            pointer => write!(f, "{severity}: {pointer}: {}", self.message),
+        }
+    }
+}
+
+/// Checks that `spec` is a structurally valid Swagger 2.0 or OpenAPI 3 document: a version,
+/// an `info` with a title and version, operations with responses, declared path parameters,
+/// OpenAPI 3.1 `webhooks` of path items and local `$ref`s that resolve.
+///
+/// Only JSON specs can be checked, others are reported as an error.
+pub fn validate(spec: &Spec) -> Vec<Diagnostic> {
+    let document = match serde_json::from_slice::<Value>(&spec.content) {
+        Ok(document) => document,
+        Err(err) => return vec![Diagnostic::error("", format!("not a JSON document: {err}"))],
+    };
+    let mut diagnostics = vec![];
+
+    let version = document["openapi"]
+        .as_str()
+        .or_else(|| document["swagger"].as_str());
+    match version {
+        Some(version) if version.starts_with("3.") || version == "2.0" => {}
+        Some(version) => diagnostics.push(Diagnostic::error(
+            "",
+            format!("unsupported version `{version}`"),
+        )),
+        None => diagnostics.push(Diagnostic::error(
+            "",
+            "missing `openapi` or `swagger` version",
+        )),
+    }
+
+    for field in ["title", "version"] {
+        if !document["info"][field].is_string() {
+            diagnostics.push(Diagnostic::error("/info", format!("missing `{field}`")));
+        }
+    }
+
+    match &document["paths"] {
+        Value::Object(paths) => {
+            for (path, item) in paths {
+                let pointer = format!("/paths/{}", escape(path));
+                if !path.starts_with('/') {
+                    diagnostics.push(Diagnostic::error(&pointer, "paths must start with `/`"));
+                }
+                for (method, operation) in operations(item) {
+                    let pointer = format!("{pointer}/{method}");
+                    if !operation["responses"].is_object() {
+                        diagnostics.push(Diagnostic::error(&pointer, "missing `responses`"));
+                    }
+                    diagnostics.extend(check_path_parameters(
+                        &document, path, item, operation, &pointer,
+                    ));
+                }
+            }
+        }
+        // OpenAPI 3.1 allows documents with only webhooks or components
+        Value::Null
+            if document["openapi"]
+                .as_str()
+                .is_some_and(|v| v.starts_with("3.1")) => {}
+        Value::Null => diagnostics.push(Diagnostic::error("", "missing `paths`")),
+        _ => diagnostics.push(Diagnostic::error("/paths", "must be an object")),
+    }
+
+    match &document["webhooks"] {
+        Value::Object(webhooks) => {
+            if !document["openapi"]
+                .as_str()
+                .is_some_and(|v| v.starts_with("3.") && !v.starts_with("3.0"))
+            {
+                diagnostics.push(Diagnostic::error(
+                    "/webhooks",
+                    "`webhooks` requires OpenAPI 3.1",
+                ));
+            }
+            for (name, item) in webhooks {
+                if !item.is_object() {
+                    let pointer = format!("/webhooks/{}", escape(name));
+                    diagnostics.push(Diagnostic::error(pointer, "must be a path item object"));
+                }
+            }
+        }
+        Value::Null => {}
+        _ => diagnostics.push(Diagnostic::error("/webhooks", "must be an object")),
+    }
+
+    check_refs(&document, &document, &mut String::new(), &mut diagnostics);
+    diagnostics
+}
+
+/// Checks `spec` for style problems that don't make it invalid: missing or duplicate
+/// `operationId`s, operations without a summary or tags and unused schemas. The operations of
+/// `webhooks` are linted like those of `paths`.
+///
+/// Specs that aren't JSON are reported like by [`validate`].
+pub fn lint(spec: &Spec) -> Vec<Diagnostic> {
+    let document = match serde_json::from_slice::<Value>(&spec.content) {
+        Ok(document) => document,
+        Err(err) => return vec![Diagnostic::error("", format!("not a JSON document: {err}"))],
+    };
+    let mut diagnostics = vec![];
+
+    if document["info"]["description"]
+        .as_str()
+        .is_none_or(str::is_empty)
+    {
+        diagnostics.push(Diagnostic::warning("/info", "missing `description`"));
+    }
+
+    let mut operation_ids: HashMap<&str, String> = HashMap::new();
+    let items = ["paths", "webhooks"].into_iter().flat_map(|section| {
+        let items = document[section].as_object().into_iter().flatten();
+        items.map(move |(path, item)| (section, path, item))
+    });
+    for (section, path, item) in items {
+        for (method, operation) in operations(item) {
+            let pointer = format!("/{section}/{}/{method}", escape(path));
+            match operation["operationId"].as_str() {
+                Some(id) => {
+                    if let Some(first) = operation_ids.insert(id, pointer.clone()) {
+                        diagnostics.push(Diagnostic::warning(
+                            &pointer,
+                            format!("`operationId` `{id}` is also used by {first}"),
+                        ));
+                    }
+                }
+                None => diagnostics.push(Diagnostic::warning(&pointer, "missing `operationId`")),
+            }
+            if operation["summary"].as_str().is_none_or(str::is_empty) {
+                diagnostics.push(Diagnostic::warning(&pointer, "missing `summary`"));
+            }
+            if operation["tags"].as_array().is_none_or(Vec::is_empty) {
+                diagnostics.push(Diagnostic::warning(&pointer, "missing `tags`"));
+            }
+        }
+    }
+
+    let (schemas, prefix) = match document["components"]["schemas"].as_object() {
+        Some(schemas) => (Some(schemas), "#/components/schemas/"),
+        None => (document["definitions"].as_object(), "#/definitions/"),
+    };
+    let mut references = BTreeSet::new();
+    collect_refs(&document, &mut references);
+    for name in schemas.into_iter().flatten().map(|(name, _)| name) {
+        if !references.contains(format!("{prefix}{}", escape(name)).as_str()) {
+            diagnostics.push(Diagnostic::warning(
+                format!("{}{}", &prefix[1..], escape(name)),
+                "schema is never referenced",
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Returns the operations of a path item with their methods.
+fn operations(item: &Value) -> impl Iterator<Item = (&'static str, &Value)> {
+    METHODS
+        .into_iter()
+        .filter_map(|method| item.get(method).map(|operation| (method, operation)))
+}
+
+/// Checks that every `{parameter}` in `path` is declared as a path parameter.
+fn check_path_parameters(
+    document: &Value,
+    path: &str,
+    item: &Value,
+    operation: &Value,
+    pointer: &str,
+) -> Vec<Diagnostic> {
+    let declared: BTreeSet<&str> = [&item["parameters"], &operation["parameters"]]
+        .into_iter()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|parameter| resolve(document, parameter))
+        .filter(|parameter| parameter["in"] == "path")
+        .filter_map(|parameter| parameter["name"].as_str())
+        .collect();
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| name)
+        .filter(|name| !declared.contains(name))
+        .map(|name| Diagnostic::error(pointer, format!("path parameter `{name}` is not declared")))
+        .collect()
+}
+
+/// Reports every local `$ref` below `value` that doesn't resolve.
+fn check_refs(document: &Value, value: &Value, pointer: &mut String, out: &mut Vec<Diagnostic>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+                let resolves = match reference.strip_prefix('#') {
+                    Some(target) => document.pointer(target).is_some(),
+                    // external references are resolved by the viewer
+                    None => true,
+                };
+                if !resolves {
+                    out.push(Diagnostic::error(
+                        pointer.clone(),
+                        format!("`$ref` `{reference}` does not resolve"),
+                    ));
+                }
+            }
+            for (key, value) in object {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&escape(key));
+                check_refs(document, value, pointer, out);
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push_str(&format!("/{index}"));
+                check_refs(document, value, pointer, out);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_refs<'a>(value: &'a Value, out: &mut BTreeSet<&'a str>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+                out.insert(reference);
+            }
+            object.values().for_each(|value| collect_refs(value, out));
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_refs(value, out)),
+        _ => {}
+    }
+}
+
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix('#'))
+    {
+        Some(pointer) => document.pointer(pointer).unwrap_or(&Value::Null),
+        None => value,
+    }
+}
+
+/// Escapes `key` for a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint, validate, Diagnostic, Severity};
+    use crate::Spec;
+
+    fn spec(content: &'static str) -> Spec {
+        Spec::builder().json(content)
+    }
+
+    #[test]
+    fn validates_the_structure() {
+        assert!(validate(&crate::swagger_spec_file!("../examples/openapi.json")).is_empty());
+
+        let diagnostics = validate(&spec(
+            r##"{
+                "openapi": "3.0.3",
+                "info": {"title": "Pets"},
+                "paths": {
+                    "/pets/{id}": {"get": {"responses": {"200": {"$ref": "#/components/responses/Pet"}}}},
+                    "/health": {"get": {}}
+                }
+            }"##,
+        ));
+        let messages: Vec<String> = diagnostics.iter().map(Diagnostic::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "error: /info: missing `version`",
+                "error: /paths/~1health/get: missing `responses`",
+                "error: /paths/~1pets~1{id}/get: path parameter `id` is not declared",
+                "error: /paths/~1pets~1{id}/get/responses/200: `$ref` `#/components/responses/Pet` does not resolve",
+            ]
+        );
+
+        assert_eq!(validate(&spec("openapi: 3.0.3")).len(), 1);
+    }
+
+    #[test]
+    fn lints_style() {
+        let diagnostics = lint(&spec(
+            r##"{
+                "openapi": "3.0.3",
+                "info": {"title": "Pets", "version": "1", "description": "Pets"},
+                "paths": {
+                    "/a": {"get": {"operationId": "same", "summary": "A", "tags": ["a"], "responses": {}}},
+                    "/b": {"get": {"operationId": "same", "responses": {}}}
+                },
+                "components": {"schemas": {"Unused": {"type": "string"}}}
+            }"##,
+        ));
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+        let messages: Vec<String> = diagnostics.iter().map(Diagnostic::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning: /paths/~1b/get: `operationId` `same` is also used by /paths/~1a/get",
+                "warning: /paths/~1b/get: missing `summary`",
+                "warning: /paths/~1b/get: missing `tags`",
+                "warning: /components/schemas/Unused: schema is never referenced",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_webhooks() {
+        let webhooks = r##"{
+            "openapi": "3.1.0",
+            "info": {"title": "Pets", "version": "1"},
+            "webhooks": {"newPet": {"post": {"operationId": "newPet", "requestBody": {
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+            }}}},
+            "components": {"schemas": {"Pet": {"type": "object"}}}
+        }"##;
+        assert!(validate(&spec(webhooks)).is_empty());
+        let messages: Vec<String> = lint(&spec(webhooks))
+            .iter()
+            .map(Diagnostic::to_string)
+            .collect();
+        assert!(messages.contains(&"warning: /webhooks/newPet/post: missing `summary`".to_string()));
+
+        let diagnostics = validate(&spec(
+            r#"{"openapi": "3.0.3", "info": {"title": "Pets", "version": "1"}, "paths": {},
+                "webhooks": {"newPet": true}}"#,
+        ));
+        let messages: Vec<String> = diagnostics.iter().map(Diagnostic::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "error: /webhooks: `webhooks` requires OpenAPI 3.1",
+                "error: /webhooks/newPet: must be a path item object",
+            ]
+        );
+    }
+}
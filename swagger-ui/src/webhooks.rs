@@ -0,0 +1,97 @@
+//! Surfacing OpenAPI 3.1 webhooks in viewers that only show paths.
+
+use crate::markdown::METHODS;
+use crate::Spec;
+use serde_json::{json, Map, Value};
+
+/// Returns `spec` with a copy of every webhook operation under `/webhooks/{name}` in its paths,
+/// tagged `tag` only, or `None` if the spec isn't JSON.
+///
+/// Viewers that ignore the `webhooks` section then list the webhooks under their own tag, which
+/// is declared with a description unless the spec already declares it. The copies are marked
+/// with `x-webhook: true` and the `webhooks` section is kept as it is. Specs without webhooks
+/// are returned unchanged.
+pub fn webhooks_as_tag(spec: &Spec, tag: &str) -> Option<Spec> {
+    let mut document: Value = serde_json::from_slice(&spec.content).ok()?;
+    let Some(webhooks) = document["webhooks"].as_object().filter(|w| !w.is_empty()) else {
+        return Some(spec.clone());
+    };
+
+    let mut items = Map::new();
+    for (name, item) in webhooks {
+        let mut copy = Map::new();
+        for method in METHODS {
+            let Some(mut operation) = item.get(method).cloned() else {
+                continue;
+            };
+            operation["tags"] = json!([tag]);
+            operation["x-webhook"] = Value::Bool(true);
+            copy.insert(method.to_string(), operation);
+        }
+        if !copy.is_empty() {
+            items.insert(format!("/webhooks/{name}"), Value::Object(copy));
+        }
+    }
+
+    if !document["paths"].is_object() {
+        document["paths"] = json!({});
+    }
+    let paths = document["paths"]
+        .as_object_mut()
+        .expect("paths are an object");
+    for (path, item) in items {
+        paths.entry(path).or_insert(item);
+    }
+    if !document["tags"].is_array() {
+        document["tags"] = json!([]);
+    }
+    let tags = document["tags"].as_array_mut().expect("tags are an array");
+    if !tags.iter().any(|declared| declared["name"] == tag) {
+        tags.push(json!({
+            "name": tag,
+            "description": "Requests this API sends to subscribers, see `webhooks`.",
+        }));
+    }
+
+    let content = serde_json::to_vec(&document).expect("Could not serialize spec as JSON.");
+    Some(Spec::builder().name(spec.name.clone()).json(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::webhooks_as_tag;
+    use crate::Spec;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn copies_webhooks_into_a_tag() {
+        let spec = Spec::builder().json(
+            serde_json::to_vec(&json!({
+                "openapi": "3.1.0",
+                "paths": {"/pets": {"get": {"tags": ["pets"]}}},
+                "webhooks": {"newPet": {"post": {"tags": ["pets"], "summary": "A pet was added"}}}
+            }))
+            .unwrap(),
+        );
+        let spec = webhooks_as_tag(&spec, "Webhooks").unwrap();
+        let document: Value = serde_json::from_slice(&spec.content).unwrap();
+
+        let copy = &document["paths"]["/webhooks/newPet"]["post"];
+        assert_eq!(copy["tags"], json!(["Webhooks"]));
+        assert_eq!(copy["summary"], "A pet was added");
+        assert_eq!(copy["x-webhook"], true);
+        assert_eq!(document["paths"]["/pets"]["get"]["tags"], json!(["pets"]));
+        assert_eq!(
+            document["webhooks"]["newPet"]["post"]["tags"],
+            json!(["pets"])
+        );
+        assert_eq!(document["tags"][0]["name"], "Webhooks");
+
+        let plain = Spec::builder().json(r#"{"openapi": "3.1.0", "paths": {}}"#);
+        assert_eq!(
+            webhooks_as_tag(&plain, "Webhooks").unwrap().content,
+            plain.content
+        );
+        assert!(webhooks_as_tag(&Spec::builder().yaml("openapi: 3.1.0"), "Webhooks").is_none());
+    }
+}
@@ -0,0 +1,182 @@
+//! Swagger-ui for tide.
+//!
+//! ```rust,ignore
+//! let mut app = tide::new();
+//! app.at("/docs").nest(tide_swagger_ui::swagger_ui(spec, None));
+//! app.at("/hello").get(|_| async { Ok("hello") });
+//! ```
+//!
+//! Tide strips the prefix of nested servers without recording it, so the pages link to each
+//! other relatively and the ui is opened at `/docs/index.html`.
+
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use tide::{Body, Endpoint, Redirect, Request, Response, Server, StatusCode};
+
+/// creates a server that is configured to serve the specified spec and config with swagger_ui,
+/// meant to be nested with `Route::nest`.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Server<()> {
+    swagger_ui_service(SwaggerUiService::new(spec, config))
+}
+
+/// creates a server serving swagger_ui with an already configured service, e.g. one with
+/// [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service(service: SwaggerUiService) -> Server<()> {
+    let mut server = tide::new();
+    server.at("*").all(SwaggerUi { service });
+    server
+}
+
+/// An `Endpoint` serving swagger_ui, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create an endpoint serving the specified spec and config with swagger_ui.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self {
+            service: SwaggerUiService::new(spec, config),
+        }
+    }
+}
+
+#[tide::utils::async_trait]
+impl<State: Clone + Send + Sync + 'static> Endpoint<State> for SwaggerUi {
+    async fn call(&self, req: Request<State>) -> tide::Result {
+        Ok(handle(&self.service, &req))
+    }
+}
+
+/// Hands a request nested into a server over to the framework-agnostic service.
+fn handle<State>(service: &SwaggerUiService, req: &Request<State>) -> Response {
+    let url = req.url();
+
+    // without the mount point the page can't be pointed to the config absolutely, like the
+    // other integrations do, so point it there relative to `index.html`
+    let query = url.query().unwrap_or_default();
+    if url.path() == "/index.html" && !query.contains("configUrl=") {
+        let location = match query {
+            "" => "index.html?configUrl=swagger-ui-config.json".to_string(),
+            query => format!("index.html?{query}&configUrl=swagger-ui-config.json"),
+        };
+        return Redirect::permanent(location).into();
+    }
+
+    let uri = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    };
+    let mut builder = http::Request::builder()
+        .method(req.method().as_ref())
+        .uri(uri);
+    for (name, values) in req.iter() {
+        for value in values {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+    }
+    let Ok(mut core_req) = builder.body(()) else {
+        return Response::new(StatusCode::BadRequest);
+    };
+    core_req.extensions_mut().insert(MountPath(".".to_string()));
+
+    let (parts, body) = service.handle(&core_req).into_parts();
+    let mut response = Response::new(parts.status.as_u16());
+    for (name, value) in &parts.headers {
+        if let Ok(value) = value.to_str() {
+            response.append_header(name.as_str(), value);
+        }
+    }
+    let content_type = response.content_type();
+    response.set_body(Body::from_bytes(body.into_bytes().to_vec()));
+    // setting the body overrides the content type with the one of the body
+    if let Some(content_type) = content_type {
+        response.set_content_type(content_type);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use swagger_ui::Config;
+    use tide::http::{Method, Request, Response, Url};
+    use tide::StatusCode;
+
+    async fn get(app: &tide::Server<()>, path: &str) -> Response {
+        let url = Url::parse(&format!("http://localhost{path}")).unwrap();
+        app.respond(Request::new(Method::Get, url)).await.unwrap()
+    }
+
+    #[async_std::test]
+    async fn nested_server_serves_ui() {
+        let mut app = tide::new();
+        app.at("/docs").nest(swagger_ui(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        ));
+        app.at("/hello").get(|_| async { Ok("hello") });
+
+        let res = get(&app, "/docs/index.html").await;
+        assert_eq!(res.status(), StatusCode::PermanentRedirect);
+        assert_eq!(
+            res["Location"],
+            "index.html?configUrl=swagger-ui-config.json"
+        );
+
+        let res = get(&app, "/docs/index.html?configUrl=swagger-ui-config.json").await;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res["Content-Type"], "text/html");
+
+        let mut res = get(&app, "/docs/swagger-ui-config.json").await;
+        let config: Config = serde_json::from_str(&res.body_string().await.unwrap()).unwrap();
+        assert_eq!(config.url, "./openapi.json");
+
+        let mut res = get(&app, "/docs/openapi.json").await;
+        assert_eq!(
+            res.body_string().await.unwrap(),
+            include_str!("../../swagger-ui/examples/openapi.json")
+        );
+
+        let mut res = get(&app, "/hello").await;
+        assert_eq!(res.body_string().await.unwrap(), "hello");
+    }
+
+    #[async_std::test]
+    async fn twice_nested_server_serves_index_spec_and_assets() {
+        let mut api = tide::new();
+        api.at("/v1/docs").nest(swagger_ui(
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        ));
+        let mut app = tide::new();
+        app.at("/api").nest(api);
+
+        let res = get(&app, "/api/v1/docs/index.html").await;
+        assert_eq!(
+            res["Location"],
+            "index.html?configUrl=swagger-ui-config.json"
+        );
+
+        let res = get(
+            &app,
+            "/api/v1/docs/index.html?configUrl=swagger-ui-config.json",
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res["Content-Type"], "text/html");
+
+        let mut res = get(&app, "/api/v1/docs/openapi.json").await;
+        assert_eq!(
+            res.body_string().await.unwrap(),
+            include_str!("../../swagger-ui/examples/openapi.json")
+        );
+
+        let mut res = get(&app, "/api/v1/docs/swagger-ui.css").await;
+        assert_eq!(res["Content-Type"], "text/css");
+        assert_eq!(
+            res.body_bytes().await.unwrap(),
+            swagger_ui::Assets::get("swagger-ui.css").unwrap().as_ref()
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Swagger-ui for tiny_http, for synchronous servers without an async runtime.
+//!
+//! ```rust,ignore
+//! let docs = tiny_http_swagger_ui::swagger_ui("/docs", spec, None);
+//! for request in server.incoming_requests() {
+//!     if docs.serves(&request) {
+//!         docs.respond(request)?;
+//!         continue;
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use std::io::{self, Cursor};
+use swagger_ui::{Bytes, Config, MountPath, SpecOrUrl, SwaggerUiService};
+use tiny_http::{Header, Request, Response, StatusCode};
+
+/// creates a handler that is configured to serve the specified spec and config with swagger_ui
+/// under `path`.
+pub fn swagger_ui(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> SwaggerUi {
+    SwaggerUi::from_service(path, SwaggerUiService::new(spec, config))
+}
+
+/// Serves swagger_ui from a tiny_http server, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    path: String,
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving swagger_ui with an already configured service under `path`, e.g.
+    /// one with [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(path: &str, service: SwaggerUiService) -> Self {
+        let path = match path.trim_matches('/') {
+            "" => String::new(),
+            path => format!("/{path}"),
+        };
+        Self { path, service }
+    }
+
+    /// Returns whether swagger_ui responds to the request, so the server can handle everything
+    /// else itself.
+    pub fn serves(&self, req: &Request) -> bool {
+        let path = req.url().split('?').next().unwrap_or_default();
+        self.rest(path)
+            .is_some_and(|rest| self.service.serves(rest))
+    }
+
+    /// Respond to the request, with `404 Not Found` outside of `path`.
+    pub fn respond(&self, req: Request) -> io::Result<()> {
+        let response = self.response(&req);
+        req.respond(response)
+    }
+
+    /// Returns the response to the request, with `404 Not Found` outside of `path`.
+    ///
+    /// The body is a view into the content the service answered with, so embedded assets are
+    /// sent without copying them.
+    pub fn response(&self, req: &Request) -> Response<Cursor<Bytes>> {
+        let (path, query) = match req.url().split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (req.url(), None),
+        };
+        let Some(rest) = self.rest(path) else {
+            return empty(404);
+        };
+        let uri = match query {
+            Some(query) => format!("/{}?{query}", rest.trim_start_matches('/')),
+            None => format!("/{}", rest.trim_start_matches('/')),
+        };
+
+        let mut builder = http::Request::builder()
+            .method(req.method().as_str())
+            .uri(uri);
+        for header in req.headers() {
+            builder = builder.header(header.field.as_str().as_str(), header.value.as_str());
+        }
+        let Ok(mut core_req) = builder.body(()) else {
+            return empty(400);
+        };
+        core_req
+            .extensions_mut()
+            .insert(MountPath(self.path.clone()));
+
+        let (parts, body) = self.service.handle(&core_req).into_parts();
+        let headers = parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Header::from_bytes(name.as_str(), value.as_bytes()).ok())
+            .collect();
+        let body = body.into_bytes();
+        let length = body.len();
+        Response::new(
+            StatusCode(parts.status.as_u16()),
+            headers,
+            Cursor::new(body),
+            Some(length),
+            None,
+        )
+    }
+
+    /// Returns the part of `path` below the mount point.
+    fn rest<'a>(&self, path: &'a str) -> Option<&'a str> {
+        let rest = path.strip_prefix(self.path.as_str())?;
+        (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+    }
+}
+
+fn empty(status: u16) -> Response<Cursor<Bytes>> {
+    Response::new(
+        StatusCode(status),
+        vec![],
+        Cursor::new(Bytes::new()),
+        Some(0),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use swagger_ui::Config;
+    use tiny_http::{Response, Server};
+
+    /// Sends a request to `addr` over a fresh connection and returns the raw response.
+    fn get(addr: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_ui_and_leaves_other_requests() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap().to_string();
+        let docs = swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                if docs.serves(&request) {
+                    docs.respond(request).unwrap();
+                } else {
+                    request.respond(Response::from_string("hello")).unwrap();
+                }
+            }
+        });
+
+        let response = get(&addr, "/docs");
+        assert!(response.starts_with("HTTP/1.1 308"));
+        assert!(response.contains("location: /docs/index.html\r\n"));
+
+        let response = get(&addr, "/docs/index.html");
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("content-type: text/html\r\n"));
+
+        let response = get(&addr, "/docs/swagger-ui-config.json");
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let config: Config = serde_json::from_str(body).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let response = get(&addr, "/hello");
+        assert!(response.ends_with("\r\n\r\nhello"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Swagger-ui for trillium.
+//!
+//! ```rust,ignore
+//! let router = Router::new()
+//!     .all("/docs/*", trillium_swagger_ui::swagger_ui(spec, None))
+//!     .get("/hello", "hello");
+//! ```
+
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use trillium::{async_trait, Conn, Handler, KnownHeaderName, Status};
+
+/// creates a handler that is configured to serve the specified spec and config with swagger_ui,
+/// meant to be routed with a wildcard, e.g. `Router::new().all("/docs/*", swagger_ui(..))`.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> SwaggerUi {
+    SwaggerUi::new(spec, config)
+}
+
+/// A `Handler` serving swagger_ui, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving the specified spec and config with swagger_ui.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self::from_service(SwaggerUiService::new(spec, config))
+    }
+
+    /// Create a handler serving swagger_ui with an already configured service, e.g. one with
+    /// [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(service: SwaggerUiService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Handler for SwaggerUi {
+    async fn run(&self, conn: Conn) -> Conn {
+        handle(&self.service, conn)
+    }
+}
+
+/// Hands a conn over to the framework-agnostic service.
+fn handle(service: &SwaggerUiService, mut conn: Conn) -> Conn {
+    // routing a wildcard makes the part of the path it matched the path of the conn, while the
+    // original path stays on the inner conn
+    let rest = format!("/{}", conn.path().trim_start_matches('/'));
+    let mount = MountPath::from_original(conn.inner().path(), &rest);
+    let uri = match conn.querystring() {
+        "" => rest,
+        query => format!("{rest}?{query}"),
+    };
+
+    let mut builder = http::Request::builder()
+        .method(conn.method().as_ref())
+        .uri(uri);
+    for (name, values) in conn.request_headers() {
+        for value in values {
+            builder = builder.header(name.as_ref(), value.as_ref());
+        }
+    }
+    let Ok(mut core_req) = builder.body(()) else {
+        return conn.with_status(Status::BadRequest).halt();
+    };
+    core_req.extensions_mut().insert(mount);
+
+    let (parts, body) = service.handle(&core_req).into_parts();
+    for (name, value) in &parts.headers {
+        if let Ok(value) = value.to_str() {
+            conn.response_headers_mut()
+                .append(name.as_str().to_string(), value.to_string());
+        }
+    }
+    // trillium sets the length of the body itself
+    conn.response_headers_mut()
+        .remove(KnownHeaderName::ContentLength);
+    conn.with_status(parts.status.as_u16())
+        .with_body(body.into_bytes().to_vec())
+        .halt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use swagger_ui::Config;
+    use trillium_router::Router;
+    use trillium_testing::prelude::*;
+
+    #[test]
+    fn routed_handler_serves_ui() {
+        let router = Router::new()
+            .all(
+                "/docs/*",
+                swagger_ui(
+                    swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                    None,
+                ),
+            )
+            .get("/hello", "hello");
+
+        let conn = get("/docs").on(&router);
+        assert_status!(&conn, 308);
+        assert_headers!(&conn, "location" => "/docs/index.html");
+
+        let conn = get("/docs/index.html").on(&router);
+        assert_ok!(&conn);
+        assert_headers!(&conn, "content-type" => "text/html");
+
+        let mut conn = get("/docs/swagger-ui-config.json").on(&router);
+        let config: Config =
+            serde_json::from_str(&conn.take_response_body_string().unwrap()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        assert_ok!(
+            get("/docs/openapi.json").on(&router),
+            include_str!("../../swagger-ui/examples/openapi.json")
+        );
+
+        assert_ok!(get("/hello").on(&router), "hello");
+    }
+}
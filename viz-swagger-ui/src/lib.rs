@@ -0,0 +1,132 @@
+//! Swagger-ui for viz.
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .nest("/docs", viz_swagger_ui::swagger_ui(spec, None))
+//!     .get("/hello", hello);
+//! ```
+
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use viz::{async_trait, Body, Handler, Request, RequestExt, Response, Result, Router};
+
+/// creates a router that is configured to serve the specified spec and config with swagger_ui,
+/// meant to be nested with `Router::nest`.
+pub fn swagger_ui(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Router {
+    swagger_ui_service(SwaggerUiService::new(spec, config))
+}
+
+/// creates a router serving swagger_ui with an already configured service, e.g. one with
+/// [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service(service: SwaggerUiService) -> Router {
+    let handler = SwaggerUi { service };
+    Router::new()
+        .any("", handler.clone())
+        .any(":rest*", handler)
+}
+
+/// A `Handler` serving swagger_ui, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving the specified spec and config with swagger_ui.
+    pub fn new(spec: impl Into<SpecOrUrl>, config: impl Into<Option<Config>>) -> Self {
+        Self {
+            service: SwaggerUiService::new(spec, config),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<Request> for SwaggerUi {
+    type Output = Result<Response>;
+
+    async fn call(&self, mut req: Request) -> Self::Output {
+        // viz doesn't strip the prefix of nested routers, the path below it is the `rest` parameter
+        let rest = format!("/{}", req.param::<String>("rest").unwrap_or_default());
+        let mount = MountPath::from_original(req.path(), &rest);
+        let uri = match req.query_string() {
+            Some(query) => format!("{rest}?{query}"),
+            None => rest,
+        };
+        *req.uri_mut() = uri.parse().map_err(viz::Error::boxed)?;
+        req.extensions_mut().insert(mount);
+
+        // viz shares the `http` types with the core service, so only the body is converted
+        Ok(self.service.handle(&req).map(Body::wrap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use std::sync::Arc;
+    use swagger_ui::Config;
+    use viz::header::{CONTENT_TYPE, LOCATION};
+    use viz::types::RouteInfo;
+    use viz::{Body, Handler, HttpBody, Method, Request, Response, Router, StatusCode, Tree};
+
+    /// Routes a request like viz's `Responder` does.
+    async fn get(tree: &Tree, uri: &str) -> Response {
+        let mut req = Request::get(uri).body(Body::Empty).unwrap();
+        let (handler, route) = tree.find(&Method::GET, uri).unwrap();
+        req.extensions_mut().insert(Arc::new(RouteInfo {
+            id: *route.id,
+            pattern: route.pattern(),
+            params: route.params().into(),
+        }));
+        let handler = handler.clone();
+        handler.call(req).await.unwrap()
+    }
+
+    async fn body(res: Response) -> Vec<u8> {
+        let mut body = res.into_body();
+        let mut bytes = Vec::new();
+        while let Some(frame) =
+            std::future::poll_fn(|cx| std::pin::Pin::new(&mut body).poll_frame(cx)).await
+        {
+            if let Ok(data) = frame.unwrap().into_data() {
+                bytes.extend_from_slice(&data);
+            }
+        }
+        bytes
+    }
+
+    #[tokio::test]
+    async fn nested_router_serves_ui() {
+        let tree = Tree::from(
+            Router::new()
+                .nest(
+                    "/docs",
+                    swagger_ui(
+                        swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+                        None,
+                    ),
+                )
+                .get("/hello", |_| async { Ok("hello") }),
+        );
+
+        let res = get(&tree, "/docs").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()[LOCATION], "/docs/index.html");
+
+        let res = get(&tree, "/docs/index.html").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/html");
+
+        let res = get(&tree, "/docs/swagger-ui-config.json").await;
+        let config: Config = serde_json::from_slice(&body(res).await).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let res = get(&tree, "/docs/openapi.json").await;
+        assert_eq!(
+            body(res).await,
+            include_bytes!("../../swagger-ui/examples/openapi.json")
+        );
+
+        let res = get(&tree, "/hello").await;
+        assert_eq!(body(res).await, b"hello");
+    }
+}
@@ -0,0 +1,164 @@
+//! Swagger-ui for warp.
+//!
+//! ```rust,ignore
+//! let docs = warp_swagger_ui::swagger_ui_filter("/docs", spec, None);
+//! warp::serve(docs.or(api)).run(([127, 0, 0, 1], 3030)).await;
+//! ```
+
+use futures_util::stream;
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use warp::filters::path::FullPath;
+use warp::http::header::CONTENT_LENGTH;
+use warp::http::{HeaderMap, Method, Request};
+use warp::reply::Response;
+use warp::{Filter, Rejection, Reply};
+
+/// creates a filter that serves the specified spec and config with swagger_ui under `path`.
+///
+/// Requests for paths swagger_ui doesn't respond to are rejected, so the filter can be combined
+/// with the other routes of the application with `or`.
+pub fn swagger_ui_filter(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    swagger_ui_service_filter(path, SwaggerUiService::new(spec, config))
+}
+
+/// creates a filter serving swagger_ui with an already configured service under `path`, e.g. one
+/// with [`SwaggerUiService::with_cors`] applied.
+pub fn swagger_ui_service_filter(
+    path: &str,
+    service: SwaggerUiService,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    let mount: Arc<str> = match path.trim_matches('/') {
+        "" => "".into(),
+        path => format!("/{path}").into(),
+    };
+    let query = warp::query::raw().or(warp::any().map(String::new)).unify();
+    warp::path::full()
+        .and(warp::method())
+        .and(warp::header::headers_cloned())
+        .and(query)
+        .and_then(
+            move |full: FullPath, method: Method, headers: HeaderMap, query: String| {
+                handle(&service, &mount, full.as_str(), method, headers, &query)
+            },
+        )
+}
+
+/// Hands a request below `mount` over to the framework-agnostic service.
+fn handle(
+    service: &SwaggerUiService,
+    mount: &str,
+    path: &str,
+    method: Method,
+    headers: HeaderMap,
+    query: &str,
+) -> Ready<Result<Response, Rejection>> {
+    let Some(rest) = strip_prefix(service, mount, path) else {
+        return ready(Err(warp::reject::not_found()));
+    };
+    let uri = match query {
+        "" => format!("/{}", rest.trim_start_matches('/')),
+        query => format!("/{}?{query}", rest.trim_start_matches('/')),
+    };
+    let Ok(mut req) = Request::builder().method(method).uri(uri).body(()) else {
+        return ready(Err(warp::reject::not_found()));
+    };
+    *req.headers_mut() = headers;
+    req.extensions_mut().insert(MountPath(mount.to_string()));
+
+    ready(Ok(into_response(service.handle(&req))))
+}
+
+/// Returns the part of `path` below `mount`, if swagger_ui responds to it.
+fn strip_prefix<'a>(service: &SwaggerUiService, mount: &str, path: &'a str) -> Option<&'a str> {
+    let rest = path.strip_prefix(mount)?;
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return None;
+    }
+    service.serves(rest).then_some(rest)
+}
+
+/// Converts a response of the core service, streaming the body without copying it.
+fn into_response(response: warp::http::Response<swagger_ui::Body>) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let chunks = body.into_chunks().collect::<Vec<_>>();
+    let length = chunks.iter().map(|chunk| chunk.len()).sum::<usize>();
+    parts.headers.entry(CONTENT_LENGTH).or_insert(length.into());
+
+    let mut response =
+        warp::reply::stream(stream::iter(chunks.into_iter().map(Ok::<_, Infallible>)))
+            .into_response();
+    *response.status_mut() = parts.status;
+    *response.headers_mut() = parts.headers;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui_filter;
+    use swagger_ui::Config;
+    use warp::http::header::{CONTENT_TYPE, LOCATION};
+    use warp::http::StatusCode;
+    use warp::Filter;
+
+    #[tokio::test]
+    async fn filter_serves_ui() {
+        let docs = swagger_ui_filter(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+
+        let response = warp::test::request().path("/docs").reply(&docs).await;
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+
+        let response = warp::test::request()
+            .path("/docs/index.html")
+            .reply(&docs)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+        let response = warp::test::request()
+            .path("/docs/swagger-ui-config.json")
+            .reply(&docs)
+            .await;
+        let config: Config = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        let response = warp::test::request()
+            .path("/docs/openapi.json")
+            .reply(&docs)
+            .await;
+        assert_eq!(
+            response.body().as_ref(),
+            include_bytes!("../../swagger-ui/examples/openapi.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn filter_rejects_other_paths() {
+        let app = swagger_ui_filter(
+            "/",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        )
+        .or(warp::path("hello").map(|| "hello"));
+
+        let response = warp::test::request().path("/index.html").reply(&app).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = warp::test::request().path("/hello").reply(&app).await;
+        assert_eq!(response.body().as_ref(), b"hello");
+
+        let response = warp::test::request().path("/missing").reply(&app).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,134 @@
+//! Swagger-ui for Cloudflare Workers, serving the embedded assets and spec from the edge.
+//!
+//! ```rust,ignore
+//! #[event(fetch)]
+//! async fn fetch(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
+//!     let docs = workers_swagger_ui::swagger_ui("/docs", spec, None);
+//!     if docs.serves(&req) {
+//!         return docs.handle(&req);
+//!     }
+//!     Response::error("Not Found", 404)
+//! }
+//! ```
+
+use swagger_ui::{Config, MountPath, SpecOrUrl, SwaggerUiService};
+use worker::{Headers, Request, Response, Result, Url};
+
+/// creates a handler that is configured to serve the specified spec and config with swagger_ui
+/// under `path`.
+pub fn swagger_ui(
+    path: &str,
+    spec: impl Into<SpecOrUrl>,
+    config: impl Into<Option<Config>>,
+) -> SwaggerUi {
+    SwaggerUi::from_service(path, SwaggerUiService::new(spec, config))
+}
+
+/// Serves swagger_ui from a Worker, see [`swagger_ui`].
+#[derive(Clone)]
+pub struct SwaggerUi {
+    path: String,
+    service: SwaggerUiService,
+}
+
+impl SwaggerUi {
+    /// Create a handler serving swagger_ui with an already configured service under `path`, e.g.
+    /// one with [`SwaggerUiService::with_cors`] applied.
+    pub fn from_service(path: &str, service: SwaggerUiService) -> Self {
+        let path = match path.trim_matches('/') {
+            "" => String::new(),
+            path => format!("/{path}"),
+        };
+        Self { path, service }
+    }
+
+    /// Returns whether swagger_ui responds to the request, so the Worker can route everything
+    /// else elsewhere.
+    pub fn serves(&self, req: &Request) -> bool {
+        self.rest(&req.path())
+            .is_some_and(|rest| self.service.serves(rest))
+    }
+
+    /// Handle a request, responding with `404 Not Found` outside of `path`.
+    pub fn handle(&self, req: &Request) -> Result<Response> {
+        let Some(response) = self.respond(req.method().as_ref(), &req.url()?, req.headers()) else {
+            return Response::error("Not Found", 404);
+        };
+
+        let (parts, body) = response.into_parts();
+        let headers = Headers::new();
+        for (name, value) in &parts.headers {
+            if let Ok(value) = value.to_str() {
+                headers.append(name.as_str(), value)?;
+            }
+        }
+        Ok(Response::from_bytes(body.into_bytes().to_vec())?
+            .with_status(parts.status.as_u16())
+            .with_headers(headers))
+    }
+
+    /// Hands a request below `path` over to the framework-agnostic service.
+    fn respond(
+        &self,
+        method: &str,
+        url: &Url,
+        headers: impl IntoIterator<Item = (String, String)>,
+    ) -> Option<http::Response<swagger_ui::Body>> {
+        let rest = self.rest(url.path())?;
+        let uri = match url.query() {
+            Some(query) => format!("/{}?{query}", rest.trim_start_matches('/')),
+            None => format!("/{}", rest.trim_start_matches('/')),
+        };
+        let mut builder = http::Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        let mut req = builder.body(()).ok()?;
+        req.extensions_mut().insert(MountPath(self.path.clone()));
+        Some(self.service.handle(&req))
+    }
+
+    /// Returns the part of `path` below the mount point.
+    fn rest<'a>(&self, path: &'a str) -> Option<&'a str> {
+        let rest = path.strip_prefix(self.path.as_str())?;
+        (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::swagger_ui;
+    use http::header::{CONTENT_TYPE, LOCATION};
+    use http::StatusCode;
+    use swagger_ui::Config;
+    use worker::Url;
+
+    // `worker::Request` and `Response` are backed by javascript, so only the conversion to the
+    // core service is tested natively
+    #[test]
+    fn responds_below_path() {
+        let docs = swagger_ui(
+            "/docs",
+            swagger_ui::swagger_spec_file!("../../swagger-ui/examples/openapi.json"),
+            None,
+        );
+        let get = |path: &str| {
+            let url = Url::parse(&format!("https://example.com{path}")).unwrap();
+            docs.respond("GET", &url, [("accept".to_string(), "*/*".to_string())])
+        };
+
+        let response = get("/docs").unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/docs/index.html");
+
+        let response = get("/docs/index.html").unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+
+        let response = get("/docs/swagger-ui-config.json?expand=full").unwrap();
+        let config: Config = serde_json::from_slice(&response.into_body().into_bytes()).unwrap();
+        assert_eq!(config.url, "/docs/openapi.json");
+
+        assert!(get("/docsearch").is_none());
+        assert!(get("/hello").is_none());
+    }
+}